@@ -0,0 +1,65 @@
+//! Lexer throughput benchmarks
+//!
+//! Run with `cargo bench --bench lexer`. Each input is lexed repeatedly and its throughput
+//! reported in bytes per second
+//!
+//! To measure a change, save a baseline before making it and compare against that baseline
+//! after, on the same machine:
+//!
+//! ```text
+//! cargo bench --bench lexer -- --save-baseline before
+//! cargo bench --bench lexer -- --baseline before
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use feolang::lexer;
+use std::path::Path;
+
+/// Returns generated feolang source of at least `size` bytes, mixing declarations, comments,
+/// number and text literals and operators in roughly the proportions of hand-written code
+///
+fn generate(size: usize) -> String {
+    let mut buf = String::with_capacity(size + 256);
+    let mut i: usize = 0;
+
+    while buf.len() < size {
+        buf.push_str(&format!(
+            "// declaration number {i}\n\
+             let value_{i}: int = {i} * 0x1F + (other_{i} - 1_000) % 7;\n\
+             var ratio_{i}: float = -{i}.25 / 3.5;\n\
+             const name_{i}: String = \"item \\\"{i}\\\"\\n\";\n\
+             /* a block comment\n   spanning two lines */ let c_{i}: char = 'x';\n\
+             if a_{i} >= b_{i} && !done {{ total += items[{i}].len(); }}\n"
+        ));
+
+        i += 1;
+    }
+
+    buf
+}
+
+fn lex(c: &mut Criterion) {
+    let path = Path::new("bench.feo");
+    let inputs = [
+        (
+            "small",
+            std::fs::read_to_string("./examples/ex01.feo").unwrap(),
+        ),
+        ("medium", generate(64 << 10)),
+        ("large", generate(4 << 20)),
+    ];
+
+    let mut group = c.benchmark_group("lex");
+
+    for (name, src) in &inputs {
+        group.throughput(Throughput::Bytes(src.len() as u64));
+        group.bench_function(*name, |b| {
+            b.iter(|| lexer::lex(black_box(src), path).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, lex);
+criterion_main!(benches);
@@ -0,0 +1,30 @@
+#![no_main]
+
+use feolang::lexer;
+use libfuzzer_sys::fuzz_target;
+use std::path::Path;
+
+// `lex` must never panic: malformed input is reported as a `FeoError`. Any input it accepts
+// must also survive a round trip through the token printer
+fuzz_target!(|data: &[u8]| {
+    let Ok(src) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let path = Path::new("fuzz.feo");
+
+    if let Ok(tokens) = lexer::lex(src, path) {
+        let printed = lexer::print(&tokens);
+        let relexed = lexer::lex(&printed, path).expect("printed tokens failed to lex");
+
+        let tok_types = |lines: &[Vec<lexer::token::Token>]| {
+            lines
+                .iter()
+                .flatten()
+                .map(|t| t.tok_type().to_string())
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(tok_types(&tokens), tok_types(&relexed));
+    }
+});
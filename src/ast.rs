@@ -0,0 +1,46 @@
+use crate::span::Span;
+
+pub mod class;
+pub mod dump;
+pub mod expr;
+pub mod fold;
+pub mod item;
+pub mod pattern;
+pub mod print;
+pub mod protocol;
+pub mod stmt;
+pub mod ty;
+pub mod visit;
+pub mod visit_mut;
+
+pub use class::{ClassDecl, ClassField, Constructor, Member, MemberKind, Modifiers};
+pub use dump::{to_json, to_sexpr};
+pub use expr::{AssignOp, BinaryOp, Expr, ExprKind, FieldInit, Literal, MatchArm, UnaryOp};
+pub use fold::Fold;
+pub use item::{
+    BindingKind, EnumDecl, Field, FnDecl, ImportKind, ImportTree, Item, ItemKind, Param,
+    StructDecl, TypeAlias, VarDecl, Variant, VariantKind,
+};
+pub use pattern::{FieldPattern, Pattern, PatternKind};
+pub use print::print;
+pub use protocol::{AssocType, ProtocolDecl, ProtocolMember, ProtocolMemberKind};
+pub use stmt::{Block, Stmt, StmtKind};
+pub use ty::{GenericParam, Type, TypeKind};
+pub use visit::Visitor;
+pub use visit_mut::VisitorMut;
+
+/// The root of the syntax tree of a source file: a list of top-level items
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module {
+    pub items: Vec<Item>,
+    pub span: Span,
+}
+
+/// An identifier, along with its location in the source
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Iden {
+    pub name: String,
+    pub span: Span,
+}
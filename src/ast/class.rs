@@ -0,0 +1,76 @@
+use super::{Block, Expr, FnDecl, GenericParam, Iden, Param, Type, TypeAlias};
+use crate::span::Span;
+
+/// A class declaration, e.g. `abstract class Animal { ... }` or `final class Dog: Animal { ... }`
+///
+/// `supertypes` lists the superclass (if any) and the protocols the class conforms to, in
+/// source order. Which is which depends on what the names refer to, so it is left to the
+/// checker
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassDecl {
+    pub name: Iden,
+    pub generics: Vec<GenericParam>,
+    pub supertypes: Vec<Iden>,
+    pub members: Vec<Member>,
+    pub is_abstract: bool,
+    pub is_final: bool,
+}
+
+/// A field, constructor, method or associated type binding of a class, along with its
+/// modifiers. Structs and enums only have methods and associated type bindings
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub kind: MemberKind,
+    pub modifiers: Modifiers,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum MemberKind {
+    /// `name: String;` or `static count: int = 0;`
+    FIELD(ClassField),
+
+    /// `new(name: String) { ... }`
+    CONSTRUCTOR(Constructor),
+
+    /// `virtual func speak() -> String { ... }`, or `abstract func speak() -> String;`
+    METHOD(FnDecl),
+
+    /// `type Output = String;`, binding a protocol's associated type
+    TYPE(TypeAlias),
+
+    /// tokens skipped after a syntax error, up to the next member
+    ERROR,
+}
+
+/// A field of a class, with an optional initial value
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassField {
+    pub name: Iden,
+    pub ty: Type,
+    pub value: Option<Expr>,
+}
+
+/// A class constructor, invoked by `new ClassName(...)`
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constructor {
+    pub params: Vec<Param>,
+    pub body: Block,
+}
+
+/// The modifier keywords preceding a class member, in any order
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Modifiers {
+    pub is_public: bool,
+    pub is_static: bool,
+    pub is_abstract: bool,
+    pub is_virtual: bool,
+    pub is_override: bool,
+    pub is_final: bool,
+}
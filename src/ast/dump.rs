@@ -0,0 +1,658 @@
+use super::{
+    BindingKind, Block, Expr, ExprKind, FnDecl, GenericParam, Iden, ImportKind, ImportTree, Item,
+    ItemKind, Member, MemberKind, Modifiers, Module, Param, Pattern, PatternKind,
+    ProtocolMemberKind, Stmt, StmtKind, Type, TypeKind, VarDecl, VariantKind,
+};
+use crate::span::Span;
+
+/// Returns a syntax tree as an indented S-expression, one node per line, e.g.
+///
+/// ```text
+/// (func add @1:1-1:40
+///   (param @1:10-1:16
+///     (binding a @1:10-1:11)
+///     (int @1:13-1:16))
+///   ...
+/// ```
+///
+/// # Arguments
+///
+/// * `module` - the syntax tree of a file
+/// * `spans` - whether to write the span of each node (as `@line:col-end_line:end_col`)
+///
+pub fn to_sexpr(module: &Module, spans: bool) -> String {
+    let mut buf = String::new();
+    write_sexpr(&module_node(module), spans, 0, &mut buf);
+    buf.push('\n');
+
+    buf
+}
+
+/// Returns a syntax tree as (compact) JSON. Every node is an object with a `kind`, a `span`
+/// (`{"line": 1, "col": 1, "end_line": 1, "end_col": 5}`), any attributes of its own (e.g.
+/// `name` or `op`) and, if it has any, a list of `children`
+///
+/// # Arguments
+///
+/// * `module` - the syntax tree of a file
+///
+pub fn to_json(module: &Module) -> String {
+    let mut buf = String::new();
+    write_json(&module_node(module), &mut buf);
+
+    buf
+}
+
+/// A syntax tree node reduced to what the dumps show, so that both formats share one walk
+/// of the tree
+///
+struct Node {
+    kind: &'static str,
+    span: Span,
+    attrs: Vec<(&'static str, Attr)>,
+    children: Vec<Node>,
+}
+
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+enum Attr {
+    TEXT(String),
+
+    /// a keyword or modifier that is present (e.g. `extern`), written as its name
+    FLAG,
+}
+
+impl Node {
+    fn new(kind: &'static str, span: Span) -> Self {
+        Self {
+            kind,
+            span,
+            attrs: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn attr(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.attrs.push((key, Attr::TEXT(value.into())));
+        self
+    }
+
+    /// Adds a flag attribute if `is_set`
+    ///
+    fn flag(mut self, key: &'static str, is_set: bool) -> Self {
+        if is_set {
+            self.attrs.push((key, Attr::FLAG));
+        }
+
+        self
+    }
+
+    fn child(mut self, child: Node) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    fn children(mut self, children: impl IntoIterator<Item = Node>) -> Self {
+        self.children.extend(children);
+        self
+    }
+}
+
+fn write_sexpr(node: &Node, spans: bool, depth: usize, buf: &mut String) {
+    buf.push('(');
+    buf.push_str(node.kind);
+
+    for (key, value) in &node.attrs {
+        buf.push(' ');
+
+        match value {
+            Attr::TEXT(text) => buf.push_str(text),
+            Attr::FLAG => buf.push_str(key),
+        }
+    }
+
+    if spans {
+        let s: Span = node.span;
+        buf.push_str(&format!(
+            " @{}:{}-{}:{}",
+            s.line, s.col, s.end_line, s.end_col
+        ));
+    }
+
+    for child in &node.children {
+        buf.push('\n');
+        buf.push_str(&"  ".repeat(depth + 1));
+        write_sexpr(child, spans, depth + 1, buf);
+    }
+
+    buf.push(')');
+}
+
+fn write_json(node: &Node, buf: &mut String) {
+    let s: Span = node.span;
+
+    buf.push_str(&format!(
+        "{{\"kind\":{},\"span\":{{\"line\":{},\"col\":{},\"end_line\":{},\"end_col\":{}}}",
+        json_string(node.kind),
+        s.line,
+        s.col,
+        s.end_line,
+        s.end_col
+    ));
+
+    for (key, value) in &node.attrs {
+        match value {
+            Attr::TEXT(text) => buf.push_str(&format!(",\"{}\":{}", key, json_string(text))),
+            Attr::FLAG => buf.push_str(&format!(",\"{}\":true", key)),
+        }
+    }
+
+    if !node.children.is_empty() {
+        buf.push_str(",\"children\":[");
+
+        for (i, child) in node.children.iter().enumerate() {
+            if i > 0 {
+                buf.push(',');
+            }
+
+            write_json(child, buf);
+        }
+
+        buf.push(']');
+    }
+
+    buf.push('}');
+}
+
+/// Returns a string as a JSON string literal
+///
+fn json_string(text: &str) -> String {
+    let mut buf = String::from('"');
+
+    for c in text.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if (c as u32) < 0x20 => buf.push_str(&format!("\\u{:04x}", c as u32)),
+            c => buf.push(c),
+        }
+    }
+
+    buf.push('"');
+    buf
+}
+
+fn path(path: &[Iden]) -> String {
+    let names: Vec<&str> = path.iter().map(|i| i.name.as_str()).collect();
+    names.join(".")
+}
+
+/// Returns a list of names, e.g. the protocols in a conformance clause, as one attribute
+///
+fn names(names: &[Iden], separator: &str) -> String {
+    let names: Vec<&str> = names.iter().map(|i| i.name.as_str()).collect();
+    names.join(separator)
+}
+
+fn module_node(module: &Module) -> Node {
+    Node::new("module", module.span).children(module.items.iter().map(item))
+}
+
+fn item(item: &Item) -> Node {
+    let span: Span = item.span;
+
+    let node: Node = match &item.kind {
+        ItemKind::VAR(decl) => var_decl(decl, span),
+        ItemKind::FUNC(func) => fn_decl(func, span),
+
+        ItemKind::STRUCT(decl) => Node::new("struct", span)
+            .attr("name", &decl.name.name)
+            .children(generics(&decl.generics))
+            .children(conformance(&decl.protocols))
+            .children(decl.fields.iter().map(|f| {
+                Node::new("field", f.span)
+                    .attr("name", &f.name.name)
+                    .flag("public", f.is_public)
+                    .child(ty(&f.ty))
+            }))
+            .children(decl.members.iter().map(member)),
+
+        ItemKind::ENUM(decl) => Node::new("enum", span)
+            .attr("name", &decl.name.name)
+            .children(generics(&decl.generics))
+            .children(conformance(&decl.protocols))
+            .children(decl.variants.iter().map(|v| {
+                let node = Node::new("variant", v.span).attr("name", &v.name.name);
+
+                let node = match &v.kind {
+                    VariantKind::UNIT => node,
+                    VariantKind::TUPLE(types) => node.children(types.iter().map(ty)),
+                    VariantKind::STRUCT(fields) => node.children(fields.iter().map(|f| {
+                        Node::new("field", f.span)
+                            .attr("name", &f.name.name)
+                            .child(ty(&f.ty))
+                    })),
+                };
+
+                node.children(v.discriminant.iter().map(expr))
+            }))
+            .children(decl.members.iter().map(member)),
+
+        ItemKind::CLASS(decl) => Node::new("class", span)
+            .attr("name", &decl.name.name)
+            .flag("abstract", decl.is_abstract)
+            .flag("final", decl.is_final)
+            .children(generics(&decl.generics))
+            .children(conformance(&decl.supertypes))
+            .children(decl.members.iter().map(member)),
+
+        ItemKind::PROTOCOL(decl) => Node::new("protocol", span)
+            .attr("name", &decl.name.name)
+            .children(decl.members.iter().map(|m| match &m.kind {
+                ProtocolMemberKind::TYPE(assoc) => {
+                    let node = Node::new("assoc_type", m.span).attr("name", &assoc.name.name);
+
+                    match assoc.bounds.is_empty() {
+                        true => node,
+                        false => node.attr("bounds", names(&assoc.bounds, " + ")),
+                    }
+                }
+
+                ProtocolMemberKind::METHOD(func) => fn_decl(func, m.span),
+            })),
+
+        ItemKind::LIB(name) => Node::new("lib", span).attr("name", &name.name),
+
+        ItemKind::IMPORT(tree) => Node::new("import", span).attr("tree", import_tree(tree)),
+
+        ItemKind::ALIAS(alias) => Node::new("alias", span)
+            .attr("name", &alias.name.name)
+            .children(generics(&alias.generics))
+            .child(ty(&alias.ty)),
+
+        ItemKind::TYPE(alias) => Node::new("type", span)
+            .attr("name", &alias.name.name)
+            .children(generics(&alias.generics))
+            .child(ty(&alias.ty)),
+
+        ItemKind::ERROR => Node::new("error", span),
+    };
+
+    after_name(node, &[("public", item.is_public)])
+}
+
+/// Adds flags to a node after its name (if any), e.g. `(func main public @1:1-1:20)`
+///
+fn after_name(mut node: Node, flags: &[(&'static str, bool)]) -> Node {
+    let mut at: usize = node.attrs.iter().take_while(|(k, _)| *k == "name").count();
+
+    for (key, is_set) in flags {
+        if *is_set {
+            node.attrs.insert(at, (key, Attr::FLAG));
+            at += 1;
+        }
+    }
+
+    node
+}
+
+/// Returns an import tree as written, e.g. `a.b.{x, y as z}`
+///
+fn import_tree(tree: &ImportTree) -> String {
+    let mut text: String = path(&tree.path);
+
+    match &tree.kind {
+        ImportKind::SINGLE(Some(alias)) => text.push_str(&format!(" as {}", alias.name)),
+        ImportKind::SINGLE(None) => (),
+
+        ImportKind::GROUP { trees, alias } => {
+            let trees: Vec<String> = trees.iter().map(import_tree).collect();
+
+            if !tree.path.is_empty() {
+                text.push('.');
+            }
+
+            text.push_str(&format!("{{{}}}", trees.join(", ")));
+
+            if let Some(alias) = alias {
+                text.push_str(&format!(" as {}", alias.name));
+            }
+        }
+    }
+
+    text
+}
+
+fn conformance(names_: &[Iden]) -> Option<Node> {
+    match names_.is_empty() {
+        true => None,
+        false => Some(Node::new("conforms", span_of(names_)).attr("names", names(names_, ", "))),
+    }
+}
+
+/// Returns the span from the first to the last of a non-empty list of names
+///
+fn span_of(names: &[Iden]) -> Span {
+    names[0].span.to(names[names.len() - 1].span)
+}
+
+fn var_decl(decl: &VarDecl, span: Span) -> Node {
+    let binding: &str = match decl.binding {
+        BindingKind::CONST => "const",
+        BindingKind::LET => "let",
+        BindingKind::VAR => "var",
+    };
+
+    Node::new(binding, span)
+        .child(pattern(&decl.pattern))
+        .children(decl.ty.iter().map(ty))
+        .children(decl.value.iter().map(expr))
+}
+
+fn fn_decl(func: &FnDecl, span: Span) -> Node {
+    Node::new("func", span)
+        .attr("name", &func.name.name)
+        .flag("extern", func.is_extern)
+        .children(generics(&func.generics))
+        .children(func.params.iter().map(param))
+        .children(
+            func.ret
+                .iter()
+                .map(|r| Node::new("returns", r.span).child(ty(r))),
+        )
+        .children(func.body.iter().map(block))
+}
+
+fn generics(generics: &[GenericParam]) -> Vec<Node> {
+    generics
+        .iter()
+        .map(|g| {
+            let node = Node::new("generic", g.span).attr("name", &g.name.name);
+
+            match g.bounds.is_empty() {
+                true => node,
+                false => node.attr("bounds", names(&g.bounds, " + ")),
+            }
+        })
+        .collect()
+}
+
+fn param(param: &Param) -> Node {
+    Node::new("param", param.span)
+        .child(pattern(&param.pattern))
+        .child(ty(&param.ty))
+        .children(
+            param
+                .default
+                .iter()
+                .map(|d| Node::new("default", d.span).child(expr(d))),
+        )
+}
+
+fn member(member: &Member) -> Node {
+    let span: Span = member.span;
+
+    let node: Node = match &member.kind {
+        MemberKind::FIELD(field) => Node::new("field", span)
+            .attr("name", &field.name.name)
+            .child(ty(&field.ty))
+            .children(field.value.iter().map(expr)),
+
+        MemberKind::CONSTRUCTOR(constructor) => Node::new("constructor", span)
+            .children(constructor.params.iter().map(param))
+            .child(block(&constructor.body)),
+
+        MemberKind::METHOD(func) => fn_decl(func, span),
+
+        MemberKind::TYPE(alias) => Node::new("type", span)
+            .attr("name", &alias.name.name)
+            .child(ty(&alias.ty)),
+
+        MemberKind::ERROR => Node::new("error", span),
+    };
+
+    modifiers(node, &member.modifiers)
+}
+
+/// Adds a member's modifiers to its node, after its name
+///
+fn modifiers(node: Node, modifiers: &Modifiers) -> Node {
+    let flags: [(&'static str, bool); 6] = [
+        ("public", modifiers.is_public),
+        ("static", modifiers.is_static),
+        ("abstract", modifiers.is_abstract),
+        ("virtual", modifiers.is_virtual),
+        ("override", modifiers.is_override),
+        ("final", modifiers.is_final),
+    ];
+
+    after_name(node, &flags)
+}
+
+fn block(block: &Block) -> Node {
+    Node::new("block", block.span)
+        .children(block.stmts.iter().map(stmt))
+        .children(
+            block
+                .tail
+                .iter()
+                .map(|t| Node::new("tail", t.span).child(expr(t))),
+        )
+}
+
+fn stmt(stmt: &Stmt) -> Node {
+    match &stmt.kind {
+        StmtKind::VAR(decl) => var_decl(decl, stmt.span),
+        StmtKind::EXPR(e) => expr(e),
+        StmtKind::ERROR => Node::new("error", stmt.span),
+    }
+}
+
+fn label(node: Node, label: &Option<Iden>) -> Node {
+    match label {
+        Some(label) => node.attr("label", format!("{}:", label.name)),
+        None => node,
+    }
+}
+
+fn expr(e: &Expr) -> Node {
+    let span: Span = e.span;
+    let exprs = |exprs: &[Expr]| -> Vec<Node> { exprs.iter().map(expr).collect() };
+
+    match &e.kind {
+        ExprKind::LITERAL(lit) => Node::new("literal", span).attr("value", lit.to_string()),
+        ExprKind::IDEN(name) => Node::new("iden", span).attr("name", name),
+        ExprKind::SELF => Node::new("self", span),
+        ExprKind::SUPER => Node::new("super", span),
+        ExprKind::PAREN(inner) => Node::new("paren", span).child(expr(inner)),
+        ExprKind::TUPLE(elems) => Node::new("tuple", span).children(exprs(elems)),
+        ExprKind::ARRAY(elems) => Node::new("array", span).children(exprs(elems)),
+
+        ExprKind::ARRAY_REPEAT { value, len } => Node::new("array_repeat", span)
+            .child(expr(value))
+            .child(expr(len)),
+
+        ExprKind::BLOCK(b) => block(b),
+
+        ExprKind::UNARY { op, expr: inner } => Node::new("unary", span)
+            .attr("op", op.to_string())
+            .child(expr(inner)),
+
+        ExprKind::BINARY { op, lhs, rhs } => Node::new("binary", span)
+            .attr("op", op.to_string())
+            .child(expr(lhs))
+            .child(expr(rhs)),
+
+        ExprKind::TERNARY { cond, then, els } => Node::new("ternary", span)
+            .child(expr(cond))
+            .child(expr(then))
+            .child(expr(els)),
+
+        ExprKind::RANGE {
+            start,
+            end,
+            is_inclusive,
+        } => Node::new("range", span)
+            .attr("op", if *is_inclusive { "..=" } else { ".." })
+            .child(expr(start))
+            .child(expr(end)),
+
+        ExprKind::ASSIGN { op, target, value } => Node::new("assign", span)
+            .attr("op", op.to_string())
+            .child(expr(target))
+            .child(expr(value)),
+
+        ExprKind::FIELD { expr: inner, field } => Node::new("field", span)
+            .attr("name", &field.name)
+            .child(expr(inner)),
+
+        ExprKind::INDEX { expr: inner, index } => Node::new("index", span)
+            .child(expr(inner))
+            .child(expr(index)),
+
+        ExprKind::CALL { callee, args } => Node::new("call", span)
+            .child(expr(callee))
+            .children(exprs(args)),
+
+        ExprKind::IF { cond, then, els } => Node::new("if", span)
+            .child(expr(cond))
+            .child(block(then))
+            .children(els.iter().map(|e| Node::new("else", e.span).child(expr(e)))),
+
+        ExprKind::MATCH { scrutinee, arms } => Node::new("match", span)
+            .child(expr(scrutinee))
+            .children(arms.iter().map(|arm| {
+                Node::new("arm", arm.span)
+                    .child(pattern(&arm.pattern))
+                    .children(
+                        arm.guard
+                            .iter()
+                            .map(|g| Node::new("guard", g.span).child(expr(g))),
+                    )
+                    .child(expr(&arm.body))
+            })),
+
+        ExprKind::WHILE {
+            label: l,
+            cond,
+            body,
+        } => label(Node::new("while", span), l)
+            .child(expr(cond))
+            .child(block(body)),
+
+        ExprKind::LOOP { label: l, body } => label(Node::new("loop", span), l).child(block(body)),
+
+        ExprKind::FOR {
+            label: l,
+            pattern: p,
+            iter,
+            body,
+        } => label(Node::new("for", span), l)
+            .child(pattern(p))
+            .child(expr(iter))
+            .child(block(body)),
+
+        ExprKind::BREAK { label: l, value } => {
+            label(Node::new("break", span), l).children(value.iter().map(|v| expr(v)))
+        }
+
+        ExprKind::CONTINUE { label: l } => label(Node::new("continue", span), l),
+
+        ExprKind::RETURN(value) => {
+            Node::new("return", span).children(value.iter().map(|v| expr(v)))
+        }
+
+        ExprKind::NEW { class, args } => Node::new("new", span)
+            .attr("class", path(class))
+            .children(exprs(args)),
+
+        ExprKind::STRUCT { path: p, fields } => Node::new("struct", span)
+            .attr("path", path(p))
+            .children(fields.iter().map(|f| {
+                Node::new("field", f.span)
+                    .attr("name", &f.name.name)
+                    .child(expr(&f.value))
+            })),
+    }
+}
+
+fn pattern(p: &Pattern) -> Node {
+    let span: Span = p.span;
+    let patterns = |patterns: &[Pattern]| -> Vec<Node> { patterns.iter().map(pattern).collect() };
+
+    match &p.kind {
+        PatternKind::WILDCARD => Node::new("wildcard", span),
+        PatternKind::BINDING(name) => Node::new("binding", span).attr("name", &name.name),
+        PatternKind::LITERAL(lit) => Node::new("literal", span).attr("value", lit.to_string()),
+
+        PatternKind::RANGE {
+            start,
+            end,
+            is_inclusive,
+        } => Node::new("range", span)
+            .attr("start", start.to_string())
+            .attr("op", if *is_inclusive { "..=" } else { ".." })
+            .attr("end", end.to_string()),
+
+        PatternKind::TUPLE(elems) => Node::new("tuple", span).children(patterns(elems)),
+
+        PatternKind::VARIANT { path: path_, elems } => {
+            let node = Node::new("variant", span).attr("path", path(path_));
+
+            match elems {
+                Some(elems) => node.flag("tuple", true).children(patterns(elems)),
+                None => node,
+            }
+        }
+
+        PatternKind::STRUCT {
+            path: path_,
+            fields,
+            has_rest,
+        } => Node::new("struct", span)
+            .attr("path", path(path_))
+            .flag("rest", *has_rest)
+            .children(fields.iter().map(|f| {
+                Node::new("field", f.span)
+                    .attr("name", &f.name.name)
+                    .child(pattern(&f.pattern))
+            })),
+
+        PatternKind::OR(alts) => Node::new("or", span).children(patterns(alts)),
+    }
+}
+
+fn ty(t: &Type) -> Node {
+    let span: Span = t.span;
+    let types = |types: &[Type]| -> Vec<Node> { types.iter().map(ty).collect() };
+
+    match &t.kind {
+        TypeKind::BOOL => Node::new("bool", span),
+        TypeKind::CHAR => Node::new("char", span),
+        TypeKind::FLOAT => Node::new("float", span),
+        TypeKind::INT => Node::new("int", span),
+        TypeKind::STRING => Node::new("String", span),
+        TypeKind::UINT => Node::new("uint", span),
+
+        TypeKind::NAMED { path: path_, args } => Node::new("named", span)
+            .attr("path", path(path_))
+            .children(types(args)),
+
+        TypeKind::ARRAY(elem) => Node::new("array", span).child(ty(elem)),
+
+        TypeKind::FIXED_ARRAY { elem, len } => Node::new("fixed_array", span)
+            .child(ty(elem))
+            .child(expr(len)),
+
+        TypeKind::TUPLE(elems) => Node::new("tuple", span).children(types(elems)),
+
+        TypeKind::FUNC { params, ret } => Node::new("func", span).children(types(params)).children(
+            ret.iter()
+                .map(|r| Node::new("returns", r.span).child(ty(r))),
+        ),
+
+        TypeKind::OPTIONAL(inner) => Node::new("optional", span).child(ty(inner)),
+    }
+}
@@ -0,0 +1,335 @@
+use super::{Block, Iden, Pattern};
+use crate::lexer::token::TokType;
+use crate::span::Span;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+impl Expr {
+    /// Returns `true` if the expression ends in a block, and so does not need a `;` to be
+    /// used as a statement
+    ///
+    #[must_use]
+    pub fn is_block_like(&self) -> bool {
+        matches!(
+            self.kind,
+            ExprKind::BLOCK(_)
+                | ExprKind::IF { .. }
+                | ExprKind::WHILE { .. }
+                | ExprKind::LOOP { .. }
+                | ExprKind::FOR { .. }
+                | ExprKind::MATCH { .. }
+        )
+    }
+
+    /// Returns the identifiers of an expression of the form `a.b.c`, if it is one
+    ///
+    #[must_use]
+    pub fn as_path(&self) -> Option<Vec<Iden>> {
+        match &self.kind {
+            ExprKind::IDEN(name) => Some(vec![Iden {
+                name: name.clone(),
+                span: self.span,
+            }]),
+
+            ExprKind::FIELD { expr, field } => {
+                let mut path: Vec<Iden> = expr.as_path()?;
+                path.push(field.clone());
+                Some(path)
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the expression refers to a memory location that can be assigned to
+    ///
+    #[must_use]
+    pub fn is_place(&self) -> bool {
+        matches!(
+            self.kind,
+            ExprKind::IDEN(_) | ExprKind::FIELD { .. } | ExprKind::INDEX { .. }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum ExprKind {
+    LITERAL(Literal),
+    IDEN(String),
+    SELF,
+    /// `super`, as in `super.method()`, or `super(...)` in a constructor
+    SUPER,
+    PAREN(Box<Expr>),
+
+    /// `(a, b)`, or `()` for the unit value
+    TUPLE(Vec<Expr>),
+
+    /// `[a, b, c]`
+    ARRAY(Vec<Expr>),
+
+    /// `[value; len]`, an array of `len` copies of `value`
+    ARRAY_REPEAT {
+        value: Box<Expr>,
+        len: Box<Expr>,
+    },
+
+    BLOCK(Block),
+
+    UNARY {
+        op: UnaryOp,
+        expr: Box<Expr>,
+    },
+
+    BINARY {
+        op: BinaryOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+
+    /// `cond ? then : els`
+    TERNARY {
+        cond: Box<Expr>,
+        then: Box<Expr>,
+        els: Box<Expr>,
+    },
+
+    /// `start..end` (exclusive) or `start..=end` (inclusive)
+    RANGE {
+        start: Box<Expr>,
+        end: Box<Expr>,
+        is_inclusive: bool,
+    },
+
+    /// `target = value`, or a compound assignment such as `target += value`
+    ASSIGN {
+        op: AssignOp,
+        target: Box<Expr>,
+        value: Box<Expr>,
+    },
+
+    /// `expr.field`
+    FIELD {
+        expr: Box<Expr>,
+        field: Iden,
+    },
+
+    /// `expr[index]`
+    INDEX {
+        expr: Box<Expr>,
+        index: Box<Expr>,
+    },
+
+    /// `callee(args)`
+    CALL {
+        callee: Box<Expr>,
+        args: Vec<Expr>,
+    },
+
+    /// `if cond { ... }`, with an optional `else { ... }` or `else if ...`
+    IF {
+        cond: Box<Expr>,
+        then: Block,
+        els: Option<Box<Expr>>,
+    },
+
+    /// `match value { pattern => expr, ... }`
+    MATCH {
+        scrutinee: Box<Expr>,
+        arms: Vec<MatchArm>,
+    },
+
+    /// `while cond { ... }`
+    WHILE {
+        label: Option<Iden>,
+        cond: Box<Expr>,
+        body: Block,
+    },
+
+    /// `loop { ... }`, which only ends through `break` (or `return`)
+    LOOP {
+        label: Option<Iden>,
+        body: Block,
+    },
+
+    /// `for x in iterable { ... }`
+    FOR {
+        label: Option<Iden>,
+        pattern: Box<Pattern>,
+        iter: Box<Expr>,
+        body: Block,
+    },
+
+    /// `break`, with an optional label and value (e.g. `break 'outer 1`)
+    BREAK {
+        label: Option<Iden>,
+        value: Option<Box<Expr>>,
+    },
+
+    /// `continue`, with an optional label
+    CONTINUE {
+        label: Option<Iden>,
+    },
+
+    /// `return`, with an optional value
+    RETURN(Option<Box<Expr>>),
+
+    /// `new ClassName(...)`
+    NEW {
+        class: Vec<Iden>,
+        args: Vec<Expr>,
+    },
+
+    /// `Point { x: 1.0, y }`, or `Shape.Rect { w: 1.0, h: 2.0 }` for an enum variant
+    STRUCT {
+        path: Vec<Iden>,
+        fields: Vec<FieldInit>,
+    },
+}
+
+/// An arm of a `match` expression, `<pattern> [if <guard>] => <expr>`
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expr>,
+    pub body: Expr,
+    pub span: Span,
+}
+
+/// A field of a struct literal. The shorthand `{ x }` is parsed as `{ x: x }`
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldInit {
+    pub name: Iden,
+    pub value: Expr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum Literal {
+    BOOL(bool),
+    CHAR(char),
+    FLOAT(f64),
+    INT(i32),
+    STRING(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum UnaryOp {
+    NEG,
+    NOT,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum BinaryOp {
+    ADD,
+    SUB,
+    MULT,
+    DIV,
+    MOD,
+    EQ,
+    NOT_EQ,
+    LESS,
+    LESS_EQ,
+    GRTR,
+    GRTR_EQ,
+    AND,
+    OR,
+    BIT_AND,
+    BIT_OR,
+}
+
+/// The operator of an assignment: plain `=`, or the arithmetic operator of a compound
+/// assignment such as `+=`
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum AssignOp {
+    ASSIGN,
+    ADD,
+    SUB,
+    MULT,
+    DIV,
+    MOD,
+}
+
+impl AssignOp {
+    /// Returns the binary operator applied by a compound assignment, if any
+    ///
+    #[must_use]
+    pub fn binary_op(&self) -> Option<BinaryOp> {
+        match self {
+            AssignOp::ASSIGN => None,
+            AssignOp::ADD => Some(BinaryOp::ADD),
+            AssignOp::SUB => Some(BinaryOp::SUB),
+            AssignOp::MULT => Some(BinaryOp::MULT),
+            AssignOp::DIV => Some(BinaryOp::DIV),
+            AssignOp::MOD => Some(BinaryOp::MOD),
+        }
+    }
+}
+
+impl fmt::Display for Literal {
+    /// Writes the literal as source code, with text escaped as needed
+    ///
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tok_type: TokType = match self {
+            Literal::BOOL(b) => TokType::LIT_BOOL(*b),
+            Literal::CHAR(c) => TokType::LIT_CHAR(*c),
+            Literal::FLOAT(n) => TokType::LIT_FLOAT(*n),
+            Literal::INT(n) => TokType::LIT_INT(*n),
+            Literal::STRING(s) => TokType::LIT_STRING(s.clone()),
+        };
+
+        write!(f, "{}", tok_type)
+    }
+}
+
+impl fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnaryOp::NEG => write!(f, "-"),
+            UnaryOp::NOT => write!(f, "!"),
+        }
+    }
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryOp::ADD => write!(f, "+"),
+            BinaryOp::SUB => write!(f, "-"),
+            BinaryOp::MULT => write!(f, "*"),
+            BinaryOp::DIV => write!(f, "/"),
+            BinaryOp::MOD => write!(f, "%"),
+            BinaryOp::EQ => write!(f, "=="),
+            BinaryOp::NOT_EQ => write!(f, "!="),
+            BinaryOp::LESS => write!(f, "<"),
+            BinaryOp::LESS_EQ => write!(f, "<="),
+            BinaryOp::GRTR => write!(f, ">"),
+            BinaryOp::GRTR_EQ => write!(f, ">="),
+            BinaryOp::AND => write!(f, "&&"),
+            BinaryOp::OR => write!(f, "||"),
+            BinaryOp::BIT_AND => write!(f, "&"),
+            BinaryOp::BIT_OR => write!(f, "|"),
+        }
+    }
+}
+
+impl fmt::Display for AssignOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.binary_op() {
+            Some(op) => write!(f, "{}=", op),
+            None => write!(f, "="),
+        }
+    }
+}
@@ -0,0 +1,542 @@
+//! Rebuilding a syntax tree
+//!
+//! A `Fold` takes each node by value and returns its replacement, so a pass can turn one tree
+//! into another (e.g. a lowering) without cloning what it leaves alone. Each method defaults
+//! to the matching `fold_*` function, which folds the node's children in source order and
+//! rebuilds the node around them
+
+use super::{
+    AssocType, Block, ClassDecl, ClassField, Constructor, EnumDecl, Expr, ExprKind, Field,
+    FieldInit, FieldPattern, FnDecl, GenericParam, ImportTree, Item, ItemKind, MatchArm, Member,
+    MemberKind, Module, Param, Pattern, PatternKind, ProtocolDecl, ProtocolMember,
+    ProtocolMemberKind, Stmt, StmtKind, StructDecl, Type, TypeAlias, TypeKind, VarDecl, Variant,
+    VariantKind,
+};
+
+/// A pass that rebuilds a syntax tree (see the module docs)
+///
+pub trait Fold: Sized {
+    fn fold_module(&mut self, module: Module) -> Module {
+        fold_module(self, module)
+    }
+
+    fn fold_item(&mut self, item: Item) -> Item {
+        fold_item(self, item)
+    }
+
+    fn fold_var_decl(&mut self, decl: VarDecl) -> VarDecl {
+        fold_var_decl(self, decl)
+    }
+
+    fn fold_fn_decl(&mut self, func: FnDecl) -> FnDecl {
+        fold_fn_decl(self, func)
+    }
+
+    fn fold_param(&mut self, param: Param) -> Param {
+        fold_param(self, param)
+    }
+
+    fn fold_generic_param(&mut self, param: GenericParam) -> GenericParam {
+        param
+    }
+
+    fn fold_struct_decl(&mut self, decl: StructDecl) -> StructDecl {
+        fold_struct_decl(self, decl)
+    }
+
+    fn fold_field(&mut self, field: Field) -> Field {
+        fold_field(self, field)
+    }
+
+    fn fold_enum_decl(&mut self, decl: EnumDecl) -> EnumDecl {
+        fold_enum_decl(self, decl)
+    }
+
+    fn fold_variant(&mut self, variant: Variant) -> Variant {
+        fold_variant(self, variant)
+    }
+
+    fn fold_class_decl(&mut self, decl: ClassDecl) -> ClassDecl {
+        fold_class_decl(self, decl)
+    }
+
+    fn fold_member(&mut self, member: Member) -> Member {
+        fold_member(self, member)
+    }
+
+    fn fold_class_field(&mut self, field: ClassField) -> ClassField {
+        fold_class_field(self, field)
+    }
+
+    fn fold_constructor(&mut self, constructor: Constructor) -> Constructor {
+        fold_constructor(self, constructor)
+    }
+
+    fn fold_protocol_decl(&mut self, decl: ProtocolDecl) -> ProtocolDecl {
+        fold_protocol_decl(self, decl)
+    }
+
+    fn fold_protocol_member(&mut self, member: ProtocolMember) -> ProtocolMember {
+        fold_protocol_member(self, member)
+    }
+
+    fn fold_assoc_type(&mut self, assoc: AssocType) -> AssocType {
+        assoc
+    }
+
+    fn fold_type_alias(&mut self, alias: TypeAlias) -> TypeAlias {
+        fold_type_alias(self, alias)
+    }
+
+    fn fold_import_tree(&mut self, tree: ImportTree) -> ImportTree {
+        tree
+    }
+
+    fn fold_block(&mut self, block: Block) -> Block {
+        fold_block(self, block)
+    }
+
+    fn fold_stmt(&mut self, stmt: Stmt) -> Stmt {
+        fold_stmt(self, stmt)
+    }
+
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        fold_expr(self, expr)
+    }
+
+    fn fold_match_arm(&mut self, arm: MatchArm) -> MatchArm {
+        fold_match_arm(self, arm)
+    }
+
+    fn fold_field_init(&mut self, field: FieldInit) -> FieldInit {
+        fold_field_init(self, field)
+    }
+
+    fn fold_pattern(&mut self, pattern: Pattern) -> Pattern {
+        fold_pattern(self, pattern)
+    }
+
+    fn fold_field_pattern(&mut self, field: FieldPattern) -> FieldPattern {
+        fold_field_pattern(self, field)
+    }
+
+    fn fold_type(&mut self, ty: Type) -> Type {
+        fold_type(self, ty)
+    }
+}
+
+/// Folds each node of a list
+///
+fn fold_all<T>(nodes: Vec<T>, f: impl FnMut(T) -> T) -> Vec<T> {
+    nodes.into_iter().map(f).collect()
+}
+
+pub fn fold_module<F: Fold>(f: &mut F, module: Module) -> Module {
+    Module {
+        items: fold_all(module.items, |i| f.fold_item(i)),
+        span: module.span,
+    }
+}
+
+pub fn fold_item<F: Fold>(f: &mut F, item: Item) -> Item {
+    let kind: ItemKind = match item.kind {
+        ItemKind::VAR(decl) => ItemKind::VAR(f.fold_var_decl(decl)),
+        ItemKind::FUNC(func) => ItemKind::FUNC(f.fold_fn_decl(func)),
+        ItemKind::STRUCT(decl) => ItemKind::STRUCT(f.fold_struct_decl(decl)),
+        ItemKind::ENUM(decl) => ItemKind::ENUM(f.fold_enum_decl(decl)),
+        ItemKind::CLASS(decl) => ItemKind::CLASS(f.fold_class_decl(decl)),
+        ItemKind::PROTOCOL(decl) => ItemKind::PROTOCOL(f.fold_protocol_decl(decl)),
+        ItemKind::IMPORT(tree) => ItemKind::IMPORT(f.fold_import_tree(tree)),
+        ItemKind::ALIAS(alias) => ItemKind::ALIAS(f.fold_type_alias(alias)),
+        ItemKind::TYPE(alias) => ItemKind::TYPE(f.fold_type_alias(alias)),
+        kind @ (ItemKind::LIB(_) | ItemKind::ERROR) => kind,
+    };
+
+    Item { kind, ..item }
+}
+
+pub fn fold_var_decl<F: Fold>(f: &mut F, decl: VarDecl) -> VarDecl {
+    VarDecl {
+        binding: decl.binding,
+        keyword: decl.keyword,
+        pattern: f.fold_pattern(decl.pattern),
+        ty: decl.ty.map(|t| f.fold_type(t)),
+        value: decl.value.map(|e| f.fold_expr(e)),
+    }
+}
+
+pub fn fold_fn_decl<F: Fold>(f: &mut F, func: FnDecl) -> FnDecl {
+    FnDecl {
+        name: func.name,
+        generics: fold_all(func.generics, |p| f.fold_generic_param(p)),
+        params: fold_all(func.params, |p| f.fold_param(p)),
+        ret: func.ret.map(|t| f.fold_type(t)),
+        body: func.body.map(|b| f.fold_block(b)),
+        is_extern: func.is_extern,
+    }
+}
+
+pub fn fold_param<F: Fold>(f: &mut F, param: Param) -> Param {
+    Param {
+        pattern: f.fold_pattern(param.pattern),
+        ty: f.fold_type(param.ty),
+        default: param.default.map(|e| f.fold_expr(e)),
+        span: param.span,
+    }
+}
+
+pub fn fold_struct_decl<F: Fold>(f: &mut F, decl: StructDecl) -> StructDecl {
+    StructDecl {
+        name: decl.name,
+        generics: fold_all(decl.generics, |p| f.fold_generic_param(p)),
+        protocols: decl.protocols,
+        fields: fold_all(decl.fields, |field| f.fold_field(field)),
+        members: fold_all(decl.members, |m| f.fold_member(m)),
+    }
+}
+
+pub fn fold_field<F: Fold>(f: &mut F, field: Field) -> Field {
+    Field {
+        ty: f.fold_type(field.ty),
+        ..field
+    }
+}
+
+pub fn fold_enum_decl<F: Fold>(f: &mut F, decl: EnumDecl) -> EnumDecl {
+    EnumDecl {
+        name: decl.name,
+        generics: fold_all(decl.generics, |p| f.fold_generic_param(p)),
+        protocols: decl.protocols,
+        variants: fold_all(decl.variants, |v| f.fold_variant(v)),
+        members: fold_all(decl.members, |m| f.fold_member(m)),
+    }
+}
+
+pub fn fold_variant<F: Fold>(f: &mut F, variant: Variant) -> Variant {
+    let kind: VariantKind = match variant.kind {
+        VariantKind::UNIT => VariantKind::UNIT,
+        VariantKind::TUPLE(types) => VariantKind::TUPLE(fold_all(types, |t| f.fold_type(t))),
+
+        VariantKind::STRUCT(fields) => {
+            VariantKind::STRUCT(fold_all(fields, |field| f.fold_field(field)))
+        }
+    };
+
+    Variant {
+        name: variant.name,
+        kind,
+        discriminant: variant.discriminant.map(|e| f.fold_expr(e)),
+        span: variant.span,
+    }
+}
+
+pub fn fold_class_decl<F: Fold>(f: &mut F, decl: ClassDecl) -> ClassDecl {
+    ClassDecl {
+        generics: fold_all(decl.generics, |p| f.fold_generic_param(p)),
+        members: fold_all(decl.members, |m| f.fold_member(m)),
+        ..decl
+    }
+}
+
+pub fn fold_member<F: Fold>(f: &mut F, member: Member) -> Member {
+    let kind: MemberKind = match member.kind {
+        MemberKind::FIELD(field) => MemberKind::FIELD(f.fold_class_field(field)),
+
+        MemberKind::CONSTRUCTOR(constructor) => {
+            MemberKind::CONSTRUCTOR(f.fold_constructor(constructor))
+        }
+
+        MemberKind::METHOD(func) => MemberKind::METHOD(f.fold_fn_decl(func)),
+        MemberKind::TYPE(alias) => MemberKind::TYPE(f.fold_type_alias(alias)),
+        MemberKind::ERROR => MemberKind::ERROR,
+    };
+
+    Member { kind, ..member }
+}
+
+pub fn fold_class_field<F: Fold>(f: &mut F, field: ClassField) -> ClassField {
+    ClassField {
+        name: field.name,
+        ty: f.fold_type(field.ty),
+        value: field.value.map(|e| f.fold_expr(e)),
+    }
+}
+
+pub fn fold_constructor<F: Fold>(f: &mut F, constructor: Constructor) -> Constructor {
+    Constructor {
+        params: fold_all(constructor.params, |p| f.fold_param(p)),
+        body: f.fold_block(constructor.body),
+    }
+}
+
+pub fn fold_protocol_decl<F: Fold>(f: &mut F, decl: ProtocolDecl) -> ProtocolDecl {
+    ProtocolDecl {
+        name: decl.name,
+        members: fold_all(decl.members, |m| f.fold_protocol_member(m)),
+    }
+}
+
+pub fn fold_protocol_member<F: Fold>(f: &mut F, member: ProtocolMember) -> ProtocolMember {
+    let kind: ProtocolMemberKind = match member.kind {
+        ProtocolMemberKind::TYPE(assoc) => ProtocolMemberKind::TYPE(f.fold_assoc_type(assoc)),
+        ProtocolMemberKind::METHOD(func) => ProtocolMemberKind::METHOD(f.fold_fn_decl(func)),
+    };
+
+    ProtocolMember {
+        kind,
+        span: member.span,
+    }
+}
+
+pub fn fold_type_alias<F: Fold>(f: &mut F, alias: TypeAlias) -> TypeAlias {
+    TypeAlias {
+        name: alias.name,
+        generics: fold_all(alias.generics, |p| f.fold_generic_param(p)),
+        ty: f.fold_type(alias.ty),
+    }
+}
+
+pub fn fold_block<F: Fold>(f: &mut F, block: Block) -> Block {
+    Block {
+        stmts: fold_all(block.stmts, |s| f.fold_stmt(s)),
+        tail: block.tail.map(|e| Box::new(f.fold_expr(*e))),
+        span: block.span,
+    }
+}
+
+pub fn fold_stmt<F: Fold>(f: &mut F, stmt: Stmt) -> Stmt {
+    let kind: StmtKind = match stmt.kind {
+        StmtKind::VAR(decl) => StmtKind::VAR(Box::new(f.fold_var_decl(*decl))),
+        StmtKind::EXPR(expr) => StmtKind::EXPR(f.fold_expr(expr)),
+        StmtKind::ERROR => StmtKind::ERROR,
+    };
+
+    Stmt {
+        kind,
+        span: stmt.span,
+    }
+}
+
+pub fn fold_expr<F: Fold>(f: &mut F, expr: Expr) -> Expr {
+    let mut boxed = |e: Box<Expr>| -> Box<Expr> { Box::new(f.fold_expr(*e)) };
+
+    let kind: ExprKind = match expr.kind {
+        kind @ (ExprKind::LITERAL(_)
+        | ExprKind::IDEN(_)
+        | ExprKind::SELF
+        | ExprKind::SUPER
+        | ExprKind::CONTINUE { .. }) => kind,
+
+        ExprKind::PAREN(inner) => ExprKind::PAREN(boxed(inner)),
+
+        ExprKind::UNARY { op, expr: inner } => ExprKind::UNARY {
+            op,
+            expr: boxed(inner),
+        },
+
+        ExprKind::FIELD { expr: inner, field } => ExprKind::FIELD {
+            expr: boxed(inner),
+            field,
+        },
+
+        ExprKind::TUPLE(elems) => ExprKind::TUPLE(fold_all(elems, |e| f.fold_expr(e))),
+        ExprKind::ARRAY(elems) => ExprKind::ARRAY(fold_all(elems, |e| f.fold_expr(e))),
+
+        ExprKind::ARRAY_REPEAT { value, len } => ExprKind::ARRAY_REPEAT {
+            value: boxed(value),
+            len: boxed(len),
+        },
+
+        ExprKind::BLOCK(block) => ExprKind::BLOCK(f.fold_block(block)),
+
+        ExprKind::BINARY { op, lhs, rhs } => ExprKind::BINARY {
+            op,
+            lhs: boxed(lhs),
+            rhs: boxed(rhs),
+        },
+
+        ExprKind::TERNARY { cond, then, els } => ExprKind::TERNARY {
+            cond: boxed(cond),
+            then: boxed(then),
+            els: boxed(els),
+        },
+
+        ExprKind::RANGE {
+            start,
+            end,
+            is_inclusive,
+        } => ExprKind::RANGE {
+            start: boxed(start),
+            end: boxed(end),
+            is_inclusive,
+        },
+
+        ExprKind::ASSIGN { op, target, value } => ExprKind::ASSIGN {
+            op,
+            target: boxed(target),
+            value: boxed(value),
+        },
+
+        ExprKind::INDEX { expr: inner, index } => ExprKind::INDEX {
+            expr: boxed(inner),
+            index: boxed(index),
+        },
+
+        ExprKind::CALL { callee, args } => ExprKind::CALL {
+            callee: boxed(callee),
+            args: fold_all(args, |e| f.fold_expr(e)),
+        },
+
+        ExprKind::IF { cond, then, els } => {
+            let cond: Box<Expr> = Box::new(f.fold_expr(*cond));
+            let then: Block = f.fold_block(then);
+            let els: Option<Box<Expr>> = els.map(|e| Box::new(f.fold_expr(*e)));
+
+            ExprKind::IF { cond, then, els }
+        }
+
+        ExprKind::MATCH { scrutinee, arms } => ExprKind::MATCH {
+            scrutinee: Box::new(f.fold_expr(*scrutinee)),
+            arms: fold_all(arms, |a| f.fold_match_arm(a)),
+        },
+
+        ExprKind::WHILE { label, cond, body } => ExprKind::WHILE {
+            label,
+            cond: Box::new(f.fold_expr(*cond)),
+            body: f.fold_block(body),
+        },
+
+        ExprKind::LOOP { label, body } => ExprKind::LOOP {
+            label,
+            body: f.fold_block(body),
+        },
+
+        ExprKind::FOR {
+            label,
+            pattern,
+            iter,
+            body,
+        } => ExprKind::FOR {
+            label,
+            pattern: Box::new(f.fold_pattern(*pattern)),
+            iter: Box::new(f.fold_expr(*iter)),
+            body: f.fold_block(body),
+        },
+
+        ExprKind::BREAK { label, value } => ExprKind::BREAK {
+            label,
+            value: value.map(|e| Box::new(f.fold_expr(*e))),
+        },
+
+        ExprKind::RETURN(value) => ExprKind::RETURN(value.map(|e| Box::new(f.fold_expr(*e)))),
+
+        ExprKind::NEW { class, args } => ExprKind::NEW {
+            class,
+            args: fold_all(args, |e| f.fold_expr(e)),
+        },
+
+        ExprKind::STRUCT { path, fields } => ExprKind::STRUCT {
+            path,
+            fields: fold_all(fields, |field| f.fold_field_init(field)),
+        },
+    };
+
+    Expr {
+        kind,
+        span: expr.span,
+    }
+}
+
+pub fn fold_match_arm<F: Fold>(f: &mut F, arm: MatchArm) -> MatchArm {
+    MatchArm {
+        pattern: f.fold_pattern(arm.pattern),
+        guard: arm.guard.map(|e| f.fold_expr(e)),
+        body: f.fold_expr(arm.body),
+        span: arm.span,
+    }
+}
+
+pub fn fold_field_init<F: Fold>(f: &mut F, field: FieldInit) -> FieldInit {
+    FieldInit {
+        name: field.name,
+        value: f.fold_expr(field.value),
+        span: field.span,
+    }
+}
+
+pub fn fold_pattern<F: Fold>(f: &mut F, pattern: Pattern) -> Pattern {
+    let kind: PatternKind = match pattern.kind {
+        kind @ (PatternKind::WILDCARD
+        | PatternKind::BINDING(_)
+        | PatternKind::LITERAL(_)
+        | PatternKind::RANGE { .. }) => kind,
+
+        PatternKind::TUPLE(elems) => PatternKind::TUPLE(fold_all(elems, |p| f.fold_pattern(p))),
+        PatternKind::OR(alts) => PatternKind::OR(fold_all(alts, |p| f.fold_pattern(p))),
+
+        PatternKind::VARIANT { path, elems } => PatternKind::VARIANT {
+            path,
+            elems: elems.map(|elems| fold_all(elems, |p| f.fold_pattern(p))),
+        },
+
+        PatternKind::STRUCT {
+            path,
+            fields,
+            has_rest,
+        } => PatternKind::STRUCT {
+            path,
+            fields: fold_all(fields, |field| f.fold_field_pattern(field)),
+            has_rest,
+        },
+    };
+
+    Pattern {
+        kind,
+        span: pattern.span,
+    }
+}
+
+pub fn fold_field_pattern<F: Fold>(f: &mut F, field: FieldPattern) -> FieldPattern {
+    FieldPattern {
+        name: field.name,
+        pattern: f.fold_pattern(field.pattern),
+        span: field.span,
+    }
+}
+
+pub fn fold_type<F: Fold>(f: &mut F, ty: Type) -> Type {
+    let kind: TypeKind = match ty.kind {
+        kind @ (TypeKind::BOOL
+        | TypeKind::CHAR
+        | TypeKind::FLOAT
+        | TypeKind::INT
+        | TypeKind::STRING
+        | TypeKind::UINT) => kind,
+
+        TypeKind::NAMED { path, args } => TypeKind::NAMED {
+            path,
+            args: fold_all(args, |t| f.fold_type(t)),
+        },
+
+        TypeKind::ARRAY(elem) => TypeKind::ARRAY(Box::new(f.fold_type(*elem))),
+
+        TypeKind::FIXED_ARRAY { elem, len } => TypeKind::FIXED_ARRAY {
+            elem: Box::new(f.fold_type(*elem)),
+            len: Box::new(f.fold_expr(*len)),
+        },
+
+        TypeKind::TUPLE(elems) => TypeKind::TUPLE(fold_all(elems, |t| f.fold_type(t))),
+
+        TypeKind::FUNC { params, ret } => TypeKind::FUNC {
+            params: fold_all(params, |t| f.fold_type(t)),
+            ret: ret.map(|t| Box::new(f.fold_type(*t))),
+        },
+
+        TypeKind::OPTIONAL(inner) => TypeKind::OPTIONAL(Box::new(f.fold_type(*inner))),
+    };
+
+    Type {
+        kind,
+        span: ty.span,
+    }
+}
@@ -0,0 +1,194 @@
+use super::{Block, ClassDecl, Expr, GenericParam, Iden, Member, Pattern, ProtocolDecl, Type};
+use crate::span::Span;
+
+/// A top-level declaration
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    pub kind: ItemKind,
+    pub is_public: bool,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum ItemKind {
+    VAR(VarDecl),
+    FUNC(FnDecl),
+    STRUCT(StructDecl),
+    ENUM(EnumDecl),
+    CLASS(ClassDecl),
+    PROTOCOL(ProtocolDecl),
+
+    /// `lib geometry;`, marking the file as the root of the library `geometry`
+    LIB(Iden),
+
+    IMPORT(ImportTree),
+
+    /// `alias Meters = float;`, another name for the same type
+    ALIAS(TypeAlias),
+
+    /// `type UserId = int;`, a distinct type with the same representation as another, so that
+    /// neither can be used in place of the other
+    TYPE(TypeAlias),
+
+    /// tokens skipped after a syntax error, up to the next item
+    ERROR,
+}
+
+/// A `let`, `var` or `const` declaration, e.g. `let foo: float = 0.1;`
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarDecl {
+    pub binding: BindingKind,
+
+    /// the span of the `let`, `var` or `const` keyword
+    pub keyword: Span,
+
+    pub pattern: Pattern,
+    pub ty: Option<Type>,
+    pub value: Option<Expr>,
+}
+
+/// The keyword introducing a variable declaration
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum BindingKind {
+    CONST,
+    LET,
+    VAR,
+}
+
+/// A function declaration, e.g. `func add(a: int, b: int = 1) -> int { a + b }`
+///
+/// Functions declared `extern` are implemented elsewhere, and have no body
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct FnDecl {
+    pub name: Iden,
+    pub generics: Vec<GenericParam>,
+    pub params: Vec<Param>,
+    pub ret: Option<Type>,
+    pub body: Option<Block>,
+    pub is_extern: bool,
+}
+
+/// A function parameter, with an optional default value for callers that omit it
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub pattern: Pattern,
+    pub ty: Type,
+    pub default: Option<Expr>,
+    pub span: Span,
+}
+
+/// A struct declaration, e.g. `struct Point: Printable { public x: float, y: float }`. Any
+/// methods follow the fields
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructDecl {
+    pub name: Iden,
+    pub generics: Vec<GenericParam>,
+    pub protocols: Vec<Iden>,
+    pub fields: Vec<Field>,
+    pub members: Vec<Member>,
+}
+
+/// A named, typed field of a struct or struct-like enum variant
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: Iden,
+    pub ty: Type,
+    pub is_public: bool,
+    pub span: Span,
+}
+
+/// An enum declaration, e.g. `enum Shape { Empty, Circle(float), Rect { w: float, h: float } }`.
+/// Any methods follow the variants
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumDecl {
+    pub name: Iden,
+    pub generics: Vec<GenericParam>,
+    pub protocols: Vec<Iden>,
+    pub variants: Vec<Variant>,
+    pub members: Vec<Member>,
+}
+
+/// An enum variant, with an optional explicit discriminant (e.g. `Red = 1`)
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub name: Iden,
+    pub kind: VariantKind,
+    pub discriminant: Option<Expr>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum VariantKind {
+    /// `Empty`
+    UNIT,
+
+    /// `Circle(float)`
+    TUPLE(Vec<Type>),
+
+    /// `Rect { w: float, h: float }`
+    STRUCT(Vec<Field>),
+}
+
+/// A name bound to a type, e.g. `alias Pair<T> = (T, T);`, or `type Output = String;` in a
+/// protocol conformance
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAlias {
+    pub name: Iden,
+    pub generics: Vec<GenericParam>,
+    pub ty: Type,
+}
+
+/// An `import` declaration, e.g. `import a.b.c;`, `import a.b.c as d;` or, importing several
+/// names from one module, `import a.b.{x, y as z};`
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportTree {
+    pub path: Vec<Iden>,
+    pub kind: ImportKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum ImportKind {
+    /// the imported module or item, with an optional alias
+    SINGLE(Option<Iden>),
+
+    /// `{x, y as z}`, relative to the tree's path, with an optional alias naming the group
+    /// as a namespace (e.g. `z` in `a.b.{x, y} as z`)
+    GROUP {
+        trees: Vec<ImportTree>,
+        alias: Option<Iden>,
+    },
+}
+
+impl ImportTree {
+    /// Returns the full path of every module or item imported by the tree, along with the
+    /// span of the import that names it
+    ///
+    #[must_use]
+    pub fn paths(&self) -> Vec<(Vec<Iden>, Span)> {
+        match &self.kind {
+            ImportKind::SINGLE(_) => vec![(self.path.clone(), self.span)],
+
+            ImportKind::GROUP { trees, .. } => trees
+                .iter()
+                .flat_map(ImportTree::paths)
+                .map(|(path, span)| ([self.path.clone(), path].concat(), span))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::{Iden, Literal};
+use crate::span::Span;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pub kind: PatternKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum PatternKind {
+    /// `_`, which matches anything without binding it
+    WILDCARD,
+
+    /// binds the matched value to a new variable
+    BINDING(Iden),
+
+    /// `1`, `'c'`, `"text"`, `true`
+    LITERAL(Literal),
+
+    /// `1..10` (exclusive) or `'a'..='z'` (inclusive)
+    RANGE {
+        start: Literal,
+        end: Literal,
+        is_inclusive: bool,
+    },
+
+    /// `(a, _, 1)`
+    TUPLE(Vec<Pattern>),
+
+    /// `Color.Red` or `Shape.Circle(p, r)`. A single name without parentheses is a
+    /// `BINDING` instead, so unit variants are always written with their enum's name
+    VARIANT {
+        path: Vec<Iden>,
+        elems: Option<Vec<Pattern>>,
+    },
+
+    /// `Point { x, y: 0 }` or `Shape.Rect { width, .. }`, where `..` ignores the other fields
+    STRUCT {
+        path: Vec<Iden>,
+        fields: Vec<FieldPattern>,
+        has_rest: bool,
+    },
+
+    /// `a | b | c`
+    OR(Vec<Pattern>),
+}
+
+/// A field of a struct pattern. The shorthand `{ x }` is parsed as `{ x: x }`
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldPattern {
+    pub name: Iden,
+    pub pattern: Pattern,
+    pub span: Span,
+}
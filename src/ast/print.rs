@@ -0,0 +1,887 @@
+use super::{
+    BindingKind, Block, ClassDecl, EnumDecl, Expr, ExprKind, FnDecl, GenericParam, Iden,
+    ImportKind, ImportTree, Item, ItemKind, Member, MemberKind, Modifiers, Module, Param, Pattern,
+    PatternKind, ProtocolDecl, ProtocolMemberKind, Stmt, StmtKind, StructDecl, Type, TypeAlias,
+    TypeKind, UnaryOp, VarDecl, VariantKind,
+};
+
+/// Returns canonical feolang source for a syntax tree, which parses back into the same tree
+/// (spans aside)
+///
+/// Code is indented by four spaces, with items separated by blank lines. Parentheses appear
+/// exactly where the tree has `PAREN` nodes, and comments, which the tree does not keep, are
+/// lost. Nodes left by error recovery (`ERROR`) are skipped
+///
+/// # Arguments
+///
+/// * `module` - the syntax tree of a file
+///
+pub fn print(module: &Module) -> String {
+    let mut printer = Printer {
+        buf: String::new(),
+        indent: 0,
+    };
+
+    let items: Vec<&Item> = module
+        .items
+        .iter()
+        .filter(|i| i.kind != ItemKind::ERROR)
+        .collect();
+
+    for (i, item) in items.iter().enumerate() {
+        // runs of declarations or imports are kept together
+        let is_grouped = |item: &Item| matches!(item.kind, ItemKind::VAR(_) | ItemKind::IMPORT(_));
+
+        if i > 0 && !(is_grouped(items[i - 1]) && is_grouped(item)) {
+            printer.buf.push('\n');
+        }
+
+        printer.item(item);
+        printer.buf.push('\n');
+    }
+
+    printer.buf
+}
+
+/// Returns a path (e.g. `geometry.Point`) as source
+///
+fn path(path: &[Iden]) -> String {
+    let names: Vec<&str> = path.iter().map(|i| i.name.as_str()).collect();
+    names.join(".")
+}
+
+struct Printer {
+    buf: String,
+    indent: usize,
+}
+
+impl Printer {
+    fn push(&mut self, text: &str) {
+        self.buf.push_str(text);
+    }
+
+    /// Starts a new line at the current indentation
+    ///
+    fn newline(&mut self) {
+        self.buf.push('\n');
+        self.buf.push_str(&"    ".repeat(self.indent));
+    }
+
+    /// Writes a comma-separated list
+    ///
+    fn list<T>(&mut self, elems: &[T], mut f: impl FnMut(&mut Self, &T)) {
+        for (i, elem) in elems.iter().enumerate() {
+            if i > 0 {
+                self.push(", ");
+            }
+
+            f(self, elem);
+        }
+    }
+
+    /// Writes a tuple of expressions, patterns or types, with a trailing comma if there is
+    /// only one element (so that it is not read as a parenthesised one)
+    ///
+    fn tuple<T>(&mut self, elems: &[T], f: impl FnMut(&mut Self, &T)) {
+        self.push("(");
+        self.list(elems, f);
+
+        if elems.len() == 1 {
+            self.push(",");
+        }
+
+        self.push(")");
+    }
+
+    fn item(&mut self, item: &Item) {
+        if item.is_public {
+            self.push("public ");
+        }
+
+        match &item.kind {
+            ItemKind::VAR(decl) => self.var_decl(decl),
+            ItemKind::FUNC(func) => self.fn_decl(func),
+            ItemKind::STRUCT(decl) => self.struct_decl(decl),
+            ItemKind::ENUM(decl) => self.enum_decl(decl),
+            ItemKind::CLASS(decl) => self.class_decl(decl),
+            ItemKind::PROTOCOL(decl) => self.protocol_decl(decl),
+
+            ItemKind::LIB(name) => {
+                self.push("lib ");
+                self.push(&name.name);
+                self.push(";");
+            }
+
+            ItemKind::IMPORT(tree) => {
+                self.push("import ");
+                self.import_tree(tree);
+                self.push(";");
+            }
+
+            ItemKind::ALIAS(alias) => {
+                self.push("alias ");
+                self.type_alias(alias);
+            }
+
+            ItemKind::TYPE(alias) => {
+                self.push("type ");
+                self.type_alias(alias);
+            }
+
+            ItemKind::ERROR => (),
+        }
+    }
+
+    fn var_decl(&mut self, decl: &VarDecl) {
+        self.push(match decl.binding {
+            BindingKind::CONST => "const ",
+            BindingKind::LET => "let ",
+            BindingKind::VAR => "var ",
+        });
+
+        self.pattern(&decl.pattern);
+
+        if let Some(ty) = &decl.ty {
+            self.push(": ");
+            self.ty(ty);
+        }
+
+        if let Some(value) = &decl.value {
+            self.push(" = ");
+            self.expr(value);
+        }
+
+        self.push(";");
+    }
+
+    fn fn_decl(&mut self, func: &FnDecl) {
+        if func.is_extern {
+            self.push("extern ");
+        }
+
+        self.push("func ");
+        self.push(&func.name.name);
+        self.generics(&func.generics);
+
+        self.push("(");
+        self.list(&func.params, Self::param);
+        self.push(")");
+
+        if let Some(ret) = &func.ret {
+            self.push(" -> ");
+            self.ty(ret);
+        }
+
+        match &func.body {
+            Some(body) => {
+                self.push(" ");
+                self.block(body);
+            }
+
+            None => self.push(";"),
+        }
+    }
+
+    fn param(&mut self, param: &Param) {
+        self.pattern(&param.pattern);
+        self.push(": ");
+        self.ty(&param.ty);
+
+        if let Some(default) = &param.default {
+            self.push(" = ");
+            self.expr(default);
+        }
+    }
+
+    fn generics(&mut self, generics: &[GenericParam]) {
+        if generics.is_empty() {
+            return;
+        }
+
+        self.push("<");
+
+        self.list(generics, |p, generic| {
+            p.push(&generic.name.name);
+
+            if !generic.bounds.is_empty() {
+                let bounds: Vec<&str> = generic.bounds.iter().map(|b| b.name.as_str()).collect();
+
+                p.push(": ");
+                p.push(&bounds.join(" + "));
+            }
+        });
+
+        self.push(">");
+    }
+
+    /// Writes a conformance clause (e.g. `: Printable, Eq`), if there are any names
+    ///
+    fn conformance(&mut self, names: &[Iden]) {
+        if !names.is_empty() {
+            self.push(": ");
+            self.list(names, |p, name| p.push(&name.name));
+        }
+    }
+
+    fn type_alias(&mut self, alias: &TypeAlias) {
+        self.push(&alias.name.name);
+        self.generics(&alias.generics);
+        self.push(" = ");
+        self.ty(&alias.ty);
+        self.push(";");
+    }
+
+    fn import_tree(&mut self, tree: &ImportTree) {
+        self.push(&path(&tree.path));
+
+        match &tree.kind {
+            ImportKind::SINGLE(alias) => {
+                if let Some(alias) = alias {
+                    self.push(" as ");
+                    self.push(&alias.name);
+                }
+            }
+
+            ImportKind::GROUP { trees, alias } => {
+                self.push(".{");
+                self.list(trees, Self::import_tree);
+                self.push("}");
+
+                if let Some(alias) = alias {
+                    self.push(" as ");
+                    self.push(&alias.name);
+                }
+            }
+        }
+    }
+
+    fn struct_decl(&mut self, decl: &StructDecl) {
+        self.push("struct ");
+        self.push(&decl.name.name);
+        self.generics(&decl.generics);
+        self.conformance(&decl.protocols);
+        self.push(" {");
+        self.indent += 1;
+
+        for field in &decl.fields {
+            self.newline();
+
+            if field.is_public {
+                self.push("public ");
+            }
+
+            self.push(&field.name.name);
+            self.push(": ");
+            self.ty(&field.ty);
+            self.push(",");
+        }
+
+        self.members(&decl.members, !decl.fields.is_empty());
+    }
+
+    fn enum_decl(&mut self, decl: &EnumDecl) {
+        self.push("enum ");
+        self.push(&decl.name.name);
+        self.generics(&decl.generics);
+        self.conformance(&decl.protocols);
+        self.push(" {");
+        self.indent += 1;
+
+        for variant in &decl.variants {
+            self.newline();
+            self.push(&variant.name.name);
+
+            match &variant.kind {
+                VariantKind::UNIT => (),
+                VariantKind::TUPLE(types) => {
+                    self.push("(");
+                    self.list(types, Self::ty);
+                    self.push(")");
+                }
+
+                VariantKind::STRUCT(fields) => {
+                    self.push(" { ");
+
+                    self.list(fields, |p, field| {
+                        p.push(&field.name.name);
+                        p.push(": ");
+                        p.ty(&field.ty);
+                    });
+
+                    self.push(" }");
+                }
+            }
+
+            if let Some(discriminant) = &variant.discriminant {
+                self.push(" = ");
+                self.expr(discriminant);
+            }
+
+            self.push(",");
+        }
+
+        self.members(&decl.members, !decl.variants.is_empty());
+    }
+
+    fn class_decl(&mut self, decl: &ClassDecl) {
+        if decl.is_abstract {
+            self.push("abstract ");
+        }
+
+        if decl.is_final {
+            self.push("final ");
+        }
+
+        self.push("class ");
+        self.push(&decl.name.name);
+        self.generics(&decl.generics);
+        self.conformance(&decl.supertypes);
+        self.push(" {");
+        self.indent += 1;
+
+        self.members(&decl.members, false);
+    }
+
+    /// Writes the members of a struct, enum or class (each preceded by a blank line, unless
+    /// it is the first thing in the body), then closes the body
+    ///
+    /// # Arguments
+    ///
+    /// * `members` - the members
+    /// * `after_fields` - whether fields or variants come before the members
+    ///
+    fn members(&mut self, members: &[Member], after_fields: bool) {
+        let members: Vec<&Member> = members
+            .iter()
+            .filter(|m| m.kind != MemberKind::ERROR)
+            .collect();
+
+        for (i, member) in members.iter().enumerate() {
+            // consecutive fields of a class are kept together
+            let is_field = |m: &Member| matches!(m.kind, MemberKind::FIELD(_));
+
+            if (i > 0 && !(is_field(members[i - 1]) && is_field(member))) || after_fields {
+                self.buf.push('\n');
+            }
+
+            self.newline();
+            self.member(member);
+        }
+
+        self.close_body(!members.is_empty() || after_fields);
+    }
+
+    fn member(&mut self, member: &Member) {
+        self.modifiers(&member.modifiers);
+
+        match &member.kind {
+            MemberKind::FIELD(field) => {
+                self.push(&field.name.name);
+                self.push(": ");
+                self.ty(&field.ty);
+
+                if let Some(value) = &field.value {
+                    self.push(" = ");
+                    self.expr(value);
+                }
+
+                self.push(";");
+            }
+
+            MemberKind::CONSTRUCTOR(constructor) => {
+                self.push("new(");
+                self.list(&constructor.params, Self::param);
+                self.push(") ");
+                self.block(&constructor.body);
+            }
+
+            MemberKind::METHOD(func) => self.fn_decl(func),
+
+            MemberKind::TYPE(alias) => {
+                self.push("type ");
+                self.type_alias(alias);
+            }
+
+            MemberKind::ERROR => (),
+        }
+    }
+
+    fn modifiers(&mut self, modifiers: &Modifiers) {
+        let flags: [(bool, &str); 6] = [
+            (modifiers.is_public, "public "),
+            (modifiers.is_static, "static "),
+            (modifiers.is_abstract, "abstract "),
+            (modifiers.is_virtual, "virtual "),
+            (modifiers.is_override, "override "),
+            (modifiers.is_final, "final "),
+        ];
+
+        for (is_set, keyword) in flags {
+            if is_set {
+                self.push(keyword);
+            }
+        }
+    }
+
+    fn protocol_decl(&mut self, decl: &ProtocolDecl) {
+        self.push("protocol ");
+        self.push(&decl.name.name);
+        self.push(" {");
+        self.indent += 1;
+
+        for (i, member) in decl.members.iter().enumerate() {
+            if i > 0 {
+                self.buf.push('\n');
+            }
+
+            self.newline();
+
+            match &member.kind {
+                ProtocolMemberKind::TYPE(assoc) => {
+                    self.push("type ");
+                    self.push(&assoc.name.name);
+
+                    if !assoc.bounds.is_empty() {
+                        let bounds: Vec<&str> =
+                            assoc.bounds.iter().map(|b| b.name.as_str()).collect();
+
+                        self.push(": ");
+                        self.push(&bounds.join(" + "));
+                    }
+
+                    self.push(";");
+                }
+
+                ProtocolMemberKind::METHOD(func) => self.fn_decl(func),
+            }
+        }
+
+        self.close_body(!decl.members.is_empty());
+    }
+
+    /// Ends the body of a declaration, whose indentation has already been increased
+    ///
+    /// # Arguments
+    ///
+    /// * `has_contents` - whether anything was written in the body (so that the `}` goes on
+    ///   its own line)
+    ///
+    fn close_body(&mut self, has_contents: bool) {
+        self.indent -= 1;
+
+        if has_contents {
+            self.newline();
+        }
+
+        self.push("}");
+    }
+
+    fn block(&mut self, block: &Block) {
+        let stmts: Vec<&Stmt> = block
+            .stmts
+            .iter()
+            .filter(|s| s.kind != StmtKind::ERROR)
+            .collect();
+
+        if stmts.is_empty() && block.tail.is_none() {
+            self.push("{}");
+            return;
+        }
+
+        self.push("{");
+        self.indent += 1;
+
+        for (i, stmt) in stmts.iter().enumerate() {
+            self.newline();
+
+            match &stmt.kind {
+                StmtKind::VAR(decl) => self.var_decl(decl),
+
+                StmtKind::EXPR(expr) => {
+                    self.expr(expr);
+
+                    // a block-like expression at the very end would be read as the tail
+                    let is_last: bool = i == stmts.len() - 1 && block.tail.is_none();
+
+                    if !expr.is_block_like() || is_last {
+                        self.push(";");
+                    }
+                }
+
+                StmtKind::ERROR => (),
+            }
+        }
+
+        if let Some(tail) = &block.tail {
+            self.newline();
+            self.expr(tail);
+        }
+
+        self.indent -= 1;
+        self.newline();
+        self.push("}");
+    }
+
+    fn label(&mut self, label: &Option<Iden>) {
+        if let Some(label) = label {
+            self.push(&label.name);
+            self.push(": ");
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::LITERAL(lit) => self.push(&lit.to_string()),
+            ExprKind::IDEN(name) => self.push(name),
+            ExprKind::SELF => self.push("self"),
+            ExprKind::SUPER => self.push("super"),
+
+            ExprKind::PAREN(inner) => {
+                self.push("(");
+                self.expr(inner);
+                self.push(")");
+            }
+
+            ExprKind::TUPLE(elems) => self.tuple(elems, Self::expr),
+
+            ExprKind::ARRAY(elems) => {
+                self.push("[");
+                self.list(elems, Self::expr);
+                self.push("]");
+            }
+
+            ExprKind::ARRAY_REPEAT { value, len } => {
+                self.push("[");
+                self.expr(value);
+                self.push("; ");
+                self.expr(len);
+                self.push("]");
+            }
+
+            ExprKind::BLOCK(block) => self.block(block),
+
+            ExprKind::UNARY { op, expr } => {
+                self.push(&op.to_string());
+
+                // `- 1` is not the same as the negative literal `-1`
+                if *op == UnaryOp::NEG && matches!(expr.kind, ExprKind::LITERAL(_)) {
+                    self.push(" ");
+                }
+
+                self.expr(expr);
+            }
+
+            ExprKind::BINARY { op, lhs, rhs } => {
+                self.expr(lhs);
+                self.push(&format!(" {} ", op));
+                self.expr(rhs);
+            }
+
+            ExprKind::TERNARY { cond, then, els } => {
+                self.expr(cond);
+                self.push(" ? ");
+                self.expr(then);
+                self.push(" : ");
+                self.expr(els);
+            }
+
+            ExprKind::RANGE {
+                start,
+                end,
+                is_inclusive,
+            } => {
+                self.expr(start);
+                self.push(if *is_inclusive { "..=" } else { ".." });
+                self.expr(end);
+            }
+
+            ExprKind::ASSIGN { op, target, value } => {
+                self.expr(target);
+                self.push(&format!(" {} ", op));
+                self.expr(value);
+            }
+
+            ExprKind::FIELD { expr, field } => {
+                self.expr(expr);
+                self.push(".");
+                self.push(&field.name);
+            }
+
+            ExprKind::INDEX { expr, index } => {
+                self.expr(expr);
+                self.push("[");
+                self.expr(index);
+                self.push("]");
+            }
+
+            ExprKind::CALL { callee, args } => {
+                self.expr(callee);
+                self.push("(");
+                self.list(args, Self::expr);
+                self.push(")");
+            }
+
+            ExprKind::IF { cond, then, els } => {
+                self.push("if ");
+                self.expr(cond);
+                self.push(" ");
+                self.block(then);
+
+                if let Some(els) = els {
+                    self.push(" else ");
+                    self.expr(els);
+                }
+            }
+
+            ExprKind::MATCH { scrutinee, arms } => {
+                self.push("match ");
+                self.expr(scrutinee);
+                self.push(" {");
+                self.indent += 1;
+
+                for arm in arms {
+                    self.newline();
+                    self.pattern(&arm.pattern);
+
+                    if let Some(guard) = &arm.guard {
+                        self.push(" if ");
+                        self.expr(guard);
+                    }
+
+                    self.push(" => ");
+                    self.expr(&arm.body);
+                    self.push(",");
+                }
+
+                self.close_body(!arms.is_empty());
+            }
+
+            ExprKind::WHILE { label, cond, body } => {
+                self.label(label);
+                self.push("while ");
+                self.expr(cond);
+                self.push(" ");
+                self.block(body);
+            }
+
+            ExprKind::LOOP { label, body } => {
+                self.label(label);
+                self.push("loop ");
+                self.block(body);
+            }
+
+            ExprKind::FOR {
+                label,
+                pattern,
+                iter,
+                body,
+            } => {
+                self.label(label);
+                self.push("for ");
+                self.pattern(pattern);
+                self.push(" in ");
+                self.expr(iter);
+                self.push(" ");
+                self.block(body);
+            }
+
+            ExprKind::BREAK { label, value } => {
+                self.push("break");
+
+                if let Some(label) = label {
+                    self.push(" ");
+                    self.push(&label.name);
+                }
+
+                if let Some(value) = value {
+                    self.push(" ");
+                    self.expr(value);
+                }
+            }
+
+            ExprKind::CONTINUE { label } => {
+                self.push("continue");
+
+                if let Some(label) = label {
+                    self.push(" ");
+                    self.push(&label.name);
+                }
+            }
+
+            ExprKind::RETURN(value) => {
+                self.push("return");
+
+                if let Some(value) = value {
+                    self.push(" ");
+                    self.expr(value);
+                }
+            }
+
+            ExprKind::NEW { class, args } => {
+                self.push("new ");
+                self.push(&path(class));
+                self.push("(");
+                self.list(args, Self::expr);
+                self.push(")");
+            }
+
+            ExprKind::STRUCT { path: p, fields } => {
+                self.push(&path(p));
+
+                if fields.is_empty() {
+                    self.push(" {}");
+                    return;
+                }
+
+                self.push(" { ");
+
+                self.list(fields, |printer, field| {
+                    printer.push(&field.name.name);
+
+                    // `{ x: x }` is written `{ x }`
+                    if !matches!(&field.value.kind, ExprKind::IDEN(n) if *n == field.name.name) {
+                        printer.push(": ");
+                        printer.expr(&field.value);
+                    }
+                });
+
+                self.push(" }");
+            }
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Pattern) {
+        match &pattern.kind {
+            PatternKind::WILDCARD => self.push("_"),
+            PatternKind::BINDING(name) => self.push(&name.name),
+            PatternKind::LITERAL(lit) => self.push(&lit.to_string()),
+
+            PatternKind::RANGE {
+                start,
+                end,
+                is_inclusive,
+            } => {
+                self.push(&start.to_string());
+                self.push(if *is_inclusive { "..=" } else { ".." });
+                self.push(&end.to_string());
+            }
+
+            PatternKind::TUPLE(elems) => self.tuple(elems, Self::pattern),
+
+            PatternKind::VARIANT { path: p, elems } => {
+                self.push(&path(p));
+
+                if let Some(elems) = elems {
+                    self.push("(");
+                    self.list(elems, Self::pattern);
+                    self.push(")");
+                }
+            }
+
+            PatternKind::STRUCT {
+                path: p,
+                fields,
+                has_rest,
+            } => {
+                self.push(&path(p));
+
+                if fields.is_empty() && !has_rest {
+                    self.push(" {}");
+                    return;
+                }
+
+                self.push(" { ");
+
+                self.list(fields, |printer, field| {
+                    printer.push(&field.name.name);
+
+                    // `{ x: x }` is written `{ x }`
+                    if !matches!(&field.pattern.kind, PatternKind::BINDING(n) if n.name == field.name.name)
+                    {
+                        printer.push(": ");
+                        printer.pattern(&field.pattern);
+                    }
+                });
+
+                if *has_rest {
+                    self.push(if fields.is_empty() { ".." } else { ", .." });
+                }
+
+                self.push(" }");
+            }
+
+            PatternKind::OR(alts) => {
+                for (i, alt) in alts.iter().enumerate() {
+                    if i > 0 {
+                        self.push(" | ");
+                    }
+
+                    self.pattern(alt);
+                }
+            }
+        }
+    }
+
+    fn ty(&mut self, ty: &Type) {
+        match &ty.kind {
+            TypeKind::BOOL => self.push("bool"),
+            TypeKind::CHAR => self.push("char"),
+            TypeKind::FLOAT => self.push("float"),
+            TypeKind::INT => self.push("int"),
+            TypeKind::STRING => self.push("String"),
+            TypeKind::UINT => self.push("uint"),
+
+            TypeKind::NAMED { path: p, args } => {
+                self.push(&path(p));
+
+                if !args.is_empty() {
+                    self.push("<");
+                    self.list(args, Self::ty);
+                    self.push(">");
+                }
+            }
+
+            TypeKind::ARRAY(elem) => {
+                self.push("[");
+                self.ty(elem);
+                self.push("]");
+            }
+
+            TypeKind::FIXED_ARRAY { elem, len } => {
+                self.push("[");
+                self.ty(elem);
+                self.push("; ");
+                self.expr(len);
+                self.push("]");
+            }
+
+            TypeKind::TUPLE(elems) => self.tuple(elems, Self::ty),
+
+            TypeKind::FUNC { params, ret } => {
+                self.push("func(");
+                self.list(params, Self::ty);
+                self.push(")");
+
+                if let Some(ret) = ret {
+                    self.push(" -> ");
+                    self.ty(ret);
+                }
+            }
+
+            TypeKind::OPTIONAL(inner) => {
+                // the `?` of `func() -> T?` belongs to `T`
+                match inner.kind {
+                    TypeKind::FUNC { .. } => {
+                        self.push("(");
+                        self.ty(inner);
+                        self.push(")");
+                    }
+
+                    _ => self.ty(inner),
+                }
+
+                self.push("?");
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use super::{FnDecl, Iden};
+use crate::span::Span;
+
+/// A protocol declaration, e.g. `protocol Printable { func print() -> String; }`
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtocolDecl {
+    pub name: Iden,
+    pub members: Vec<ProtocolMember>,
+}
+
+/// An associated type or method of a protocol
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtocolMember {
+    pub kind: ProtocolMemberKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum ProtocolMemberKind {
+    /// `type Output;` or `type Output: Printable + Eq;`
+    TYPE(AssocType),
+
+    /// `func print() -> String;` (required), or with a body (a default implementation)
+    METHOD(FnDecl),
+}
+
+/// An associated type, which every conforming type binds with a `type` member, along with
+/// the protocols it must conform to
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssocType {
+    pub name: Iden,
+    pub bounds: Vec<Iden>,
+}
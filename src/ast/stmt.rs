@@ -0,0 +1,30 @@
+use super::{Expr, VarDecl};
+use crate::span::Span;
+
+/// A list of statements between curly braces, optionally ending in an expression without a
+/// trailing `;`, which is the value of the block
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub stmts: Vec<Stmt>,
+    pub tail: Option<Box<Expr>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum StmtKind {
+    VAR(Box<VarDecl>),
+
+    /// an expression followed by `;` (which is optional after block-like expressions)
+    EXPR(Expr),
+
+    /// tokens skipped after a syntax error, up to the next statement
+    ERROR,
+}
@@ -0,0 +1,56 @@
+use super::{Expr, Iden};
+use crate::span::Span;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type {
+    pub kind: TypeKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum TypeKind {
+    BOOL,
+    CHAR,
+    FLOAT,
+    INT,
+    STRING,
+    UINT,
+
+    /// a user-defined type or generic parameter, referred to by (a path to) its name, with
+    /// any generic arguments (e.g. `geometry.Point` or `Pair<int, float>`)
+    NAMED {
+        path: Vec<Iden>,
+        args: Vec<Type>,
+    },
+
+    /// `[T]`, an array of any length
+    ARRAY(Box<Type>),
+
+    /// `[T; N]`, an array whose length is a constant expression
+    FIXED_ARRAY {
+        elem: Box<Type>,
+        len: Box<Expr>,
+    },
+
+    /// `(int, float)`, or `()` for the unit type
+    TUPLE(Vec<Type>),
+
+    /// `func(int, int) -> bool`
+    FUNC {
+        params: Vec<Type>,
+        ret: Option<Box<Type>>,
+    },
+
+    /// `T?`, either a `T` or nothing
+    OPTIONAL(Box<Type>),
+}
+
+/// A generic parameter, with the protocols its arguments must conform to (e.g. `T: A + B`)
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericParam {
+    pub name: Iden,
+    pub bounds: Vec<Iden>,
+    pub span: Span,
+}
@@ -0,0 +1,497 @@
+//! Read-only traversal of a syntax tree
+//!
+//! A pass implements `Visitor`, overriding the methods for the nodes it cares about. Each
+//! method defaults to the matching `walk_*` function, which visits the node's children in
+//! source order, so an override calls `walk_*` itself to keep descending (or doesn't, to skip
+//! the node's children)
+
+use super::{
+    AssocType, Block, ClassDecl, ClassField, Constructor, EnumDecl, Expr, ExprKind, Field,
+    FieldInit, FieldPattern, FnDecl, GenericParam, ImportTree, Item, ItemKind, MatchArm, Member,
+    MemberKind, Module, Param, Pattern, PatternKind, ProtocolDecl, ProtocolMember,
+    ProtocolMemberKind, Stmt, StmtKind, StructDecl, Type, TypeAlias, TypeKind, VarDecl, Variant,
+    VariantKind,
+};
+
+/// A read-only pass over a syntax tree (see the module docs)
+///
+pub trait Visitor: Sized {
+    fn visit_module(&mut self, module: &Module) {
+        walk_module(self, module);
+    }
+
+    fn visit_item(&mut self, item: &Item) {
+        walk_item(self, item);
+    }
+
+    fn visit_var_decl(&mut self, decl: &VarDecl) {
+        walk_var_decl(self, decl);
+    }
+
+    fn visit_fn_decl(&mut self, func: &FnDecl) {
+        walk_fn_decl(self, func);
+    }
+
+    fn visit_param(&mut self, param: &Param) {
+        walk_param(self, param);
+    }
+
+    fn visit_generic_param(&mut self, _param: &GenericParam) {}
+
+    fn visit_struct_decl(&mut self, decl: &StructDecl) {
+        walk_struct_decl(self, decl);
+    }
+
+    fn visit_field(&mut self, field: &Field) {
+        walk_field(self, field);
+    }
+
+    fn visit_enum_decl(&mut self, decl: &EnumDecl) {
+        walk_enum_decl(self, decl);
+    }
+
+    fn visit_variant(&mut self, variant: &Variant) {
+        walk_variant(self, variant);
+    }
+
+    fn visit_class_decl(&mut self, decl: &ClassDecl) {
+        walk_class_decl(self, decl);
+    }
+
+    fn visit_member(&mut self, member: &Member) {
+        walk_member(self, member);
+    }
+
+    fn visit_class_field(&mut self, field: &ClassField) {
+        walk_class_field(self, field);
+    }
+
+    fn visit_constructor(&mut self, constructor: &Constructor) {
+        walk_constructor(self, constructor);
+    }
+
+    fn visit_protocol_decl(&mut self, decl: &ProtocolDecl) {
+        walk_protocol_decl(self, decl);
+    }
+
+    fn visit_protocol_member(&mut self, member: &ProtocolMember) {
+        walk_protocol_member(self, member);
+    }
+
+    fn visit_assoc_type(&mut self, _assoc: &AssocType) {}
+
+    fn visit_type_alias(&mut self, alias: &TypeAlias) {
+        walk_type_alias(self, alias);
+    }
+
+    fn visit_import_tree(&mut self, _tree: &ImportTree) {}
+
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block);
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        walk_expr(self, expr);
+    }
+
+    fn visit_match_arm(&mut self, arm: &MatchArm) {
+        walk_match_arm(self, arm);
+    }
+
+    fn visit_field_init(&mut self, field: &FieldInit) {
+        walk_field_init(self, field);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        walk_pattern(self, pattern);
+    }
+
+    fn visit_field_pattern(&mut self, field: &FieldPattern) {
+        walk_field_pattern(self, field);
+    }
+
+    fn visit_type(&mut self, ty: &Type) {
+        walk_type(self, ty);
+    }
+}
+
+pub fn walk_module<V: Visitor>(v: &mut V, module: &Module) {
+    for item in &module.items {
+        v.visit_item(item);
+    }
+}
+
+pub fn walk_item<V: Visitor>(v: &mut V, item: &Item) {
+    match &item.kind {
+        ItemKind::VAR(decl) => v.visit_var_decl(decl),
+        ItemKind::FUNC(func) => v.visit_fn_decl(func),
+        ItemKind::STRUCT(decl) => v.visit_struct_decl(decl),
+        ItemKind::ENUM(decl) => v.visit_enum_decl(decl),
+        ItemKind::CLASS(decl) => v.visit_class_decl(decl),
+        ItemKind::PROTOCOL(decl) => v.visit_protocol_decl(decl),
+        ItemKind::IMPORT(tree) => v.visit_import_tree(tree),
+        ItemKind::ALIAS(alias) | ItemKind::TYPE(alias) => v.visit_type_alias(alias),
+        ItemKind::LIB(_) | ItemKind::ERROR => (),
+    }
+}
+
+pub fn walk_var_decl<V: Visitor>(v: &mut V, decl: &VarDecl) {
+    v.visit_pattern(&decl.pattern);
+
+    if let Some(ty) = &decl.ty {
+        v.visit_type(ty);
+    }
+
+    if let Some(value) = &decl.value {
+        v.visit_expr(value);
+    }
+}
+
+pub fn walk_fn_decl<V: Visitor>(v: &mut V, func: &FnDecl) {
+    for param in &func.generics {
+        v.visit_generic_param(param);
+    }
+
+    for param in &func.params {
+        v.visit_param(param);
+    }
+
+    if let Some(ret) = &func.ret {
+        v.visit_type(ret);
+    }
+
+    if let Some(body) = &func.body {
+        v.visit_block(body);
+    }
+}
+
+pub fn walk_param<V: Visitor>(v: &mut V, param: &Param) {
+    v.visit_pattern(&param.pattern);
+    v.visit_type(&param.ty);
+
+    if let Some(default) = &param.default {
+        v.visit_expr(default);
+    }
+}
+
+pub fn walk_struct_decl<V: Visitor>(v: &mut V, decl: &StructDecl) {
+    for param in &decl.generics {
+        v.visit_generic_param(param);
+    }
+
+    for field in &decl.fields {
+        v.visit_field(field);
+    }
+
+    for member in &decl.members {
+        v.visit_member(member);
+    }
+}
+
+pub fn walk_field<V: Visitor>(v: &mut V, field: &Field) {
+    v.visit_type(&field.ty);
+}
+
+pub fn walk_enum_decl<V: Visitor>(v: &mut V, decl: &EnumDecl) {
+    for param in &decl.generics {
+        v.visit_generic_param(param);
+    }
+
+    for variant in &decl.variants {
+        v.visit_variant(variant);
+    }
+
+    for member in &decl.members {
+        v.visit_member(member);
+    }
+}
+
+pub fn walk_variant<V: Visitor>(v: &mut V, variant: &Variant) {
+    match &variant.kind {
+        VariantKind::UNIT => (),
+
+        VariantKind::TUPLE(types) => {
+            for ty in types {
+                v.visit_type(ty);
+            }
+        }
+
+        VariantKind::STRUCT(fields) => {
+            for field in fields {
+                v.visit_field(field);
+            }
+        }
+    }
+
+    if let Some(discriminant) = &variant.discriminant {
+        v.visit_expr(discriminant);
+    }
+}
+
+pub fn walk_class_decl<V: Visitor>(v: &mut V, decl: &ClassDecl) {
+    for param in &decl.generics {
+        v.visit_generic_param(param);
+    }
+
+    for member in &decl.members {
+        v.visit_member(member);
+    }
+}
+
+pub fn walk_member<V: Visitor>(v: &mut V, member: &Member) {
+    match &member.kind {
+        MemberKind::FIELD(field) => v.visit_class_field(field),
+        MemberKind::CONSTRUCTOR(constructor) => v.visit_constructor(constructor),
+        MemberKind::METHOD(func) => v.visit_fn_decl(func),
+        MemberKind::TYPE(alias) => v.visit_type_alias(alias),
+        MemberKind::ERROR => (),
+    }
+}
+
+pub fn walk_class_field<V: Visitor>(v: &mut V, field: &ClassField) {
+    v.visit_type(&field.ty);
+
+    if let Some(value) = &field.value {
+        v.visit_expr(value);
+    }
+}
+
+pub fn walk_constructor<V: Visitor>(v: &mut V, constructor: &Constructor) {
+    for param in &constructor.params {
+        v.visit_param(param);
+    }
+
+    v.visit_block(&constructor.body);
+}
+
+pub fn walk_protocol_decl<V: Visitor>(v: &mut V, decl: &ProtocolDecl) {
+    for member in &decl.members {
+        v.visit_protocol_member(member);
+    }
+}
+
+pub fn walk_protocol_member<V: Visitor>(v: &mut V, member: &ProtocolMember) {
+    match &member.kind {
+        ProtocolMemberKind::TYPE(assoc) => v.visit_assoc_type(assoc),
+        ProtocolMemberKind::METHOD(func) => v.visit_fn_decl(func),
+    }
+}
+
+pub fn walk_type_alias<V: Visitor>(v: &mut V, alias: &TypeAlias) {
+    for param in &alias.generics {
+        v.visit_generic_param(param);
+    }
+
+    v.visit_type(&alias.ty);
+}
+
+pub fn walk_block<V: Visitor>(v: &mut V, block: &Block) {
+    for stmt in &block.stmts {
+        v.visit_stmt(stmt);
+    }
+
+    if let Some(tail) = &block.tail {
+        v.visit_expr(tail);
+    }
+}
+
+pub fn walk_stmt<V: Visitor>(v: &mut V, stmt: &Stmt) {
+    match &stmt.kind {
+        StmtKind::VAR(decl) => v.visit_var_decl(decl),
+        StmtKind::EXPR(expr) => v.visit_expr(expr),
+        StmtKind::ERROR => (),
+    }
+}
+
+pub fn walk_expr<V: Visitor>(v: &mut V, expr: &Expr) {
+    match &expr.kind {
+        ExprKind::LITERAL(_) | ExprKind::IDEN(_) | ExprKind::SELF | ExprKind::SUPER => (),
+        ExprKind::CONTINUE { .. } => (),
+
+        ExprKind::PAREN(inner) | ExprKind::UNARY { expr: inner, .. } => v.visit_expr(inner),
+        ExprKind::FIELD { expr: inner, .. } => v.visit_expr(inner),
+
+        ExprKind::TUPLE(elems) | ExprKind::ARRAY(elems) => {
+            for elem in elems {
+                v.visit_expr(elem);
+            }
+        }
+
+        ExprKind::ARRAY_REPEAT { value, len } => {
+            v.visit_expr(value);
+            v.visit_expr(len);
+        }
+
+        ExprKind::BLOCK(block) => v.visit_block(block),
+
+        ExprKind::BINARY { lhs, rhs, .. } => {
+            v.visit_expr(lhs);
+            v.visit_expr(rhs);
+        }
+
+        ExprKind::TERNARY { cond, then, els } => {
+            v.visit_expr(cond);
+            v.visit_expr(then);
+            v.visit_expr(els);
+        }
+
+        ExprKind::RANGE { start, end, .. } => {
+            v.visit_expr(start);
+            v.visit_expr(end);
+        }
+
+        ExprKind::ASSIGN { target, value, .. } => {
+            v.visit_expr(target);
+            v.visit_expr(value);
+        }
+
+        ExprKind::INDEX { expr: inner, index } => {
+            v.visit_expr(inner);
+            v.visit_expr(index);
+        }
+
+        ExprKind::CALL { callee, args } => {
+            v.visit_expr(callee);
+
+            for arg in args {
+                v.visit_expr(arg);
+            }
+        }
+
+        ExprKind::IF { cond, then, els } => {
+            v.visit_expr(cond);
+            v.visit_block(then);
+
+            if let Some(els) = els {
+                v.visit_expr(els);
+            }
+        }
+
+        ExprKind::MATCH { scrutinee, arms } => {
+            v.visit_expr(scrutinee);
+
+            for arm in arms {
+                v.visit_match_arm(arm);
+            }
+        }
+
+        ExprKind::WHILE { cond, body, .. } => {
+            v.visit_expr(cond);
+            v.visit_block(body);
+        }
+
+        ExprKind::LOOP { body, .. } => v.visit_block(body),
+
+        ExprKind::FOR {
+            pattern,
+            iter,
+            body,
+            ..
+        } => {
+            v.visit_pattern(pattern);
+            v.visit_expr(iter);
+            v.visit_block(body);
+        }
+
+        ExprKind::BREAK { value, .. } | ExprKind::RETURN(value) => {
+            if let Some(value) = value {
+                v.visit_expr(value);
+            }
+        }
+
+        ExprKind::NEW { args, .. } => {
+            for arg in args {
+                v.visit_expr(arg);
+            }
+        }
+
+        ExprKind::STRUCT { fields, .. } => {
+            for field in fields {
+                v.visit_field_init(field);
+            }
+        }
+    }
+}
+
+pub fn walk_match_arm<V: Visitor>(v: &mut V, arm: &MatchArm) {
+    v.visit_pattern(&arm.pattern);
+
+    if let Some(guard) = &arm.guard {
+        v.visit_expr(guard);
+    }
+
+    v.visit_expr(&arm.body);
+}
+
+pub fn walk_field_init<V: Visitor>(v: &mut V, field: &FieldInit) {
+    v.visit_expr(&field.value);
+}
+
+pub fn walk_pattern<V: Visitor>(v: &mut V, pattern: &Pattern) {
+    match &pattern.kind {
+        PatternKind::WILDCARD
+        | PatternKind::BINDING(_)
+        | PatternKind::LITERAL(_)
+        | PatternKind::RANGE { .. } => (),
+
+        PatternKind::TUPLE(elems) | PatternKind::OR(elems) => {
+            for elem in elems {
+                v.visit_pattern(elem);
+            }
+        }
+
+        PatternKind::VARIANT { elems, .. } => {
+            for elem in elems.iter().flatten() {
+                v.visit_pattern(elem);
+            }
+        }
+
+        PatternKind::STRUCT { fields, .. } => {
+            for field in fields {
+                v.visit_field_pattern(field);
+            }
+        }
+    }
+}
+
+pub fn walk_field_pattern<V: Visitor>(v: &mut V, field: &FieldPattern) {
+    v.visit_pattern(&field.pattern);
+}
+
+pub fn walk_type<V: Visitor>(v: &mut V, ty: &Type) {
+    match &ty.kind {
+        TypeKind::BOOL
+        | TypeKind::CHAR
+        | TypeKind::FLOAT
+        | TypeKind::INT
+        | TypeKind::STRING
+        | TypeKind::UINT => (),
+
+        TypeKind::NAMED { args: types, .. } | TypeKind::TUPLE(types) => {
+            for ty in types {
+                v.visit_type(ty);
+            }
+        }
+
+        TypeKind::ARRAY(elem) | TypeKind::OPTIONAL(elem) => v.visit_type(elem),
+
+        TypeKind::FIXED_ARRAY { elem, len } => {
+            v.visit_type(elem);
+            v.visit_expr(len);
+        }
+
+        TypeKind::FUNC { params, ret } => {
+            for param in params {
+                v.visit_type(param);
+            }
+
+            if let Some(ret) = ret {
+                v.visit_type(ret);
+            }
+        }
+    }
+}
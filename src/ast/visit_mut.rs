@@ -0,0 +1,495 @@
+//! In-place traversal of a syntax tree
+//!
+//! `VisitorMut` mirrors `Visitor`, but hands out mutable references, so a pass can rewrite
+//! nodes (e.g. desugaring an expression) as it walks. To build a new tree instead, see `Fold`
+
+use super::{
+    AssocType, Block, ClassDecl, ClassField, Constructor, EnumDecl, Expr, ExprKind, Field,
+    FieldInit, FieldPattern, FnDecl, GenericParam, ImportTree, Item, ItemKind, MatchArm, Member,
+    MemberKind, Module, Param, Pattern, PatternKind, ProtocolDecl, ProtocolMember,
+    ProtocolMemberKind, Stmt, StmtKind, StructDecl, Type, TypeAlias, TypeKind, VarDecl, Variant,
+    VariantKind,
+};
+
+/// A pass that edits a syntax tree in place (see the module docs)
+///
+pub trait VisitorMut: Sized {
+    fn visit_module_mut(&mut self, module: &mut Module) {
+        walk_module_mut(self, module);
+    }
+
+    fn visit_item_mut(&mut self, item: &mut Item) {
+        walk_item_mut(self, item);
+    }
+
+    fn visit_var_decl_mut(&mut self, decl: &mut VarDecl) {
+        walk_var_decl_mut(self, decl);
+    }
+
+    fn visit_fn_decl_mut(&mut self, func: &mut FnDecl) {
+        walk_fn_decl_mut(self, func);
+    }
+
+    fn visit_param_mut(&mut self, param: &mut Param) {
+        walk_param_mut(self, param);
+    }
+
+    fn visit_generic_param_mut(&mut self, _param: &mut GenericParam) {}
+
+    fn visit_struct_decl_mut(&mut self, decl: &mut StructDecl) {
+        walk_struct_decl_mut(self, decl);
+    }
+
+    fn visit_field_mut(&mut self, field: &mut Field) {
+        walk_field_mut(self, field);
+    }
+
+    fn visit_enum_decl_mut(&mut self, decl: &mut EnumDecl) {
+        walk_enum_decl_mut(self, decl);
+    }
+
+    fn visit_variant_mut(&mut self, variant: &mut Variant) {
+        walk_variant_mut(self, variant);
+    }
+
+    fn visit_class_decl_mut(&mut self, decl: &mut ClassDecl) {
+        walk_class_decl_mut(self, decl);
+    }
+
+    fn visit_member_mut(&mut self, member: &mut Member) {
+        walk_member_mut(self, member);
+    }
+
+    fn visit_class_field_mut(&mut self, field: &mut ClassField) {
+        walk_class_field_mut(self, field);
+    }
+
+    fn visit_constructor_mut(&mut self, constructor: &mut Constructor) {
+        walk_constructor_mut(self, constructor);
+    }
+
+    fn visit_protocol_decl_mut(&mut self, decl: &mut ProtocolDecl) {
+        walk_protocol_decl_mut(self, decl);
+    }
+
+    fn visit_protocol_member_mut(&mut self, member: &mut ProtocolMember) {
+        walk_protocol_member_mut(self, member);
+    }
+
+    fn visit_assoc_type_mut(&mut self, _assoc: &mut AssocType) {}
+
+    fn visit_type_alias_mut(&mut self, alias: &mut TypeAlias) {
+        walk_type_alias_mut(self, alias);
+    }
+
+    fn visit_import_tree_mut(&mut self, _tree: &mut ImportTree) {}
+
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block);
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        walk_stmt_mut(self, stmt);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        walk_expr_mut(self, expr);
+    }
+
+    fn visit_match_arm_mut(&mut self, arm: &mut MatchArm) {
+        walk_match_arm_mut(self, arm);
+    }
+
+    fn visit_field_init_mut(&mut self, field: &mut FieldInit) {
+        walk_field_init_mut(self, field);
+    }
+
+    fn visit_pattern_mut(&mut self, pattern: &mut Pattern) {
+        walk_pattern_mut(self, pattern);
+    }
+
+    fn visit_field_pattern_mut(&mut self, field: &mut FieldPattern) {
+        walk_field_pattern_mut(self, field);
+    }
+
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        walk_type_mut(self, ty);
+    }
+}
+
+pub fn walk_module_mut<V: VisitorMut>(v: &mut V, module: &mut Module) {
+    for item in &mut module.items {
+        v.visit_item_mut(item);
+    }
+}
+
+pub fn walk_item_mut<V: VisitorMut>(v: &mut V, item: &mut Item) {
+    match &mut item.kind {
+        ItemKind::VAR(decl) => v.visit_var_decl_mut(decl),
+        ItemKind::FUNC(func) => v.visit_fn_decl_mut(func),
+        ItemKind::STRUCT(decl) => v.visit_struct_decl_mut(decl),
+        ItemKind::ENUM(decl) => v.visit_enum_decl_mut(decl),
+        ItemKind::CLASS(decl) => v.visit_class_decl_mut(decl),
+        ItemKind::PROTOCOL(decl) => v.visit_protocol_decl_mut(decl),
+        ItemKind::IMPORT(tree) => v.visit_import_tree_mut(tree),
+        ItemKind::ALIAS(alias) | ItemKind::TYPE(alias) => v.visit_type_alias_mut(alias),
+        ItemKind::LIB(_) | ItemKind::ERROR => (),
+    }
+}
+
+pub fn walk_var_decl_mut<V: VisitorMut>(v: &mut V, decl: &mut VarDecl) {
+    v.visit_pattern_mut(&mut decl.pattern);
+
+    if let Some(ty) = &mut decl.ty {
+        v.visit_type_mut(ty);
+    }
+
+    if let Some(value) = &mut decl.value {
+        v.visit_expr_mut(value);
+    }
+}
+
+pub fn walk_fn_decl_mut<V: VisitorMut>(v: &mut V, func: &mut FnDecl) {
+    for param in &mut func.generics {
+        v.visit_generic_param_mut(param);
+    }
+
+    for param in &mut func.params {
+        v.visit_param_mut(param);
+    }
+
+    if let Some(ret) = &mut func.ret {
+        v.visit_type_mut(ret);
+    }
+
+    if let Some(body) = &mut func.body {
+        v.visit_block_mut(body);
+    }
+}
+
+pub fn walk_param_mut<V: VisitorMut>(v: &mut V, param: &mut Param) {
+    v.visit_pattern_mut(&mut param.pattern);
+    v.visit_type_mut(&mut param.ty);
+
+    if let Some(default) = &mut param.default {
+        v.visit_expr_mut(default);
+    }
+}
+
+pub fn walk_struct_decl_mut<V: VisitorMut>(v: &mut V, decl: &mut StructDecl) {
+    for param in &mut decl.generics {
+        v.visit_generic_param_mut(param);
+    }
+
+    for field in &mut decl.fields {
+        v.visit_field_mut(field);
+    }
+
+    for member in &mut decl.members {
+        v.visit_member_mut(member);
+    }
+}
+
+pub fn walk_field_mut<V: VisitorMut>(v: &mut V, field: &mut Field) {
+    v.visit_type_mut(&mut field.ty);
+}
+
+pub fn walk_enum_decl_mut<V: VisitorMut>(v: &mut V, decl: &mut EnumDecl) {
+    for param in &mut decl.generics {
+        v.visit_generic_param_mut(param);
+    }
+
+    for variant in &mut decl.variants {
+        v.visit_variant_mut(variant);
+    }
+
+    for member in &mut decl.members {
+        v.visit_member_mut(member);
+    }
+}
+
+pub fn walk_variant_mut<V: VisitorMut>(v: &mut V, variant: &mut Variant) {
+    match &mut variant.kind {
+        VariantKind::UNIT => (),
+
+        VariantKind::TUPLE(types) => {
+            for ty in types {
+                v.visit_type_mut(ty);
+            }
+        }
+
+        VariantKind::STRUCT(fields) => {
+            for field in fields {
+                v.visit_field_mut(field);
+            }
+        }
+    }
+
+    if let Some(discriminant) = &mut variant.discriminant {
+        v.visit_expr_mut(discriminant);
+    }
+}
+
+pub fn walk_class_decl_mut<V: VisitorMut>(v: &mut V, decl: &mut ClassDecl) {
+    for param in &mut decl.generics {
+        v.visit_generic_param_mut(param);
+    }
+
+    for member in &mut decl.members {
+        v.visit_member_mut(member);
+    }
+}
+
+pub fn walk_member_mut<V: VisitorMut>(v: &mut V, member: &mut Member) {
+    match &mut member.kind {
+        MemberKind::FIELD(field) => v.visit_class_field_mut(field),
+        MemberKind::CONSTRUCTOR(constructor) => v.visit_constructor_mut(constructor),
+        MemberKind::METHOD(func) => v.visit_fn_decl_mut(func),
+        MemberKind::TYPE(alias) => v.visit_type_alias_mut(alias),
+        MemberKind::ERROR => (),
+    }
+}
+
+pub fn walk_class_field_mut<V: VisitorMut>(v: &mut V, field: &mut ClassField) {
+    v.visit_type_mut(&mut field.ty);
+
+    if let Some(value) = &mut field.value {
+        v.visit_expr_mut(value);
+    }
+}
+
+pub fn walk_constructor_mut<V: VisitorMut>(v: &mut V, constructor: &mut Constructor) {
+    for param in &mut constructor.params {
+        v.visit_param_mut(param);
+    }
+
+    v.visit_block_mut(&mut constructor.body);
+}
+
+pub fn walk_protocol_decl_mut<V: VisitorMut>(v: &mut V, decl: &mut ProtocolDecl) {
+    for member in &mut decl.members {
+        v.visit_protocol_member_mut(member);
+    }
+}
+
+pub fn walk_protocol_member_mut<V: VisitorMut>(v: &mut V, member: &mut ProtocolMember) {
+    match &mut member.kind {
+        ProtocolMemberKind::TYPE(assoc) => v.visit_assoc_type_mut(assoc),
+        ProtocolMemberKind::METHOD(func) => v.visit_fn_decl_mut(func),
+    }
+}
+
+pub fn walk_type_alias_mut<V: VisitorMut>(v: &mut V, alias: &mut TypeAlias) {
+    for param in &mut alias.generics {
+        v.visit_generic_param_mut(param);
+    }
+
+    v.visit_type_mut(&mut alias.ty);
+}
+
+pub fn walk_block_mut<V: VisitorMut>(v: &mut V, block: &mut Block) {
+    for stmt in &mut block.stmts {
+        v.visit_stmt_mut(stmt);
+    }
+
+    if let Some(tail) = &mut block.tail {
+        v.visit_expr_mut(tail);
+    }
+}
+
+pub fn walk_stmt_mut<V: VisitorMut>(v: &mut V, stmt: &mut Stmt) {
+    match &mut stmt.kind {
+        StmtKind::VAR(decl) => v.visit_var_decl_mut(decl),
+        StmtKind::EXPR(expr) => v.visit_expr_mut(expr),
+        StmtKind::ERROR => (),
+    }
+}
+
+pub fn walk_expr_mut<V: VisitorMut>(v: &mut V, expr: &mut Expr) {
+    match &mut expr.kind {
+        ExprKind::LITERAL(_) | ExprKind::IDEN(_) | ExprKind::SELF | ExprKind::SUPER => (),
+        ExprKind::CONTINUE { .. } => (),
+
+        ExprKind::PAREN(inner) | ExprKind::UNARY { expr: inner, .. } => v.visit_expr_mut(inner),
+        ExprKind::FIELD { expr: inner, .. } => v.visit_expr_mut(inner),
+
+        ExprKind::TUPLE(elems) | ExprKind::ARRAY(elems) => {
+            for elem in elems {
+                v.visit_expr_mut(elem);
+            }
+        }
+
+        ExprKind::ARRAY_REPEAT { value, len } => {
+            v.visit_expr_mut(value);
+            v.visit_expr_mut(len);
+        }
+
+        ExprKind::BLOCK(block) => v.visit_block_mut(block),
+
+        ExprKind::BINARY { lhs, rhs, .. } => {
+            v.visit_expr_mut(lhs);
+            v.visit_expr_mut(rhs);
+        }
+
+        ExprKind::TERNARY { cond, then, els } => {
+            v.visit_expr_mut(cond);
+            v.visit_expr_mut(then);
+            v.visit_expr_mut(els);
+        }
+
+        ExprKind::RANGE { start, end, .. } => {
+            v.visit_expr_mut(start);
+            v.visit_expr_mut(end);
+        }
+
+        ExprKind::ASSIGN { target, value, .. } => {
+            v.visit_expr_mut(target);
+            v.visit_expr_mut(value);
+        }
+
+        ExprKind::INDEX { expr: inner, index } => {
+            v.visit_expr_mut(inner);
+            v.visit_expr_mut(index);
+        }
+
+        ExprKind::CALL { callee, args } => {
+            v.visit_expr_mut(callee);
+
+            for arg in args {
+                v.visit_expr_mut(arg);
+            }
+        }
+
+        ExprKind::IF { cond, then, els } => {
+            v.visit_expr_mut(cond);
+            v.visit_block_mut(then);
+
+            if let Some(els) = els {
+                v.visit_expr_mut(els);
+            }
+        }
+
+        ExprKind::MATCH { scrutinee, arms } => {
+            v.visit_expr_mut(scrutinee);
+
+            for arm in arms {
+                v.visit_match_arm_mut(arm);
+            }
+        }
+
+        ExprKind::WHILE { cond, body, .. } => {
+            v.visit_expr_mut(cond);
+            v.visit_block_mut(body);
+        }
+
+        ExprKind::LOOP { body, .. } => v.visit_block_mut(body),
+
+        ExprKind::FOR {
+            pattern,
+            iter,
+            body,
+            ..
+        } => {
+            v.visit_pattern_mut(pattern);
+            v.visit_expr_mut(iter);
+            v.visit_block_mut(body);
+        }
+
+        ExprKind::BREAK { value, .. } | ExprKind::RETURN(value) => {
+            if let Some(value) = value {
+                v.visit_expr_mut(value);
+            }
+        }
+
+        ExprKind::NEW { args, .. } => {
+            for arg in args {
+                v.visit_expr_mut(arg);
+            }
+        }
+
+        ExprKind::STRUCT { fields, .. } => {
+            for field in fields {
+                v.visit_field_init_mut(field);
+            }
+        }
+    }
+}
+
+pub fn walk_match_arm_mut<V: VisitorMut>(v: &mut V, arm: &mut MatchArm) {
+    v.visit_pattern_mut(&mut arm.pattern);
+
+    if let Some(guard) = &mut arm.guard {
+        v.visit_expr_mut(guard);
+    }
+
+    v.visit_expr_mut(&mut arm.body);
+}
+
+pub fn walk_field_init_mut<V: VisitorMut>(v: &mut V, field: &mut FieldInit) {
+    v.visit_expr_mut(&mut field.value);
+}
+
+pub fn walk_pattern_mut<V: VisitorMut>(v: &mut V, pattern: &mut Pattern) {
+    match &mut pattern.kind {
+        PatternKind::WILDCARD
+        | PatternKind::BINDING(_)
+        | PatternKind::LITERAL(_)
+        | PatternKind::RANGE { .. } => (),
+
+        PatternKind::TUPLE(elems) | PatternKind::OR(elems) => {
+            for elem in elems {
+                v.visit_pattern_mut(elem);
+            }
+        }
+
+        PatternKind::VARIANT { elems, .. } => {
+            for elem in elems.iter_mut().flatten() {
+                v.visit_pattern_mut(elem);
+            }
+        }
+
+        PatternKind::STRUCT { fields, .. } => {
+            for field in fields {
+                v.visit_field_pattern_mut(field);
+            }
+        }
+    }
+}
+
+pub fn walk_field_pattern_mut<V: VisitorMut>(v: &mut V, field: &mut FieldPattern) {
+    v.visit_pattern_mut(&mut field.pattern);
+}
+
+pub fn walk_type_mut<V: VisitorMut>(v: &mut V, ty: &mut Type) {
+    match &mut ty.kind {
+        TypeKind::BOOL
+        | TypeKind::CHAR
+        | TypeKind::FLOAT
+        | TypeKind::INT
+        | TypeKind::STRING
+        | TypeKind::UINT => (),
+
+        TypeKind::NAMED { args: types, .. } | TypeKind::TUPLE(types) => {
+            for ty in types {
+                v.visit_type_mut(ty);
+            }
+        }
+
+        TypeKind::ARRAY(elem) | TypeKind::OPTIONAL(elem) => v.visit_type_mut(elem),
+
+        TypeKind::FIXED_ARRAY { elem, len } => {
+            v.visit_type_mut(elem);
+            v.visit_expr_mut(len);
+        }
+
+        TypeKind::FUNC { params, ret } => {
+            for param in params {
+                v.visit_type_mut(param);
+            }
+
+            if let Some(ret) = ret {
+                v.visit_type_mut(ret);
+            }
+        }
+    }
+}
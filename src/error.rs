@@ -1,17 +1,49 @@
+use crate::span::Span;
 use std::{error::Error, fmt, path::Path};
 
 #[derive(Debug)]
 pub struct FeoError {
     id: ErrorId,
     info: ErrorInfo,
+    notes: Vec<Note>,
+    fixes: Vec<Fix>,
 }
 
 impl fmt::Display for FeoError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {:?}", self.id, self.source().unwrap())
+        write!(f, "{}: {:?}", self.id, self.source().unwrap())?;
+
+        for note in &self.notes {
+            write!(f, "\n    note: {} ({})", note.message, note.span)?;
+        }
+
+        for fix in &self.fixes {
+            write!(f, "\n    help: {} ({})", fix.message, fix.span)?;
+        }
+
+        Ok(())
     }
 }
 
+/// Another location in the same file that explains an error, e.g. the earlier definition of
+/// a name that is defined twice
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Note {
+    pub span: Span,
+    pub message: String,
+}
+
+/// A change to the file that would resolve an error, e.g. replacing `let` with `var` for a
+/// binding that is assigned to
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    pub span: Span,
+    pub replacement: String,
+    pub message: String,
+}
+
 impl Error for FeoError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         Some(&self.info)
@@ -23,7 +55,7 @@ struct ErrorInfo {
     file: &'static Path,
     line: usize,
     col: usize,
-    message: &'static str,
+    message: String,
 }
 
 impl fmt::Debug for ErrorInfo {
@@ -42,32 +74,91 @@ impl fmt::Display for ErrorInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let position = format!("{}, {}:{}", self.file.display(), self.line, self.col);
 
-        write!(
-            f,
-            "`{}`. {} ({}).",
-            self.char.unwrap(),
-            self.message,
-            position
-        )
+        match self.char {
+            Some(c) => write!(f, "`{}`. {} ({}).", c, self.message, position),
+            None => write!(f, "{} ({}).", self.message, position),
+        }
     }
 }
 
 impl Error for ErrorInfo {}
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
 pub enum ErrorId {
+    ERR_AMBIGUOUS_METHOD,
+    ERR_ARG_COUNT,
+    ERR_ASSIGN_IMMUTABLE,
+    ERR_DIVISION_BY_ZERO,
+    ERR_DUPLICATE_CONFORMANCE,
+    ERR_DUPLICATE_NAME,
+    ERR_IMPORT_CYCLE,
+    ERR_INHERITANCE_CYCLE,
+    ERR_INVALID_ABSTRACT,
     ERR_INVALID_CHAR,
     ERR_INVALID_DATA,
+    ERR_INVALID_LIB,
+    ERR_INVALID_OPERANDS,
+    ERR_INVALID_OVERRIDE,
+    ERR_INVALID_SELF,
+    ERR_INVALID_SUPER,
+    ERR_INVALID_SUPERCLASS,
+    ERR_INVALID_TYPE,
+    ERR_MISSING_FIELD,
+    ERR_MISSING_METHOD,
+    ERR_NESTING_TOO_DEEP,
+    ERR_NOT_CONST,
+    ERR_OUTSIDE_LOOP,
+    ERR_OUT_OF_BOUNDS,
+    ERR_OVERFLOW,
+    ERR_TYPE_MISMATCH,
+    ERR_UNDEFINED_LABEL,
+    ERR_UNDEFINED_MEMBER,
+    ERR_UNDEFINED_NAME,
+    ERR_UNEXPECTED_EOF,
+    ERR_UNEXPECTED_TOKEN,
     ERR_UNKNOWN,
+    ERR_UNRESOLVED_IMPORT,
+    ERR_UNSATISFIED_BOUND,
 }
 
 impl fmt::Display for ErrorId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
+            ErrorId::ERR_AMBIGUOUS_METHOD => write!(f, "Ambiguous method"),
+            ErrorId::ERR_ARG_COUNT => write!(f, "Wrong number of arguments"),
+            ErrorId::ERR_ASSIGN_IMMUTABLE => write!(f, "Assignment to an immutable binding"),
+            ErrorId::ERR_DIVISION_BY_ZERO => write!(f, "Division by zero"),
+            ErrorId::ERR_DUPLICATE_CONFORMANCE => write!(f, "Duplicate conformance"),
+            ErrorId::ERR_DUPLICATE_NAME => write!(f, "Duplicate name"),
+            ErrorId::ERR_IMPORT_CYCLE => write!(f, "Import cycle"),
+            ErrorId::ERR_INHERITANCE_CYCLE => write!(f, "Inheritance cycle"),
+            ErrorId::ERR_INVALID_ABSTRACT => write!(f, "Invalid use of `abstract`"),
             ErrorId::ERR_INVALID_CHAR => write!(f, "Invalid character"),
             ErrorId::ERR_INVALID_DATA => write!(f, "Invalid data"),
+            ErrorId::ERR_INVALID_LIB => write!(f, "Invalid library declaration"),
+            ErrorId::ERR_INVALID_OPERANDS => write!(f, "Invalid operands"),
+            ErrorId::ERR_INVALID_OVERRIDE => write!(f, "Invalid override"),
+            ErrorId::ERR_INVALID_SELF => write!(f, "Invalid use of `self`"),
+            ErrorId::ERR_INVALID_SUPER => write!(f, "Invalid use of `super`"),
+            ErrorId::ERR_INVALID_SUPERCLASS => write!(f, "Invalid superclass"),
+            ErrorId::ERR_INVALID_TYPE => write!(f, "Invalid type"),
+            ErrorId::ERR_MISSING_FIELD => write!(f, "Missing field"),
+            ErrorId::ERR_MISSING_METHOD => write!(f, "Missing method"),
+            ErrorId::ERR_NESTING_TOO_DEEP => write!(f, "Nesting too deep"),
+            ErrorId::ERR_NOT_CONST => write!(f, "Not a constant"),
+            ErrorId::ERR_OUTSIDE_LOOP => write!(f, "`break` or `continue` outside a loop"),
+            ErrorId::ERR_OUT_OF_BOUNDS => write!(f, "Index out of bounds"),
+            ErrorId::ERR_OVERFLOW => write!(f, "Arithmetic overflow"),
+            ErrorId::ERR_TYPE_MISMATCH => write!(f, "Mismatched types"),
+            ErrorId::ERR_UNDEFINED_LABEL => write!(f, "Undefined label"),
+            ErrorId::ERR_UNDEFINED_MEMBER => write!(f, "Undefined member"),
+            ErrorId::ERR_UNDEFINED_NAME => write!(f, "Undefined name"),
+            ErrorId::ERR_UNEXPECTED_EOF => write!(f, "Unexpected end of file"),
+            ErrorId::ERR_UNEXPECTED_TOKEN => write!(f, "Unexpected token"),
             ErrorId::ERR_UNKNOWN => write!(f, "Unknown error"),
+            ErrorId::ERR_UNRESOLVED_IMPORT => write!(f, "Unresolved import"),
+            ErrorId::ERR_UNSATISFIED_BOUND => write!(f, "Unsatisfied bound"),
         }
     }
 }
@@ -81,16 +172,96 @@ impl FeoError {
         file: &'static Path,
         line: usize,
         col: usize,
-        message: &'static str,
+        message: impl Into<String>,
     ) -> FeoError {
         let info = ErrorInfo {
             char,
             file,
             line,
             col,
-            message,
+            message: message.into(),
         };
 
-        FeoError { id, info }
+        FeoError {
+            id,
+            info,
+            notes: Vec::new(),
+            fixes: Vec::new(),
+        }
+    }
+
+    /// Returns an error at the start of a span
+    ///
+    pub fn at(id: ErrorId, file: &'static Path, span: Span, message: impl Into<String>) -> Self {
+        Self::new(id, None, file, span.line, span.col, message)
+    }
+
+    /// Adds a note pointing at another part of the file
+    ///
+    /// # Arguments
+    ///
+    /// * `span` - the location the note refers to
+    /// * `message` - what the location has to do with the error
+    ///
+    #[must_use]
+    pub fn with_note(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.notes.push(Note {
+            span,
+            message: message.into(),
+        });
+
+        self
+    }
+
+    /// Adds a suggested change that would resolve the error
+    ///
+    /// # Arguments
+    ///
+    /// * `span` - the text to replace
+    /// * `replacement` - what to replace it with
+    /// * `message` - a description of the change
+    ///
+    #[must_use]
+    pub fn with_fix(
+        mut self,
+        span: Span,
+        replacement: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        self.fixes.push(Fix {
+            span,
+            replacement: replacement.into(),
+            message: message.into(),
+        });
+
+        self
+    }
+
+    pub fn id(&self) -> ErrorId {
+        self.id
+    }
+
+    pub fn file(&self) -> &'static Path {
+        self.info.file
+    }
+
+    pub fn line(&self) -> usize {
+        self.info.line
+    }
+
+    pub fn col(&self) -> usize {
+        self.info.col
+    }
+
+    pub fn message(&self) -> &str {
+        &self.info.message
+    }
+
+    pub fn notes(&self) -> &[Note] {
+        &self.notes
+    }
+
+    pub fn fixes(&self) -> &[Fix] {
+        &self.fixes
     }
 }
@@ -1,12 +1,22 @@
 use crate::error::{ErrorId, FeoError};
 use std::path::Path;
+use symbol::Symbol;
 use token::{TokType, Token};
 
-mod token;
+pub mod incremental;
+pub mod symbol;
+pub mod token;
 
-/// States whether or not the lexer is in a comment block, determining certain behaviour
+/// The lexer's state at the start of a line, which is all that carries over from one line
+/// to the next. Text literals cannot span lines, so only multi-line comments need tracking
 ///
-static mut COMMENT_BLOCK: bool = false;
+/// Lexing can be resumed at any line start given that line's `LexState`
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LexState {
+    /// whether or not the lexer is inside a multi-line comment
+    comment_block: bool,
+}
 
 /// Returns a list of `Token` collected into a list of the input file's individual lines
 ///
@@ -15,102 +25,226 @@ static mut COMMENT_BLOCK: bool = false;
 /// * `file` - the contents of the file
 /// * `path` - a reference to the location of the file in memory
 ///
-pub fn lex(file: &String, path: &'static Path) -> Option<Vec<Vec<Token>>> {
+/// # Errors
+///
+/// Returns a `FeoError` pointing at the first malformed token (e.g. an unterminated text
+/// literal or an out-of-range number). The lexer never panics on bad input
+///
+pub fn lex(file: &str, path: &'static Path) -> Result<Vec<Vec<Token>>, FeoError> {
     let mut tokens: Vec<Vec<Token>> = Vec::new();
-    let mut line_num: usize = 1;
+    let mut state = LexState::default();
 
     // iterate over the lines of a source `String` as individual string slices
-    // check that each string slice is not empty
-    for l in file.lines() {
-        let chars: Vec<char> = l.chars().collect();
-
-        // check for valid input parameters
-        let line: Vec<Token> = match tokenize_line(&chars, line_num, l.len(), path) {
-            Ok(l) => l,
-            Err(_) => {
-                let err = FeoError::new(
-                    ErrorId::ERR_INVALID_DATA,
-                    None,
-                    path,
-                    line_num,
-                    1,
-                    "Unable to tokenize line",
-                );
-
-                panic!("{}", err);
-            }
-        };
+    for (line_num, l) in (1..).zip(file.lines()) {
+        let line: Vec<Token> = tokenize_line(l, line_num, &mut state, path)?;
 
         tokens.push(line);
-        line_num += 1;
     }
 
-    Some(tokens)
+    Ok(tokens)
+}
+
+/// Returns feolang source code that lexes back into the given tokens
+///
+/// Tokens are separated by a single space and each line of tokens is printed on its own line,
+/// so that line numbers are preserved (comments and original spacing are not)
+///
+/// # Arguments
+///
+/// * `tokens` - a list of `Token` collected into a list of lines, as returned by `lex`
+///
+pub fn print(tokens: &[Vec<Token>]) -> String {
+    let mut buf = String::new();
+
+    for line in tokens {
+        let line: Vec<String> = line.iter().map(|t| t.tok_type().to_string()).collect();
+
+        // every line is terminated, so that trailing empty lines are kept
+        buf.push_str(&line.join(" "));
+        buf.push('\n');
+    }
+
+    buf
+}
+
+/// Bit flags classifying each byte value, so that the scanning loops reduce to a table
+/// lookup per byte
+///
+const WHITESPACE: u8 = 1 << 0;
+const ALPHA: u8 = 1 << 1;
+const DIGIT: u8 = 1 << 2;
+const HEX_DIGIT: u8 = 1 << 3;
+const PUNCTUATION: u8 = 1 << 4;
+
+/// Lookup table mapping each byte to its character class flags. Bytes outside the ASCII range
+/// have no flags set
+///
+static CHAR_CLASS: [u8; 256] = char_class_table();
+
+const fn char_class_table() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut b: usize = 0;
+
+    while b < 128 {
+        let c = b as u8;
+        let mut class: u8 = 0;
+
+        if c.is_ascii_whitespace() || c.is_ascii_control() {
+            class |= WHITESPACE;
+        }
+
+        if c.is_ascii_alphabetic() || c == b'_' {
+            class |= ALPHA;
+        }
+
+        if c.is_ascii_digit() {
+            class |= DIGIT;
+        }
+
+        if c.is_ascii_hexdigit() {
+            class |= HEX_DIGIT;
+        }
+
+        if c.is_ascii_punctuation() {
+            class |= PUNCTUATION;
+        }
+
+        table[b] = class;
+        b += 1;
+    }
+
+    table
+}
+
+/// Returns `true` if the byte belongs to any of the given character classes
+///
+fn is_class(b: u8, class: u8) -> bool {
+    CHAR_CLASS[b as usize] & class != 0
+}
+
+/// Returns `true` if the optional byte belongs to any of the given character classes
+///
+fn is_class_at(bytes: &[u8], i: usize, class: u8) -> bool {
+    bytes.get(i).is_some_and(|b| is_class(*b, class))
+}
+
+/// Returns the index of the first byte at or after `i` that is not in the given classes
+///
+fn skip_class(bytes: &[u8], mut i: usize, class: u8) -> usize {
+    while i < bytes.len() && is_class(bytes[i], class) {
+        i += 1;
+    }
+
+    i
+}
+
+/// Returns `true` if the `'` at `i` starts a loop label (e.g. `'outer`) rather than a
+/// character literal (e.g. `'o'`), i.e. it is followed by a name that is not closed by a `'`
+///
+fn is_label(bytes: &[u8], i: usize) -> bool {
+    is_class_at(bytes, i + 1, ALPHA)
+        && bytes.get(skip_class(bytes, i + 1, ALPHA | DIGIT)) != Some(&b'\'')
 }
 
 /// Returns a list of the input line's tokens
 ///
+/// The line is scanned byte by byte. Every token starts with an ASCII byte, so the only
+/// non-ASCII input that needs decoding is inside text literals and invalid characters. Token
+/// columns count `char`, which for the common all-ASCII line is simply the byte index + 1
+///
 /// # Arguments
 ///
-/// * `chars` - a reference to a line in the input `&str`, collected into a list of `char`
+/// * `line` - a reference to a line in the input `&str`
 /// * `line_num` - the line's number (index + 1)
-/// * `line_len` - the length of the line
+/// * `state` - the lexer's state at the start of the line (updated to reflect the state at
+///   the start of the next line)
 /// * `path` - the location of the source file in memory
 ///
 fn tokenize_line(
-    chars: &Vec<char>,
+    line: &str,
     line_num: usize,
-    line_len: usize,
+    state: &mut LexState,
     path: &'static Path,
 ) -> Result<Vec<Token>, FeoError> {
-    let mut tokens: Vec<Token> = Vec::new();
+    let bytes: &[u8] = line.as_bytes();
+    let is_ascii: bool = line.is_ascii();
+    let col = |i: usize| -> usize {
+        if is_ascii {
+            i + 1
+        } else {
+            line[..i].chars().count() + 1
+        }
+    };
+
+    // a token's position is stored as a `u32`, which a file of more than 4 GiB outgrows
+    let token = |tok_type: TokType, start: usize, len: usize| -> Result<Token, FeoError> {
+        Token::new(tok_type, line_num, start, len).ok_or_else(|| {
+            FeoError::new(
+                ErrorId::ERR_INVALID_DATA,
+                None,
+                path,
+                line_num,
+                start,
+                "The file is too large to lex",
+            )
+        })
+    };
+
+    let line_len: usize = bytes.len();
+    let mut tokens: Vec<Token> = Vec::with_capacity(line_len / 4);
     let mut i: usize = 0;
-    let mut tok_i: usize = 0;
     let mut is_negative_number: bool = false;
 
     while i < line_len {
-        match chars[i] {
-            _ if chars[i].is_ascii_whitespace() || chars[i].is_ascii_control() => {
-                i += 1;
-                continue;
-            }
+        let next: Option<u8> = bytes.get(i + 1).copied();
 
-            _ if chars[i] == '*' && chars[i + 1] == '/' => {
-                if unsafe { COMMENT_BLOCK == false } {
+        match bytes[i] {
+            b'*' if next == Some(b'/') => {
+                if !state.comment_block {
                     let err: FeoError = FeoError::new(
                         ErrorId::ERR_INVALID_CHAR,
                         Some('*'),
                         path,
                         line_num,
-                        i + 1,
+                        col(i),
                         "Encountered multi-line comment terminator in an uncommented block",
                     );
 
-                    panic!("{}", err);
+                    return Err(err);
                 }
 
                 i += 2;
-                make_comment_false();
+                state.comment_block = false;
                 continue;
             }
 
-            _ if chars[i] == '/' && chars[i + 1] == '*' => {
+            b'/' if next == Some(b'*') => {
                 i += 2;
-                make_comment_true();
+                state.comment_block = true;
                 continue;
             }
 
-            _ if unsafe { COMMENT_BLOCK == true } => {
-                i += 1;
+            // skip straight to the next `*`, which may start the comment terminator
+            _ if state.comment_block => {
+                i = match bytes[i + 1..].iter().position(|b| *b == b'*') {
+                    Some(n) => i + 1 + n,
+                    None => line_len,
+                };
                 continue;
             }
 
-            _ if chars[i] == '/' && chars[i + 1] == '/' => break,
+            b if is_class(b, WHITESPACE) => {
+                i = skip_class(bytes, i + 1, WHITESPACE);
+                continue;
+            }
+
+            b'/' if next == Some(b'/') => break,
 
-            _ if chars[i].is_ascii_alphabetic() || chars[i] == '_' => {
+            b if is_class(b, ALPHA) => {
                 let start_index: usize = i;
-                let alpha = get_alpha(chars, line_num, line_len, i, path).unwrap();
-                let tok_type: TokType = match alpha.as_str() {
+                let end: usize = skip_class(bytes, i + 1, ALPHA | DIGIT);
+                let alpha: &str = &line[start_index..end];
+                let tok_type: TokType = match alpha {
                     "abstract" => TokType::KW_ABSTRACT,
                     "alias" => TokType::KW_ALIAS,
                     "as" => TokType::KW_AS,
@@ -130,6 +264,7 @@ fn tokenize_line(
                     "func" => TokType::KW_FUNC,
                     "if" => TokType::KW_IF,
                     "import" => TokType::KW_IMPORT,
+                    "in" => TokType::KW_IN,
                     "int" => TokType::KW_INT,
                     "is" => TokType::KW_IS,
                     "let" => TokType::KW_LET,
@@ -152,166 +287,158 @@ fn tokenize_line(
                     "var" => TokType::KW_VAR,
                     "virtual" => TokType::KW_VIRTUAL,
                     "while" => TokType::KW_WHILE,
-                    "_" => {
-                        let err = FeoError::new(
-                            ErrorId::ERR_INVALID_CHAR,
-                            Some('_'),
-                            path,
-                            line_num,
-                            i + 1,
-                            "Invalid keyword or identifier",
-                        );
+                    "_" => TokType::PUNC_UNDERSCORE,
 
-                        panic!("{}", err);
-                    }
-
-                    _ => TokType::IDEN(alpha.clone()),
+                    _ => TokType::IDEN(Symbol::intern(alpha)),
                 };
 
-                i += alpha.len();
+                i = end;
 
-                let tok = Token::new(tok_type, line_num, start_index + 1);
-                tokens.push(tok);
-                tok_i += 1;
+                let len: usize = col(i) - col(start_index);
+                tokens.push(token(tok_type, col(start_index), len)?);
                 continue;
             }
 
-            _ if i < line_len - 1 && chars[i] == '-' && chars[i + 1].is_ascii_digit() => {
+            // a `-` directly followed by a digit is the sign of a number literal, unless it
+            // follows an operand (e.g. `x -1`), in which case it is the subtraction operator
+            b'-' if next.is_some_and(|b| is_class(b, DIGIT)) && !ends_operand(&tokens) => {
                 is_negative_number = true;
                 i += 1;
                 continue;
             }
 
-            _ if i < line_len - 1 && chars[i] == '0' && chars[i + 1] == 'x' => {
-                let start_index: usize;
-
-                if is_negative_number {
-                    start_index = &i - 1;
-                } else {
-                    start_index = i;
-                }
+            b'0' if next == Some(b'x') => {
+                let start_index: usize = if is_negative_number { i - 1 } else { i };
+                let end: usize = get_hex(bytes, line_num, i + 2, path, &col)?;
 
-                let hex = get_hex(chars, line_num, line_len, i, is_negative_number, path).unwrap();
-                let tok_type: TokType = match hex {
-                    _ if hex.contains('.') => {
+                let tok_type: TokType = match parse_int(&bytes[i + 2..end], 16, is_negative_number)
+                {
+                    Some(n) => TokType::LIT_INT(n),
+                    None => {
                         let err = FeoError::new(
-                            ErrorId::ERR_INVALID_CHAR,
-                            Some('.'),
+                            ErrorId::ERR_INVALID_DATA,
+                            None,
                             path,
                             line_num,
-                            i + 1,
-                            "Hexadecimal float types are unsupported",
+                            col(start_index),
+                            "Invalid or out-of-range hexadecimal integer literal",
                         );
 
-                        panic!("{}", err);
+                        return Err(err);
                     }
-
-                    _ => TokType::LIT_INT(i32::from_str_radix(hex.as_str(), 16).unwrap()),
                 };
 
-                if is_negative_number {
-                    i += &hex.len() + 1;
-                } else {
-                    i += &hex.len() + 2;
-                }
+                i = end;
+                is_negative_number = false;
 
-                let tok = Token::new(tok_type, line_num, start_index + 1);
-                tokens.push(tok);
-                tok_i += 1;
+                let len: usize = col(i) - col(start_index);
+                tokens.push(token(tok_type, col(start_index), len)?);
                 continue;
             }
 
-            _ if chars[i].is_ascii_digit() => {
-                let start_index: usize;
+            b if is_class(b, DIGIT) => {
+                let start_index: usize = if is_negative_number { i - 1 } else { i };
+                let (end, is_float) = get_num(bytes, line_num, i, path, &col)?;
+
+                let tok_type: Option<TokType> = if is_float {
+                    // the literal includes its sign, if any
+                    let num: &str = &line[start_index..end];
 
-                if is_negative_number {
-                    start_index = &i - 1;
+                    if num.contains('_') {
+                        num.replace('_', "").parse::<f64>().ok()
+                    } else {
+                        num.parse::<f64>().ok()
+                    }
+                    // a float too large for an `f64`, such as `1e999`, parses as infinity
+                    .filter(|x| x.is_finite())
+                    .map(TokType::LIT_FLOAT)
                 } else {
-                    start_index = i;
-                }
+                    parse_int(&bytes[i..end], 10, is_negative_number).map(TokType::LIT_INT)
+                };
 
-                let num = get_num(chars, line_num, line_len, i, is_negative_number, path).unwrap();
-                let tok_type: TokType = match num {
-                    _ if num.contains('.') => TokType::LIT_FLOAT(num.parse::<f64>().unwrap()),
-                    _ => TokType::LIT_INT(num.parse::<i32>().unwrap()),
+                let tok_type: TokType = match tok_type {
+                    Some(t) => t,
+                    None => {
+                        let err = FeoError::new(
+                            ErrorId::ERR_INVALID_DATA,
+                            None,
+                            path,
+                            line_num,
+                            col(start_index),
+                            "Number literal out of range",
+                        );
+
+                        return Err(err);
+                    }
                 };
 
-                if is_negative_number {
-                    i += &num.len() - 1;
-                } else {
-                    i += &num.len();
-                }
+                i = end;
+                is_negative_number = false;
 
-                let tok = Token::new(tok_type, line_num, start_index + 1);
-                tokens.push(tok);
-                tok_i += 1;
+                let len: usize = col(i) - col(start_index);
+                tokens.push(token(tok_type, col(start_index), len)?);
                 continue;
             }
 
-            _ if chars[i] == '\'' || chars[i] == '"' => {
+            b'\'' if is_label(bytes, i) => {
                 let start_index: usize = i;
+                let end: usize = skip_class(bytes, i + 1, ALPHA | DIGIT);
+                let tok_type = TokType::LABEL(Symbol::intern(&line[start_index..end]));
 
-                if i < line_len - 1 {
-                    i += 1;
-                } else {
-                    break;
-                }
+                i = end;
 
-                let lit = get_text_literal(chars, line_num, line_len, i, path).unwrap();
-                let tok_type: TokType = match chars[start_index] {
-                    '\'' => TokType::LIT_CHAR(lit.parse::<char>().unwrap()),
-                    '"' => TokType::LIT_STRING(lit.to_owned()),
-                    _ => TokType::INVALID_CHAR(chars[start_index]),
+                let len: usize = col(i) - col(start_index);
+                tokens.push(token(tok_type, col(start_index), len)?);
+                continue;
+            }
+
+            quote @ (b'\'' | b'"') => {
+                let start_index: usize = i;
+
+                let (lit, end) = get_text_literal(line, line_num, i + 1, path, &col)?;
+                let tok_type: TokType = match quote {
+                    b'\'' => TokType::LIT_CHAR(lit.chars().next().unwrap_or_default()),
+                    _ => TokType::LIT_STRING(lit),
                 };
 
-                i += &lit.len() + 1; // +1 to skip closing quote
+                i = end;
 
-                let tok = Token::new(tok_type, line_num, start_index + 1);
-                tokens.push(tok);
-                tok_i += 1;
+                let len: usize = col(i) - col(start_index);
+                tokens.push(token(tok_type, col(start_index), len)?);
                 continue;
             }
 
-            '=' => {
-                if i < line_len - 1
-                    && (tokens[tok_i - 1].tok_type().is_iden()
-                        || tokens[tok_i - 1].tok_type().is_literal())
-                {
-                    {
-                        let start_index: usize = i;
-
-                        let tok_type: TokType = match chars[i] {
-                            _ if chars[i + 1] == '=' => {
-                                i += 1;
-                                TokType::OP_EQ
-                            }
-                            _ if chars[i + 1] == '>' => {
-                                i += 1;
-                                TokType::OP_FAT_ARW
-                            }
-
-                            _ => TokType::OP_ASSIGN,
-                        };
-
-                        i += 1;
-
-                        let tok = Token::new(tok_type, line_num, start_index + 1);
-                        tokens.push(tok);
-                        tok_i += 1;
-                        continue;
-                    }
-                }
+            b'\\' => {
+                let err = FeoError::new(
+                    ErrorId::ERR_INVALID_CHAR,
+                    Some('\\'),
+                    path,
+                    line_num,
+                    col(i),
+                    "Escape character encountered out of context",
+                );
+
+                return Err(err);
             }
 
-            _ if chars[i].is_ascii_punctuation()
-                && (tokens[tok_i - 1].tok_type().is_iden()
-                    || tokens[tok_i - 1].tok_type().is_literal()) =>
-            {
+            b if is_class(b, PUNCTUATION) => {
                 let start_index: usize = i;
 
-                let tok_type: TokType = match chars[i] {
-                    '+' if i < line_len - 1 => {
-                        if chars[i + 1] == '=' {
+                let tok_type: TokType = match b {
+                    b'=' => {
+                        if next == Some(b'=') {
+                            i += 1;
+                            TokType::OP_EQ
+                        } else if next == Some(b'>') {
+                            i += 1;
+                            TokType::OP_FAT_ARW
+                        } else {
+                            TokType::OP_ASSIGN
+                        }
+                    }
+
+                    b'+' => {
+                        if next == Some(b'=') {
                             i += 1;
                             TokType::OP_PLUS_EQ
                         } else {
@@ -319,11 +446,11 @@ fn tokenize_line(
                         }
                     }
 
-                    '-' if i < line_len - 1 => {
-                        if chars[i + 1] == '=' {
+                    b'-' => {
+                        if next == Some(b'=') {
                             i += 1;
                             TokType::OP_MINUS_EQ
-                        } else if chars[i + 1] == '>' {
+                        } else if next == Some(b'>') {
                             i += 1;
                             TokType::OP_THIN_ARW
                         } else {
@@ -331,8 +458,8 @@ fn tokenize_line(
                         }
                     }
 
-                    '*' if i < line_len - 1 => {
-                        if chars[i + 1] == '=' {
+                    b'*' => {
+                        if next == Some(b'=') {
                             i += 1;
                             TokType::OP_MULT_EQ
                         } else {
@@ -340,8 +467,8 @@ fn tokenize_line(
                         }
                     }
 
-                    '/' if i < line_len - 1 => {
-                        if chars[i + 1] == '=' {
+                    b'/' => {
+                        if next == Some(b'=') {
                             i += 1;
                             TokType::OP_DIV_EQ
                         } else {
@@ -349,8 +476,8 @@ fn tokenize_line(
                         }
                     }
 
-                    '%' if i < line_len - 1 => {
-                        if chars[i + 1] == '=' {
+                    b'%' => {
+                        if next == Some(b'=') {
                             i += 1;
                             TokType::OP_MOD_EQ
                         } else {
@@ -358,8 +485,8 @@ fn tokenize_line(
                         }
                     }
 
-                    '<' if i < line_len - 1 => {
-                        if chars[i + 1] == '=' {
+                    b'<' => {
+                        if next == Some(b'=') {
                             i += 1;
                             TokType::OP_LESS_EQ
                         } else {
@@ -367,8 +494,8 @@ fn tokenize_line(
                         }
                     }
 
-                    '>' if i < line_len - 1 => {
-                        if chars[i + 1] == '=' {
+                    b'>' => {
+                        if next == Some(b'=') {
                             i += 1;
                             TokType::OP_GRTR_EQ
                         } else {
@@ -376,8 +503,8 @@ fn tokenize_line(
                         }
                     }
 
-                    '!' if i < line_len - 1 => {
-                        if chars[i + 1] == '=' {
+                    b'!' => {
+                        if next == Some(b'=') {
                             i += 1;
                             TokType::OP_NOT_EQ
                         } else {
@@ -385,8 +512,8 @@ fn tokenize_line(
                         }
                     }
 
-                    '&' if i < line_len - 1 => {
-                        if chars[i + 1] == '&' {
+                    b'&' => {
+                        if next == Some(b'&') {
                             i += 1;
                             TokType::OP_AND
                         } else {
@@ -394,8 +521,8 @@ fn tokenize_line(
                         }
                     }
 
-                    '|' if i < line_len - 1 => {
-                        if chars[i + 1] == '|' {
+                    b'|' => {
+                        if next == Some(b'|') {
                             i += 1;
                             TokType::OP_OR
                         } else {
@@ -403,52 +530,52 @@ fn tokenize_line(
                         }
                     }
 
-                    '?' => TokType::OP_TERNARY,
-
-                    ';' => TokType::PUNC_SEMICOLON,
-
-                    ':' => TokType::PUNC_COLON,
+                    b'?' => TokType::OP_TERNARY,
 
-                    ',' => TokType::PUNC_COMMA,
+                    b';' => TokType::PUNC_SEMICOLON,
 
-                    '.' => TokType::PUNC_DOT,
+                    b':' => TokType::PUNC_COLON,
 
-                    '(' => TokType::PUNC_OPEN_PAREN,
+                    b',' => TokType::PUNC_COMMA,
 
-                    ')' => TokType::PUNC_CLS_PAREN,
+                    b'.' => {
+                        if next == Some(b'.') && bytes.get(i + 2) == Some(&b'=') {
+                            i += 2;
+                            TokType::OP_RANGE_INCL
+                        } else if next == Some(b'.') {
+                            i += 1;
+                            TokType::OP_RANGE
+                        } else {
+                            TokType::PUNC_DOT
+                        }
+                    }
 
-                    '[' => TokType::PUNC_OPEN_SQ_BKT,
+                    b'(' => TokType::PUNC_OPEN_PAREN,
 
-                    ']' => TokType::PUNC_CLS_SQ_BKT,
+                    b')' => TokType::PUNC_CLS_PAREN,
 
-                    '{' => TokType::PUNC_OPEN_CRL_BRC,
+                    b'[' => TokType::PUNC_OPEN_SQ_BKT,
 
-                    '}' => TokType::PUNC_CLS_CRL_BRC,
+                    b']' => TokType::PUNC_CLS_SQ_BKT,
 
-                    '\\' => {
-                        let err = FeoError::new(
-                            ErrorId::ERR_INVALID_CHAR,
-                            Some('\\'),
-                            path,
-                            line_num,
-                            i + 1,
-                            "Escape character encountered out of context",
-                        );
+                    b'{' => TokType::PUNC_OPEN_CRL_BRC,
 
-                        panic!("{}", err);
-                    }
+                    b'}' => TokType::PUNC_CLS_CRL_BRC,
 
-                    _ => TokType::INVALID_CHAR(chars[i]),
+                    _ => TokType::INVALID_CHAR(b as char),
                 };
 
-                let tok = Token::new(tok_type, line_num, start_index + 1);
-                tokens.push(tok);
-                tok_i += 1;
+                let len: usize = i + 1 - start_index;
+                tokens.push(token(tok_type, col(start_index), len)?);
             }
 
+            // the only bytes left are the start of non-ASCII characters
             _ => {
-                tokens.push(Token::new(TokType::INVALID_CHAR(chars[i]), line_num, i + 1));
-                tok_i += 1;
+                let c: char = line[i..].chars().next().unwrap_or_default();
+
+                tokens.push(token(TokType::INVALID_CHAR(c), col(i), 1)?);
+                i += c.len_utf8();
+                continue;
             }
         }
 
@@ -458,274 +585,251 @@ fn tokenize_line(
     Ok(tokens)
 }
 
-/// Returns a `String` containing either a keyword or identifier
+/// Returns `true` if the last token in the list can end an operand (an identifier, a literal
+/// or a closing bracket), in which case a following `-` is a binary operator
 ///
 /// # Arguments
 ///
-/// * `chars` - a reference to a list of `char` (a line in the file)
-/// * `line_len` - the length of the line
-/// * `i` - the index of a given `char` in the line
-/// * `path` - the location of the source file in memory
+/// * `tokens` - a reference to the tokens of the current line so far
 ///
-fn get_alpha(
-    chars: &Vec<char>,
-    line_num: usize,
-    line_len: usize,
-    mut i: usize,
-    path: &'static Path,
-) -> Option<String> {
-    let mut buf = String::new();
+fn ends_operand(tokens: &[Token]) -> bool {
+    match tokens.last() {
+        Some(t) => {
+            t.tok_type().is_iden()
+                || t.tok_type().is_literal()
+                || *t.tok_type() == TokType::PUNC_CLS_PAREN
+                || *t.tok_type() == TokType::PUNC_CLS_SQ_BKT
+        }
+        None => false,
+    }
+}
 
-    buf.push(chars[i]);
-    i += 1;
+/// Returns the value of an integer literal's digits, skipping digit separators (`_`), or
+/// `None` if there are no digits or the value does not fit in an `i32`
+///
+/// # Arguments
+///
+/// * `digits` - the literal's digits, without any sign or radix prefix
+/// * `radix` - the literal's base (10 or 16)
+/// * `is_negative` - a `bool` to indicate the number's sign
+///
+fn parse_int(digits: &[u8], radix: u32, is_negative: bool) -> Option<i32> {
+    let mut value: i64 = 0;
+    let mut has_digits: bool = false;
 
-    while i < line_len {
-        if !(chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
-            break;
+    for b in digits {
+        if *b == b'_' {
+            continue;
         }
 
-        buf.push(chars[i]);
-        i += 1;
+        let d = (*b as char).to_digit(radix)?;
+        value = value.checked_mul(radix as i64)?.checked_add(d as i64)?;
+        has_digits = true;
     }
 
-    if buf.is_empty() {
-        let err = FeoError::new(
-            ErrorId::ERR_INVALID_CHAR,
-            None,
-            path,
-            line_num,
-            i + 1,
-            "Missing alphanumeric or `_` value",
-        );
-
-        panic!("{}", err);
-    } else {
-        Some(buf)
+    if !has_digits {
+        return None;
     }
+
+    i32::try_from(if is_negative { -value } else { value }).ok()
 }
 
-/// Returns a `String` containing a number literal
+/// Returns the index of the first byte after a number literal, and whether or not the literal
+/// is a float
+///
+/// Digit separators (`_`) are skipped, and a `.` is only treated as a decimal point if it is
+/// followed by a digit (so that `1.foo()` is a method call on an integer)
 ///
 /// # Arguments
 ///
-/// * `chars` - a reference to a list of `char` (a line in the file)
+/// * `bytes` - a reference to a line in the file
 /// * `line_num` - the line's number (index + 1)
-/// * `line_len` - the length of the line
-/// * `i` - the index of a given `char` in the line
-/// * `is_negative` - a `bool` to indicate the number's sign
+/// * `i` - the index of the literal's first digit
 /// * `path` - the location of the source file in memory
+/// * `col` - maps a byte index in the line to its column
 ///
 fn get_num(
-    chars: &Vec<char>,
+    bytes: &[u8],
     line_num: usize,
-    line_len: usize,
     mut i: usize,
-    is_negative: bool,
     path: &'static Path,
-) -> Option<String> {
-    let mut buf = String::new();
+    col: &dyn Fn(usize) -> usize,
+) -> Result<(usize, bool), FeoError> {
+    let mut is_float: bool = false;
 
-    if is_negative {
-        buf.push('-')
-    }
+    while i < bytes.len() {
+        let is_point: bool = bytes[i] == b'.' && is_class_at(bytes, i + 1, DIGIT);
 
-    while i < line_len && (chars[i].is_ascii_digit() || chars[i] == '_' || chars[i] == '.') {
-        if chars[i] == '_' {
-            if i < line_len - 1 {
-                i += 1;
-            } else {
-                break;
-            }
+        if !(is_class(bytes[i], DIGIT) || bytes[i] == b'_' || is_point) {
+            break;
         }
 
-        if chars[i] == '.' && buf.contains('.') {
+        if is_point && is_float {
             let err = FeoError::new(
                 ErrorId::ERR_INVALID_CHAR,
                 Some('.'),
                 path,
                 line_num,
-                i + 1,
+                col(i),
                 "Float types can only have one point",
             );
 
-            panic!("{}", err);
+            return Err(err);
         }
 
-        buf.push(chars[i]);
+        is_float |= is_point;
         i += 1;
     }
 
-    if buf.is_empty() {
-        let err = FeoError::new(
-            ErrorId::ERR_INVALID_CHAR,
-            None,
-            path,
-            line_num,
-            i + 1,
-            "Missing numeric type",
-        );
-
-        panic!("{}", err);
-    } else {
-        Some(buf)
-    }
+    Ok((i, is_float))
 }
 
-/// Returns a `String` containing a hexadecimal number literal
+/// Returns the index of the first byte after a hexadecimal number literal's digits
 ///
 /// # Arguments
 ///
-/// * `chars` - a reference to a list of `char` (a line in the file)
+/// * `bytes` - a reference to a line in the file
 /// * `line_num` - the line's number (index + 1)
-/// * `line_len` - the length of the line
-/// * `i` - the index of a given `char` in the line
-/// * `is_negative` - a `bool` to indicate the number's sign
+/// * `i` - the index of the first byte after the `0x` prefix
 /// * `path` - the location of the source file in memory
+/// * `col` - maps a byte index in the line to its column
 ///
 fn get_hex(
-    chars: &Vec<char>,
+    bytes: &[u8],
     line_num: usize,
-    line_len: usize,
     mut i: usize,
-    is_negative: bool,
     path: &'static Path,
-) -> Option<String> {
-    let mut buf = String::new();
+    col: &dyn Fn(usize) -> usize,
+) -> Result<usize, FeoError> {
+    i = skip_class(bytes, i, HEX_DIGIT);
 
-    if is_negative {
-        buf.push('-')
+    while i < bytes.len() && bytes[i] == b'_' {
+        i = skip_class(bytes, i + 1, HEX_DIGIT);
     }
 
-    i += 2;
-
-    while i < line_len && (chars[i].is_ascii_hexdigit() || chars[i] == '_' || chars[i] == '.') {
-        if chars[i] == '_' {
-            if i < line_len - 1 {
-                i += 1;
-            } else {
-                break;
-            }
-        }
-
-        if chars[i] == '.' && buf.contains('.') {
-            let err = FeoError::new(
-                ErrorId::ERR_INVALID_CHAR,
-                Some('.'),
-                path,
-                line_num,
-                i + 1,
-                "Float types can only have one point",
-            );
-
-            panic!("{}", err);
-        }
-
-        buf.push(chars[i]);
-        i += 1;
-    }
-
-    if buf.is_empty() {
+    if i < bytes.len() && bytes[i] == b'.' && is_class_at(bytes, i + 1, HEX_DIGIT) {
         let err = FeoError::new(
             ErrorId::ERR_INVALID_CHAR,
-            None,
+            Some('.'),
             path,
             line_num,
-            i + 1,
-            "Missing numeric type",
+            col(i),
+            "Hexadecimal float types are unsupported",
         );
 
-        panic!("{}", err);
-    } else {
-        Some(buf)
+        return Err(err);
     }
+
+    Ok(i)
 }
 
-/// Returns a `String` containing either a `char` or `String` literal
+/// Returns a `String` containing the (unescaped) contents of either a `char` or `String`
+/// literal, along with the index of the first byte after the closing quote
+///
+/// The escape sequences `\n`, `\r`, `\t` and `\0` are decoded; any other escaped character
+/// (e.g. `\\`, `\"` or `\'`) stands for itself
 ///
 /// # Arguments
 ///
-/// * `chars` - a reference to a list of `char` (a line in the file)
+/// * `line` - a reference to a line in the file
 /// * `line_num` - the line's number (index + 1)
-/// * `line_len` - the length of the line
-/// * `i` - the index of a given `char` in the line
+/// * `i` - the index of the first byte after the opening quote
 /// * `path` - the location of the source file in memory
+/// * `col` - maps a byte index in the line to its column
 ///
 fn get_text_literal(
-    chars: &Vec<char>,
+    line: &str,
     line_num: usize,
-    line_len: usize,
     mut i: usize,
     path: &'static Path,
-) -> Option<String> {
-    let start_index: usize = &i - 1;
-    let quote_type: char = chars[start_index];
+    col: &dyn Fn(usize) -> usize,
+) -> Result<(String, usize), FeoError> {
+    let bytes: &[u8] = line.as_bytes();
+    let start_index: usize = i - 1;
+    let quote_type: u8 = bytes[start_index];
     let mut buf = String::new();
+    let mut is_closed: bool = false;
 
-    while i < line_len - 1 {
-        if chars[i] == quote_type {
-            break;
-        }
+    while i < bytes.len() {
+        // copy everything up to the next quote or escape in one go
+        let run: usize = bytes[i..]
+            .iter()
+            .position(|b| *b == quote_type || *b == b'\\')
+            .unwrap_or(bytes.len() - i);
 
-        if chars[i] == '\\' {
-            i += 1;
-        }
+        buf.push_str(&line[i..i + run]);
+        i += run;
 
-        buf.push(chars[i]);
-        i += 1;
+        match bytes.get(i) {
+            Some(b'\\') => {
+                i += 1;
+
+                let c: char = match line[i..].chars().next() {
+                    Some(c) => c,
+                    None => break,
+                };
+
+                buf.push(match c {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    '0' => '\0',
+                    _ => c,
+                });
+
+                i += c.len_utf8();
+            }
+
+            Some(_) => {
+                is_closed = true;
+                break;
+            }
+
+            None => break,
+        }
     }
 
-    if chars[i] != quote_type {
+    if !is_closed {
         let err = FeoError::new(
             ErrorId::ERR_INVALID_CHAR,
-            Some(quote_type),
+            Some(quote_type as char),
             path,
             line_num,
-            start_index + 1,
+            col(start_index),
             "Missing quote character",
         );
 
-        panic!("{}", err);
+        return Err(err);
     };
 
-    if quote_type == '\'' {
+    if quote_type == b'\'' {
         if buf.is_empty() {
             let err = FeoError::new(
                 ErrorId::ERR_INVALID_CHAR,
                 None,
                 path,
                 line_num,
-                start_index + 1,
+                col(start_index),
                 "Missing character value. Character literals cannot be empty",
             );
 
-            panic!("{}", err);
+            return Err(err);
         }
 
-        if buf.len() > 1 {
+        if buf.chars().count() > 1 {
             let err = FeoError::new(
                 ErrorId::ERR_INVALID_CHAR,
                 None,
                 path,
                 line_num,
-                start_index + 1,
+                col(start_index),
                 "Character literals must consist of a single value",
             );
 
-            panic!("{}", err);
+            return Err(err);
         }
     }
 
-    Some(buf)
-}
-
-/// Change the value of `COMMENT_BLOCK` to true
-///
-fn make_comment_true() {
-    unsafe { COMMENT_BLOCK = true }
-}
-
-/// Change the value of `COMMENT_BLOCK` to true
-///
-fn make_comment_false() {
-    unsafe { COMMENT_BLOCK = false }
+    // skip the closing quote
+    Ok((buf, i + 1))
 }
@@ -0,0 +1,244 @@
+use super::{tokenize_line, LexState};
+use crate::error::{ErrorId, FeoError};
+use crate::lexer::token::Token;
+use std::{ops::Range, path::Path};
+
+/// A lexed source file that can be edited in place, re-lexing only the lines affected by
+/// each edit
+///
+/// Every line remembers the `LexState` it was lexed from. After an edit, lexing resumes at
+/// the first changed line and stops as soon as it reaches an unchanged line whose stored
+/// state matches the new one; tokens from there on are reused (moved to their new line
+/// numbers if lines were added or removed)
+///
+#[derive(Debug)]
+pub struct LexedFile {
+    path: &'static Path,
+    source: String,
+    line_starts: Vec<usize>,
+    states: Vec<LexState>,
+    tokens: Vec<Vec<Token>>,
+    dirty: Option<Range<usize>>,
+}
+
+impl LexedFile {
+    /// Lexes a whole file, ready for editing
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - the contents of the file
+    /// * `path` - the location of the file in memory
+    ///
+    pub fn new(source: String, path: &'static Path) -> Result<Self, FeoError> {
+        let mut file = Self {
+            path,
+            source: String::new(),
+            line_starts: Vec::new(),
+            states: Vec::new(),
+            tokens: Vec::new(),
+            dirty: None,
+        };
+
+        file.edit(0..0, &source)?;
+
+        Ok(file)
+    }
+
+    /// Returns the current contents of the file
+    ///
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the tokens of each line (as returned by `lex`), or `None` if the last edit
+    /// left the file with a lexing error
+    ///
+    pub fn tokens(&self) -> Option<&[Vec<Token>]> {
+        match self.dirty {
+            Some(_) => None,
+            None => Some(&self.tokens),
+        }
+    }
+
+    /// Replaces a range of the source with new text and re-lexes the affected lines,
+    /// returning the (0-based) indices of the lines that were re-lexed
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - the byte range of the source to replace
+    /// * `replacement` - the text to insert in its place
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FeoError` if the range is out of bounds or does not fall on `char`
+    /// boundaries (in which case the file is unchanged), or if the edited source fails to lex.
+    /// In the latter case the edit is still applied, and lexing restarts from the offending
+    /// line on the next edit
+    ///
+    pub fn edit(
+        &mut self,
+        range: Range<usize>,
+        replacement: &str,
+    ) -> Result<Range<usize>, FeoError> {
+        if range.start > range.end
+            || range.end > self.source.len()
+            || !self.source.is_char_boundary(range.start)
+            || !self.source.is_char_boundary(range.end)
+        {
+            let err = FeoError::new(
+                ErrorId::ERR_INVALID_DATA,
+                None,
+                self.path,
+                self.line_index(range.start.min(self.source.len())) + 1,
+                1,
+                "Edit range is out of bounds or splits a character",
+            );
+
+            return Err(err);
+        }
+
+        // the lines touched by the edit, in the old line numbering
+        let first: usize = self.line_index(range.start);
+        let old_count: usize = match self.line_starts.len() {
+            0 => 0,
+            _ => self.line_index(range.end) + 1 - first,
+        };
+
+        let region_start: usize = self.line_offset(first);
+        let region_end: usize = self.line_offset(first + old_count);
+        let first_state: LexState = self.states.get(first).copied().unwrap_or_default();
+
+        self.source.replace_range(range.clone(), replacement);
+
+        // find the start of every line in the edited region of the new source
+        let byte_delta: isize = replacement.len() as isize - range.len() as isize;
+        let new_region_end: usize = (region_end as isize + byte_delta) as usize;
+        let mut new_starts: Vec<usize> = Vec::new();
+        let mut start: usize = region_start;
+
+        while start < new_region_end {
+            new_starts.push(start);
+
+            start = match self.source[start..new_region_end].find('\n') {
+                Some(n) => start + n + 1,
+                None => new_region_end,
+            };
+        }
+
+        let new_count: usize = new_starts.len();
+        let shifted_starts = self.line_starts[first + old_count..]
+            .iter()
+            .map(|s| (*s as isize + byte_delta) as usize);
+        let shifted_starts: Vec<usize> = new_starts.into_iter().chain(shifted_starts).collect();
+        self.line_starts.truncate(first);
+        self.line_starts.extend(shifted_starts);
+
+        let placeholders = (0..new_count).map(|_| Vec::new());
+        self.tokens.splice(first..first + old_count, placeholders);
+        self.states.splice(
+            first..first + old_count,
+            (0..new_count).map(|_| LexState::default()),
+        );
+
+        // lines after the edited region keep their text, but move by `line_delta`
+        let line_delta: isize = new_count as isize - old_count as isize;
+        let relex_from: usize;
+        let relex_until: usize;
+
+        // lines left unlexed by a previous failed edit must be re-lexed too
+        match self.dirty.take() {
+            Some(dirty) => {
+                let dirty_start: usize = match dirty.start {
+                    d if d < first => d,
+                    d if d < first + old_count => first,
+                    d => (d as isize + line_delta) as usize,
+                };
+                let dirty_end: usize = match dirty.end {
+                    d if d <= first => d,
+                    d if d <= first + old_count => first + new_count,
+                    d => (d as isize + line_delta) as usize,
+                };
+
+                relex_from = dirty_start.min(first);
+                relex_until = dirty_end.max(first + new_count);
+            }
+            None => {
+                relex_from = first;
+                relex_until = first + new_count;
+            }
+        }
+
+        let mut state: LexState = match relex_from {
+            _ if relex_from == first => first_state,
+            _ => self.states[relex_from],
+        };
+        let mut line: usize = relex_from;
+
+        while line < self.line_starts.len() {
+            if line >= relex_until && self.states[line] == state {
+                break;
+            }
+
+            self.states[line] = state;
+
+            let text: &str = self.line_text(line);
+
+            match tokenize_line(text, line + 1, &mut state, self.path) {
+                Ok(tokens) => self.tokens[line] = tokens,
+                Err(e) => {
+                    self.tokens[line] = Vec::new();
+                    self.dirty = Some(line..relex_until.max(line + 1));
+                    self.renumber(line + 1, line_delta);
+
+                    return Err(e);
+                }
+            }
+
+            line += 1;
+        }
+
+        self.renumber(line, line_delta);
+
+        Ok(relex_from..line)
+    }
+
+    /// Moves every token from the given line onwards by `line_delta` lines
+    ///
+    fn renumber(&mut self, from: usize, line_delta: isize) {
+        if line_delta == 0 {
+            return;
+        }
+
+        for tok in self.tokens.iter_mut().skip(from).flatten() {
+            tok.set_line((tok.line() as isize + line_delta) as usize);
+        }
+    }
+
+    /// Returns the index of the line containing the given byte offset (the last line, if the
+    /// offset is at the end of the source)
+    ///
+    fn line_index(&self, offset: usize) -> usize {
+        self.line_starts
+            .partition_point(|s| *s <= offset)
+            .saturating_sub(1)
+    }
+
+    /// Returns the byte offset at which the given line starts, or the end of the source if
+    /// there is no such line
+    ///
+    fn line_offset(&self, line: usize) -> usize {
+        match self.line_starts.get(line) {
+            Some(s) => *s,
+            None => self.source.len(),
+        }
+    }
+
+    /// Returns the text of the given line, without its line break (as with `str::lines`)
+    ///
+    fn line_text(&self, line: usize) -> &str {
+        let text: &str = &self.source[self.line_starts[line]..self.line_offset(line + 1)];
+        let text: &str = text.strip_suffix('\n').unwrap_or(text);
+
+        text.strip_suffix('\r').unwrap_or(text)
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+/// The size of each block of interned text. Names are copied into the current block until it
+/// is full, so interning costs one allocation per block rather than one per name
+///
+const BLOCK_SIZE: usize = 16 << 10;
+
+/// An interned identifier: a handle to a name stored once for the life of the program, so
+/// that lexing an identifier does not allocate and identifiers compare as integers
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Returns the symbol for a name, interning it if it has not been seen before
+    ///
+    pub fn intern(name: &str) -> Symbol {
+        Interner::lock().intern(name)
+    }
+
+    /// Returns the name the symbol stands for
+    ///
+    pub fn as_str(self) -> &'static str {
+        Interner::lock().names[self.0 as usize]
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+/// The table of interned names, shared by every thread. It is locked for each name looked
+/// up, rather than for a whole file, so that files can be lexed on several threads at once
+///
+/// Interned names are never freed: each distinct name is stored once, in blocks that live
+/// for the rest of the program, which is what lets `Symbol::as_str` hand out `&'static str`.
+/// Lexing a name seen before takes no more memory, so re-lexing a file after each edit only
+/// grows the table by the names the edit introduces. In a long-running process such as an
+/// editor that includes every partial name typed along the way (`f`, `fo`, `foo`), so the
+/// table grows, slowly, for as long as the process runs
+///
+#[derive(Default)]
+struct Interner {
+    symbols: HashMap<&'static str, Symbol, BuildHasherDefault<FxHasher>>,
+    names: Vec<&'static str>,
+
+    /// the unused end of the current block
+    block: &'static mut [u8],
+}
+
+impl Interner {
+    /// Locks the table
+    ///
+    fn lock() -> MutexGuard<'static, Interner> {
+        static INTERNER: OnceLock<Mutex<Interner>> = OnceLock::new();
+
+        INTERNER
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(name) {
+            return *symbol;
+        }
+
+        if self.block.len() < name.len() {
+            let size: usize = BLOCK_SIZE.max(name.len());
+            self.block = vec![0; size].leak();
+        }
+
+        let (stored, rest) = std::mem::take(&mut self.block).split_at_mut(name.len());
+        stored.copy_from_slice(name.as_bytes());
+        self.block = rest;
+
+        // the bytes were copied from a `str`, so they are valid UTF-8
+        let stored: &'static str = std::str::from_utf8(stored).unwrap();
+        let symbol = Symbol(self.names.len() as u32);
+        self.names.push(stored);
+        self.symbols.insert(stored, symbol);
+
+        symbol
+    }
+}
+
+/// A fast, non-cryptographic hasher for short keys such as identifiers (the hash used by
+/// `rustc`'s own interner)
+///
+#[derive(Default)]
+struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(Self::SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+
+        for b in chunks.remainder() {
+            self.add(u64::from(*b));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add(u64::from(i));
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
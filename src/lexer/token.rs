@@ -1,5 +1,9 @@
-#[derive(Debug, PartialEq)]
-#[allow(non_camel_case_types)]
+use super::symbol::Symbol;
+use crate::span::Span;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 pub enum TokType {
     // keywords
     KW_ABSTRACT,
@@ -20,6 +24,7 @@ pub enum TokType {
     KW_FUNC,
     KW_IF,
     KW_IMPORT,
+    KW_IN,
     KW_INT,
     KW_IS,
     KW_LET,
@@ -43,7 +48,10 @@ pub enum TokType {
     KW_WHILE,
 
     // identifier
-    IDEN(String),
+    IDEN(Symbol),
+
+    /// a loop label, such as `'outer`, including its `'`
+    LABEL(Symbol),
 
     // literals
     LIT_BOOL(bool),
@@ -76,6 +84,8 @@ pub enum TokType {
     OP_PIPE,
     OP_PLUS,
     OP_PLUS_EQ,
+    OP_RANGE,
+    OP_RANGE_INCL,
     OP_TERNARY,
     OP_THIN_ARW,
 
@@ -90,6 +100,7 @@ pub enum TokType {
     PUNC_CLS_SQ_BKT,
     PUNC_OPEN_PAREN,
     PUNC_CLS_PAREN,
+    PUNC_UNDERSCORE,
 
     INVALID_CHAR(char),
 }
@@ -124,6 +135,153 @@ impl From<bool> for TokType {
     }
 }
 
+impl fmt::Display for TokType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokType::KW_ABSTRACT => write!(f, "abstract"),
+            TokType::KW_ALIAS => write!(f, "alias"),
+            TokType::KW_AS => write!(f, "as"),
+            TokType::KW_BOOL => write!(f, "bool"),
+            TokType::KW_BREAK => write!(f, "break"),
+            TokType::KW_CHAR => write!(f, "char"),
+            TokType::KW_CLASS => write!(f, "class"),
+            TokType::KW_CONST => write!(f, "const"),
+            TokType::KW_CONTINUE => write!(f, "continue"),
+            TokType::KW_ELSE => write!(f, "else"),
+            TokType::KW_ENUM => write!(f, "enum"),
+            TokType::KW_EXTERN => write!(f, "extern"),
+            TokType::KW_FINAL => write!(f, "final"),
+            TokType::KW_FLOAT => write!(f, "float"),
+            TokType::KW_FOR => write!(f, "for"),
+            TokType::KW_FUNC => write!(f, "func"),
+            TokType::KW_IF => write!(f, "if"),
+            TokType::KW_IMPORT => write!(f, "import"),
+            TokType::KW_IN => write!(f, "in"),
+            TokType::KW_INT => write!(f, "int"),
+            TokType::KW_IS => write!(f, "is"),
+            TokType::KW_LET => write!(f, "let"),
+            TokType::KW_LIB => write!(f, "lib"),
+            TokType::KW_LOOP => write!(f, "loop"),
+            TokType::KW_MATCH => write!(f, "match"),
+            TokType::KW_NEW => write!(f, "new"),
+            TokType::KW_OVERRIDE => write!(f, "override"),
+            TokType::KW_PROTOCOL => write!(f, "protocol"),
+            TokType::KW_PUBLIC => write!(f, "public"),
+            TokType::KW_RETURN => write!(f, "return"),
+            TokType::KW_SELF => write!(f, "self"),
+            TokType::KW_STATIC => write!(f, "static"),
+            TokType::KW_STRING => write!(f, "String"),
+            TokType::KW_STRUCT => write!(f, "struct"),
+            TokType::KW_SUPER => write!(f, "super"),
+            TokType::KW_TYPE => write!(f, "type"),
+            TokType::KW_UINT => write!(f, "uint"),
+            TokType::KW_VAR => write!(f, "var"),
+            TokType::KW_VIRTUAL => write!(f, "virtual"),
+            TokType::KW_WHILE => write!(f, "while"),
+
+            TokType::IDEN(name) => write!(f, "{}", name),
+            TokType::LABEL(name) => write!(f, "{}", name),
+
+            TokType::LIT_BOOL(b) => write!(f, "{}", b),
+            TokType::LIT_CHAR(c) => write!(f, "'{}'", escape(&c.to_string(), '\'')),
+            TokType::LIT_FLOAT(n) => write_float(f, *n),
+            TokType::LIT_INT(n) => write!(f, "{}", n),
+            TokType::LIT_STRING(s) => write!(f, "\"{}\"", escape(s, '"')),
+
+            TokType::OP_AMPERSAND => write!(f, "&"),
+            TokType::OP_AND => write!(f, "&&"),
+            TokType::OP_ASSIGN => write!(f, "="),
+            TokType::OP_BANG => write!(f, "!"),
+            TokType::OP_DIV => write!(f, "/"),
+            TokType::OP_DIV_EQ => write!(f, "/="),
+            TokType::OP_EQ => write!(f, "=="),
+            TokType::OP_FAT_ARW => write!(f, "=>"),
+            TokType::OP_GRTR => write!(f, ">"),
+            TokType::OP_GRTR_EQ => write!(f, ">="),
+            TokType::OP_LESS => write!(f, "<"),
+            TokType::OP_LESS_EQ => write!(f, "<="),
+            TokType::OP_MINUS => write!(f, "-"),
+            TokType::OP_MINUS_EQ => write!(f, "-="),
+            TokType::OP_MOD => write!(f, "%"),
+            TokType::OP_MOD_EQ => write!(f, "%="),
+            TokType::OP_MULT => write!(f, "*"),
+            TokType::OP_MULT_EQ => write!(f, "*="),
+            TokType::OP_NOT_EQ => write!(f, "!="),
+            TokType::OP_OR => write!(f, "||"),
+            TokType::OP_PIPE => write!(f, "|"),
+            TokType::OP_PLUS => write!(f, "+"),
+            TokType::OP_PLUS_EQ => write!(f, "+="),
+            TokType::OP_RANGE => write!(f, ".."),
+            TokType::OP_RANGE_INCL => write!(f, "..="),
+            TokType::OP_TERNARY => write!(f, "?"),
+            TokType::OP_THIN_ARW => write!(f, "->"),
+
+            TokType::PUNC_DOT => write!(f, "."),
+            TokType::PUNC_COMMA => write!(f, ","),
+            TokType::PUNC_COLON => write!(f, ":"),
+            TokType::PUNC_SEMICOLON => write!(f, ";"),
+            TokType::PUNC_OPEN_CRL_BRC => write!(f, "{{"),
+            TokType::PUNC_CLS_CRL_BRC => write!(f, "}}"),
+            TokType::PUNC_OPEN_SQ_BKT => write!(f, "["),
+            TokType::PUNC_CLS_SQ_BKT => write!(f, "]"),
+            TokType::PUNC_OPEN_PAREN => write!(f, "("),
+            TokType::PUNC_CLS_PAREN => write!(f, ")"),
+            TokType::PUNC_UNDERSCORE => write!(f, "_"),
+
+            TokType::INVALID_CHAR(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+/// Returns a copy of a text literal's contents with the quote character, backslashes and
+/// control characters escaped, so that the result can be lexed back into the same value
+///
+/// # Arguments
+///
+/// * `text` - the unescaped contents of a `char` or `String` literal
+/// * `quote` - the quote character delimiting the literal
+///
+fn escape(text: &str, quote: char) -> String {
+    let mut buf = String::new();
+
+    for c in text.chars() {
+        match c {
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            '\0' => buf.push_str("\\0"),
+            _ if c == quote => {
+                buf.push('\\');
+                buf.push(c);
+            }
+            _ => buf.push(c),
+        }
+    }
+
+    buf
+}
+
+/// Writes a float literal without loss of precision, keeping the decimal point so that the
+/// output is not read back as an integer
+///
+/// The lexer rejects literals too large to be finite, so `inf` and `NaN`, which would be
+/// read back as identifiers, are never written
+///
+fn write_float(f: &mut fmt::Formatter<'_>, n: f64) -> fmt::Result {
+    debug_assert!(n.is_finite(), "float literals are finite, found {}", n);
+
+    // `Display` for `f64` prints the shortest representation that round-trips, and never
+    // uses exponent notation (which the lexer does not support)
+    let text = n.to_string();
+
+    if text.contains('.') {
+        write!(f, "{}", text)
+    } else {
+        write!(f, "{}.0", text)
+    }
+}
+
 impl TokType {
     /// Returns `true` if the tok type is [`TIden`].
     ///
@@ -147,23 +305,52 @@ impl TokType {
     }
 }
 
-#[derive(Debug)]
+/// A token, along with the region of its line it was lexed from. Positions are stored as
+/// `u32`, which keeps a token to half the size of the `usize` equivalent and so the tokens of
+/// a large file in fewer pages
+///
+#[derive(Debug, Clone)]
 pub struct Token {
     tok_type: TokType,
-    _line: usize,
-    _col: usize,
+    line: u32,
+    col: u32,
+    len: u32,
 }
 
 impl Token {
-    pub fn new(tok_type: TokType, line: usize, col: usize) -> Self {
-        Self {
+    /// Returns a token, or `None` if its position does not fit in a `u32`
+    ///
+    pub fn new(tok_type: TokType, line: usize, col: usize, len: usize) -> Option<Self> {
+        Some(Self {
             tok_type,
-            _line: line,
-            _col: col,
-        }
+            line: u32::try_from(line).ok()?,
+            col: u32::try_from(col).ok()?,
+            len: u32::try_from(len).ok()?,
+        })
     }
 
     pub fn tok_type(&self) -> &TokType {
         &self.tok_type
     }
+
+    pub fn line(&self) -> usize {
+        self.line as usize
+    }
+
+    pub fn col(&self) -> usize {
+        self.col as usize
+    }
+
+    /// Returns the region of the source the token was lexed from
+    ///
+    pub fn span(&self) -> Span {
+        let (line, col) = (self.line(), self.col());
+        Span::new(line, col, line, col + self.len as usize)
+    }
+
+    /// Moves the token to another line, e.g. when lines are inserted above it
+    ///
+    pub(crate) fn set_line(&mut self, line: usize) {
+        self.line = line as u32;
+    }
 }
@@ -1,25 +1,3010 @@
+pub mod ast;
 pub mod error;
 pub mod lexer;
+pub mod module;
+pub mod parser;
+pub mod sema;
+pub mod span;
+pub mod syntax;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ast::{
+        BindingKind, ExprKind, ImportKind, ItemKind, Literal, MemberKind, PatternKind,
+        ProtocolMemberKind, StmtKind, TypeKind, VariantKind,
+    };
+    use error::ErrorId;
+    use lexer::incremental::LexedFile;
+    use lexer::symbol::Symbol;
+    use lexer::token::{TokType, Token};
+    use module::ModuleResolver;
+    use span::Span;
     use std::path::Path;
+    use std::rc::Rc;
+    use syntax::view::{self, AstView, SourceFile};
+    use syntax::{GreenElement, GreenNode, GreenToken, SyntaxNode};
 
     #[test]
     fn lexer_works() {
         let path = Path::new("./examples/ex01.feo");
-        let file = match std::fs::read_to_string(path) {
-            Ok(f) => f,
-            Err(e) => match e.kind() {
-                std::io::ErrorKind::NotFound => panic!("Unable to locate file"),
-                std::io::ErrorKind::InvalidInput => panic!("Path not valid ({:?})", path),
-                _ => panic!("Unable to read file"),
-            },
+        let file = std::fs::read_to_string(path).unwrap();
+        let tokens = lexer::lex(&file, path).unwrap();
+
+        assert_eq!(tokens.len(), file.lines().count());
+
+        // the block comment spans the first three lines, and the line comment is dropped
+        assert!(tokens[..3].iter().all(|l| l.is_empty()));
+        assert_eq!(
+            tok_types(&tokens[3..5]),
+            [
+                vec![
+                    &TokType::KW_LET,
+                    &TokType::IDEN(Symbol::intern("baz")),
+                    &TokType::PUNC_COLON,
+                    &TokType::KW_INT,
+                    &TokType::OP_ASSIGN,
+                    &TokType::LIT_INT(1),
+                    &TokType::PUNC_SEMICOLON,
+                ],
+                vec![
+                    &TokType::KW_LET,
+                    &TokType::IDEN(Symbol::intern("foo")),
+                    &TokType::PUNC_COLON,
+                    &TokType::KW_FLOAT,
+                    &TokType::OP_ASSIGN,
+                    &TokType::LIT_FLOAT(0.1),
+                    &TokType::PUNC_SEMICOLON,
+                ],
+            ]
+        );
+
+        let positions: Vec<_> = tokens[3].iter().map(|t| (t.line(), t.col())).collect();
+        assert_eq!(
+            positions,
+            [(4, 1), (4, 5), (4, 8), (4, 10), (4, 14), (4, 16), (4, 17)]
+        );
+
+        // a position past `u32::MAX`, in a file of more than 4 GiB, is not truncated
+        let far = u32::MAX as usize + 1;
+        assert!(Token::new(TokType::PUNC_SEMICOLON, far, 1, 1).is_none());
+        assert!(Token::new(TokType::PUNC_SEMICOLON, 1, far, 1).is_none());
+    }
+
+    #[test]
+    fn lexer_interns_identifiers() {
+        let tokens = lex_str("let total = total + count;");
+        let names: Vec<Symbol> = tokens[0]
+            .iter()
+            .filter_map(|t| match t.tok_type() {
+                TokType::IDEN(name) => Some(*name),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(names[0], names[1]);
+        assert_ne!(names[0], names[2]);
+        assert_eq!(names[2], Symbol::intern("count"));
+        assert_eq!(names[0].as_str(), "total");
+        assert_eq!(format!("{:?} {}", names[2], names[2]), "\"count\" count");
+
+        // files lexed on several threads at once share one table of names
+        let threads: Vec<_> = (0..4)
+            .map(|n| std::thread::spawn(move || lex_str(&format!("let shared_{} = 1;", n % 2))))
+            .collect();
+        let names: Vec<TokType> = threads
+            .into_iter()
+            .map(|t| t.join().unwrap()[0][1].tok_type().clone())
+            .collect();
+
+        assert_eq!(names[0], names[2]);
+        assert_eq!(names[1], names[3]);
+        assert_eq!(names[0], TokType::IDEN(Symbol::intern("shared_0")));
+    }
+
+    #[test]
+    fn lexer_round_trips() {
+        let path = Path::new("./examples/ex01.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+
+        let tokens = lexer::lex(&file, path).unwrap();
+        let printed = lexer::print(&tokens);
+        let relexed = lexer::lex(&printed, path).unwrap();
+
+        assert_eq!(tok_types(&tokens), tok_types(&relexed));
+    }
+
+    #[test]
+    fn it_displays_literals() {
+        assert_eq!(
+            TokType::LIT_STRING("a\"b\\c".to_string()).to_string(),
+            r#""a\"b\\c""#
+        );
+        assert_eq!(TokType::LIT_CHAR('\'').to_string(), r"'\''");
+        assert_eq!(TokType::LIT_FLOAT(0.1).to_string(), "0.1");
+        assert_eq!(TokType::LIT_FLOAT(-3.0).to_string(), "-3.0");
+        assert_eq!(
+            TokType::LIT_FLOAT(1e20).to_string(),
+            "100000000000000000000.0"
+        );
+    }
+
+    #[test]
+    fn lexer_matches_golden_files() {
+        // set `FEO_BLESS=1` to (re)generate the expected `.tokens` files
+        let bless = std::env::var_os("FEO_BLESS").is_some();
+        let dir = Path::new("./tests/golden/lexer");
+
+        let mut inputs: Vec<_> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.extension().is_some_and(|e| e == "feo"))
+            .collect();
+        inputs.sort();
+
+        assert!(!inputs.is_empty(), "No golden files found in {:?}", dir);
+
+        for input in inputs {
+            let file = std::fs::read_to_string(&input).unwrap();
+            let path: &'static Path = Box::leak(input.clone().into_boxed_path());
+
+            let actual = match lexer::lex(&file, path) {
+                Ok(tokens) => dump_tokens(&tokens),
+                Err(e) => format!("{}\n", e),
+            };
+
+            let expected_path = input.with_extension("tokens");
+
+            if bless {
+                std::fs::write(&expected_path, &actual).unwrap();
+                continue;
+            }
+
+            let expected = std::fs::read_to_string(&expected_path)
+                .unwrap_or_else(|_| panic!("Missing golden file {:?}", expected_path));
+
+            assert_eq!(expected, actual, "Token dump mismatch for {:?}", input);
+        }
+    }
+
+    #[test]
+    fn lexer_round_trips_int_literals() {
+        let mut rng = Rng::new(0x5eed_0001);
+
+        for _ in 0..1000 {
+            let n = rng.next() as i32;
+            let src = format!("let x = {};", n);
+
+            assert_eq!(
+                lex_str(&src)[0][3].tok_type(),
+                &TokType::LIT_INT(n),
+                "{}",
+                src
+            );
+        }
+    }
+
+    #[test]
+    fn lexer_round_trips_float_literals() {
+        let mut rng = Rng::new(0x5eed_0002);
+
+        for _ in 0..1000 {
+            let n = f64::from_bits(rng.next());
+
+            if !n.is_finite() {
+                continue;
+            }
+
+            let src = format!("let x = {};", TokType::LIT_FLOAT(n));
+
+            match lex_str(&src)[0][3].tok_type() {
+                TokType::LIT_FLOAT(m) => assert_eq!(n.to_bits(), m.to_bits(), "{}", src),
+                t => panic!("Expected a float literal in {}, found {:?}", src, t),
+            }
+        }
+
+        // a float too large for an `f64` is out of range, like an integer too large for an
+        // `int`, rather than infinite
+        let path = Path::new("test.feo");
+        let src = format!("let x = -1{}.5;", "0".repeat(400));
+        let err = lexer::lex(&src, path).unwrap_err();
+        let int_err = lexer::lex("let x = 2147483648;", path).unwrap_err();
+
+        assert_eq!((err.id(), err.col()), (ErrorId::ERR_INVALID_DATA, 9));
+        assert_eq!(err.message(), int_err.message());
+    }
+
+    #[test]
+    fn lexer_round_trips_text_literals() {
+        let pool = [
+            'a', 'Z', '0', ' ', '"', '\'', '\\', '/', '*', '\n', '\t', '\r', '\0', 'é', '中',
+        ];
+        let mut rng = Rng::new(0x5eed_0003);
+
+        for _ in 0..1000 {
+            let len = (rng.next() % 16) as usize;
+            let s: String = (0..len)
+                .map(|_| pool[(rng.next() % pool.len() as u64) as usize])
+                .collect();
+            let c = pool[(rng.next() % pool.len() as u64) as usize];
+
+            let string_lit = TokType::LIT_STRING(s);
+            let char_lit = TokType::LIT_CHAR(c);
+            let src = format!("let x = {}; let y = {};", string_lit, char_lit);
+            let tokens = lex_str(&src);
+
+            assert_eq!(tokens[0][3].tok_type(), &string_lit, "{}", src);
+            assert_eq!(tokens[0][8].tok_type(), &char_lit, "{}", src);
+        }
+    }
+
+    #[test]
+    fn lexer_round_trips_random_token_streams() {
+        let pieces = [
+            "let",
+            "x",
+            "foo_1",
+            "=",
+            "==",
+            "-",
+            "-=",
+            "->",
+            "=>",
+            "+",
+            "*",
+            "/",
+            "%",
+            "<",
+            ">=",
+            "!",
+            "&&",
+            "|",
+            "?",
+            ":",
+            ";",
+            ",",
+            ".",
+            "..",
+            "..=",
+            "_",
+            "(",
+            ")",
+            "[",
+            "]",
+            "{",
+            "}",
+            "1",
+            "-7",
+            "0x1F",
+            "2.5",
+            "-0.25",
+            "1_000",
+            "'c'",
+            "'outer",
+            "\"s\\\"\"",
+            "true",
+            "String",
+            "/* c */",
+            "// c",
+            " ",
+            "\n",
+            "\t",
+            "#",
+            "@",
+        ];
+        let mut rng = Rng::new(0x5eed_0004);
+
+        for _ in 0..1000 {
+            let len = (rng.next() % 32) as usize;
+            let src: String = (0..len)
+                .map(|_| pieces[(rng.next() % pieces.len() as u64) as usize])
+                .collect();
+
+            // the generated source is not necessarily valid, but the lexer must never panic,
+            // and whatever it accepts must survive a round trip through the printer
+            if let Ok(tokens) = lexer::lex(&src, Path::new("random.feo")) {
+                let relexed = lex_str(&lexer::print(&tokens));
+                assert_eq!(tok_types(&tokens), tok_types(&relexed), "{:?}", src);
+            }
+        }
+    }
+
+    #[test]
+    fn incremental_lexer_relexes_affected_lines() {
+        let path = Path::new("./examples/ex01.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+        let mut lexed = LexedFile::new(file, path).unwrap();
+
+        // `let baz: int = 1;` -> `let baz: int = 23;`, on line 4 only
+        let offset = lexed.source().find("= 1;").unwrap() + 2;
+        assert_eq!(lexed.edit(offset..offset + 1, "23").unwrap(), 3..4);
+        assert_eq!(
+            lexed.tokens().unwrap()[3][5].tok_type(),
+            &TokType::LIT_INT(23)
+        );
+
+        // opening a comment re-lexes every line up to the next comment terminator
+        let offset = lexed.source().find("let baz").unwrap();
+        assert_eq!(lexed.edit(offset..offset, "/*\n").unwrap(), 3..9);
+        assert!(lexed.tokens().unwrap()[3..].iter().all(|l| l.is_empty()));
+
+        // an error is reported, then recovered from on the next edit
+        assert!(lexed.edit(0..0, "\"").is_err());
+        assert!(lexed.tokens().is_none());
+        assert!(lexed.edit(0..1, "").is_ok());
+        assert_eq!(
+            dump_tokens(lexed.tokens().unwrap()),
+            dump_tokens(&lexer::lex(lexed.source(), path).unwrap())
+        );
+    }
+
+    #[test]
+    fn incremental_lexer_matches_full_lex() {
+        let pieces = [
+            "let", " x", " = ", "1", "-2", ";", "\n", "\r\n", "/*", "*/", "// c", "\"s\"", "'c'",
+            "é", "{", "}", " ",
+        ];
+        let path = Path::new("edits.feo");
+        let mut rng = Rng::new(0x5eed_0005);
+        let mut lexed = LexedFile::new(String::new(), path).unwrap();
+
+        for _ in 0..2000 {
+            let source = lexed.source();
+            let mut start = (rng.next() % (source.len() as u64 + 1)) as usize;
+            let mut end = start + (rng.next() % 8) as usize;
+            end = end.min(source.len());
+
+            while !source.is_char_boundary(start) {
+                start -= 1;
+            }
+
+            while !source.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let replacement: String = (0..rng.next() % 4)
+                .map(|_| pieces[(rng.next() % pieces.len() as u64) as usize])
+                .collect();
+
+            let result = lexed.edit(start..end, &replacement);
+
+            match lexer::lex(lexed.source(), path) {
+                Ok(expected) => {
+                    assert!(result.is_ok(), "{:?}", lexed.source());
+                    assert_eq!(
+                        dump_tokens(lexed.tokens().unwrap()),
+                        dump_tokens(&expected),
+                        "{:?}",
+                        lexed.source()
+                    );
+                }
+                Err(_) => {
+                    assert!(result.is_err(), "{:?}", lexed.source());
+                    assert!(lexed.tokens().is_none());
+                }
+            }
+
+            // keep the source from growing (or staying broken) indefinitely
+            if lexed.source().len() > 400 || rng.next().is_multiple_of(50) {
+                let len = lexed.source().len();
+                let _ = lexed.edit(0..len, "");
+            }
+        }
+    }
+
+    #[test]
+    fn parser_works() {
+        let path = Path::new("./examples/ex01.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+        let module = parser::parse(lexer::lex(&file, path).unwrap(), path).unwrap();
+
+        assert_eq!(module.items.len(), 5);
+
+        let bindings: Vec<(BindingKind, &str)> = module
+            .items
+            .iter()
+            .map(|item| {
+                let ItemKind::VAR(decl) = &item.kind else {
+                    panic!("Expected a variable declaration, found {:?}", item.kind);
+                };
+                let PatternKind::BINDING(iden) = &decl.pattern.kind else {
+                    panic!("Expected a binding, found {:?}", decl.pattern.kind);
+                };
+
+                (decl.binding, iden.name.as_str())
+            })
+            .collect();
+
+        assert_eq!(
+            bindings,
+            vec![
+                (BindingKind::LET, "baz"),
+                (BindingKind::LET, "foo"),
+                (BindingKind::LET, "bar"),
+                (BindingKind::CONST, "ready"),
+                (BindingKind::CONST, "greeting"),
+            ]
+        );
+
+        let ItemKind::VAR(decl) = &module.items[1].kind else {
+            panic!("Expected a variable declaration");
+        };
+
+        assert_eq!(decl.ty.as_ref().unwrap().kind, TypeKind::FLOAT);
+        assert_eq!(
+            decl.value.as_ref().unwrap().kind,
+            ExprKind::LITERAL(Literal::FLOAT(0.1))
+        );
+        assert_eq!(module.items[1].span, Span::new(5, 1, 5, 22));
+    }
+
+    #[test]
+    fn parser_parses_functions() {
+        let path = Path::new("./examples/ex02.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+        let module = parser::parse(lexer::lex(&file, path).unwrap(), path).unwrap();
+
+        let funcs: Vec<(&ast::Item, &ast::FnDecl)> = module
+            .items
+            .iter()
+            .filter_map(|item| match &item.kind {
+                ItemKind::FUNC(func) => Some((item, func)),
+                _ => None,
+            })
+            .collect();
+
+        let names: Vec<&str> = funcs.iter().map(|(_, f)| f.name.name.as_str()).collect();
+        assert_eq!(names, ["print", "add", "scale", "main"]);
+
+        let (print, add) = (funcs[0].1, funcs[1].1);
+        assert!(print.is_extern && print.body.is_none());
+        assert!(funcs[1].0.is_public && !funcs[0].0.is_public);
+
+        assert_eq!(add.params.len(), 2);
+        assert!(add.params[0].default.is_none());
+        assert_eq!(
+            add.params[1].default.as_ref().unwrap().kind,
+            ExprKind::LITERAL(Literal::INT(1))
+        );
+        assert_eq!(add.ret.as_ref().unwrap().kind, TypeKind::INT);
+
+        let body = add.body.as_ref().unwrap();
+        assert_eq!(body.stmts.len(), 1);
+        assert!(body.tail.is_none());
+
+        let scale = funcs[2].1.body.as_ref().unwrap();
+        assert_eq!(scale.stmts.len(), 1);
+        assert!(flat_sexpr(&module).contains("(tail (iden result))"));
+        assert!(funcs[3].1.ret.is_none());
+
+        let parse = |src: &str| parser::parse(lex_str(src), Path::new("test.feo"));
+        assert!(parse("extern func f() { }").is_err());
+        assert!(parse("func f();").is_err());
+        assert!(parse("func f(a) { }").is_err());
+    }
+
+    #[test]
+    fn parser_parses_structs_and_enums() {
+        let path = Path::new("./examples/ex03.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+        let module = parser::parse(lexer::lex(&file, path).unwrap(), path).unwrap();
+
+        let ItemKind::STRUCT(point) = &module.items[0].kind else {
+            panic!("expected a struct, found {:?}", module.items[0].kind);
+        };
+        assert_eq!(point.name.name, "Point");
+        assert_eq!(point.fields.len(), 2);
+        assert!(point.fields[0].is_public);
+        assert_eq!(point.fields[1].ty.kind, TypeKind::FLOAT);
+
+        let ItemKind::STRUCT(empty) = &module.items[1].kind else {
+            panic!("expected a struct, found {:?}", module.items[1].kind);
+        };
+        assert!(empty.fields.is_empty());
+
+        let ItemKind::ENUM(shape) = &module.items[2].kind else {
+            panic!("expected an enum, found {:?}", module.items[2].kind);
+        };
+        assert_eq!(shape.variants[0].kind, VariantKind::UNIT);
+        assert!(matches!(&shape.variants[1].kind, VariantKind::TUPLE(t) if t.len() == 2));
+        assert!(matches!(&shape.variants[2].kind, VariantKind::STRUCT(f) if f.len() == 3));
+        assert!(shape.variants.iter().all(|v| v.discriminant.is_none()));
+
+        let ItemKind::ENUM(color) = &module.items[3].kind else {
+            panic!("expected an enum, found {:?}", module.items[3].kind);
+        };
+        let discriminants: Vec<ExprKind> = color
+            .variants
+            .iter()
+            .map(|v| v.discriminant.as_ref().unwrap().kind.clone())
+            .collect();
+        assert_eq!(
+            discriminants,
+            [1, 2, 4].map(|n| ExprKind::LITERAL(Literal::INT(n)))
+        );
+
+        let dump = flat_sexpr(&module);
+        assert!(dump.contains("(tail (struct Point (field x (iden x)) (field y (literal 0.0))))"));
+        assert!(dump.contains(
+            "(tail (struct Shape.Rect (field origin (call (iden origin))) \
+             (field width (literal 1.0)) (field height (literal 1.0))))"
+        ));
+
+        let parse = |src: &str| parser::parse(lex_str(src), Path::new("test.feo"));
+        assert!(parse("enum E { A { public x: int } }").is_err());
+        assert!(parse("struct S { x }").is_err());
+        assert!(parse("let p = f() { x: 1 };").is_err());
+    }
+
+    #[test]
+    fn parser_parses_classes() {
+        let path = Path::new("./examples/ex04.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+        let module = parser::parse(lexer::lex(&file, path).unwrap(), path).unwrap();
+
+        let ItemKind::CLASS(animal) = &module.items[0].kind else {
+            panic!("expected a class, found {:?}", module.items[0].kind);
+        };
+        assert!(module.items[0].is_public && animal.is_abstract && !animal.is_final);
+        assert!(animal.supertypes.is_empty());
+
+        let kinds: Vec<&str> = animal
+            .members
+            .iter()
+            .map(|m| match &m.kind {
+                MemberKind::FIELD(_) => "field",
+                MemberKind::CONSTRUCTOR(_) => "constructor",
+                MemberKind::METHOD(_) => "method",
+                MemberKind::TYPE(_) => "type",
+                MemberKind::ERROR => "error",
+            })
+            .collect();
+        assert_eq!(kinds, ["field", "field", "constructor", "method", "method"]);
+
+        let count = &animal.members[1];
+        assert!(count.modifiers.is_static && !count.modifiers.is_public);
+        assert!(matches!(&count.kind, MemberKind::FIELD(f) if f.value.is_some()));
+
+        let MemberKind::METHOD(speak) = &animal.members[3].kind else {
+            panic!("expected a method, found {:?}", animal.members[3].kind);
+        };
+        assert!(animal.members[3].modifiers.is_abstract && speak.body.is_none());
+        assert!(animal.members[4].modifiers.is_virtual);
+
+        let ItemKind::CLASS(dog) = &module.items[1].kind else {
+            panic!("expected a class, found {:?}", module.items[1].kind);
         };
+        assert!(dog.is_final && !dog.is_abstract);
+        assert_eq!(dog.supertypes[0].name, "Animal");
+
+        let describe = &dog.members[2];
+        assert!(describe.modifiers.is_final && describe.modifiers.is_override);
+
+        let dump = flat_sexpr(&module);
+        assert!(dump.contains(
+            "(constructor (param (binding name) (String)) \
+             (block (call (super) (iden name))))"
+        ));
+        assert!(dump.contains("(block (tail (call (field describe (super)))))"));
+        assert!(dump.contains("(let (binding dog) (named Dog) (new Dog (literal \"Rex\")))"));
+
+        let parse = |src: &str| parser::parse(lex_str(src), Path::new("test.feo"));
+        assert!(parse("class A { virtual x: int; }").is_err());
+        assert!(parse("class A { static new() { } }").is_err());
+        assert!(parse("class A { public public func f() { } }").is_err());
+        assert!(parse("class A { abstract func f() { } }").is_err());
+        assert!(parse("static class A { }").is_err());
+        assert!(parse("let a = new A;").is_err());
+    }
+
+    #[test]
+    fn parser_parses_protocols_and_conformance() {
+        let path = Path::new("./examples/ex05.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+        let module = parser::parse(lexer::lex(&file, path).unwrap(), path).unwrap();
+
+        let ItemKind::PROTOCOL(printable) = &module.items[0].kind else {
+            panic!("expected a protocol, found {:?}", module.items[0].kind);
+        };
+        assert_eq!(printable.members.len(), 3);
+
+        let ProtocolMemberKind::TYPE(output) = &printable.members[0].kind else {
+            panic!("expected a type, found {:?}", printable.members[0].kind);
+        };
+        assert_eq!(output.name.name, "Output");
+        assert_eq!(output.bounds[0].name, "Printable");
+
+        let bodies: Vec<bool> = printable.members[1..]
+            .iter()
+            .map(|m| match &m.kind {
+                ProtocolMemberKind::METHOD(func) => func.body.is_some(),
+                kind => panic!("expected a method, found {:?}", kind),
+            })
+            .collect();
+        assert_eq!(bodies, [false, true]);
+
+        let ItemKind::STRUCT(point) = &module.items[2].kind else {
+            panic!("expected a struct, found {:?}", module.items[2].kind);
+        };
+        assert_eq!(point.protocols[0].name, "Printable");
+        assert_eq!(point.fields.len(), 2);
+        assert_eq!(point.members.len(), 3);
+        assert!(matches!(&point.members[0].kind, MemberKind::TYPE(t) if t.name.name == "Output"));
+        assert!(point.members[1].modifiers.is_public);
+        assert!(point.members[2].modifiers.is_static);
+
+        let ItemKind::ENUM(unit) = &module.items[3].kind else {
+            panic!("expected an enum, found {:?}", module.items[3].kind);
+        };
+        let protocols: Vec<&str> = unit.protocols.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(protocols, ["Printable", "Shape"]);
+        assert_eq!((unit.variants.len(), unit.members.len()), (2, 3));
+
+        let ItemKind::CLASS(label) = &module.items[4].kind else {
+            panic!("expected a class, found {:?}", module.items[4].kind);
+        };
+        assert_eq!(label.supertypes[0].name, "Printable");
+        assert_eq!(label.members.len(), 3);
+
+        let parse = |src: &str| parser::parse(lex_str(src), Path::new("test.feo"));
+        assert!(parse("protocol P { let x = 1; }").is_err());
+        assert!(parse("protocol P { type T = int; }").is_err());
+        assert!(parse("struct S { x: int, new() { } }").is_err());
+        assert!(parse("struct S { virtual func f() { } }").is_err());
+        assert!(parse("struct S { x: int y: int }").is_err());
+        assert!(parse("struct S: { }").is_err());
+    }
+
+    #[test]
+    fn parser_parses_control_flow() {
+        let path = Path::new("./examples/ex06.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+        let module = parser::parse(lexer::lex(&file, path).unwrap(), path).unwrap();
+
+        let dump = flat_sexpr(&module);
+        assert!(dump.contains(
+            "(tail (if (binary < (iden n) (literal 0)) (block (tail (literal -1))) \
+             (else (if (binary == (iden n) (literal 0)) (block (tail (literal 0))) \
+             (else (block (tail (literal 1))))))))"
+        ));
+        assert!(dump
+            .contains("(for (binding i) (iden items) (block (assign += (iden total) (iden i))))"));
+        assert!(dump.contains("(while (binary > (iden total) (literal 100))"));
+        assert!(dump.contains(
+            "(for 'outer: (binding row) (iden grid) (block (tail (for (binding cell) (iden row) \
+             (block (if (binary == (iden cell) (literal 0)) (block (continue 'outer:))) \
+             (assign = (iden first) (iden cell)) (break 'outer:))))))"
+        ));
+        assert!(dump.contains("(loop (block (break (iden first))))"));
+        assert!(dump.contains(
+            "(loop (block (assign += (iden n) (literal 1)) (tail (if (binary >= (iden n) \
+             (literal 10)) (block (break (binary * (iden n) (literal 2))))))))"
+        ));
+
+        assert_eq!(
+            expr_sexpr("if a { b } else { c } + 1"),
+            "(binary + (if (iden a) (block (tail (iden b))) (else (block (tail (iden c))))) \
+             (literal 1))"
+        );
+        assert_eq!(
+            expr_sexpr("'x: loop { break 'x }"),
+            "(loop 'x: (block (tail (break 'x:))))"
+        );
+        assert_eq!(
+            expr_sexpr("loop { break x }"),
+            "(loop (block (tail (break (iden x)))))"
+        );
+        assert_eq!(
+            expr_sexpr("c ? x : loop { }"),
+            "(ternary (iden c) (iden x) (loop (block)))"
+        );
+        assert_eq!(
+            expr_sexpr("if p == q { x }"),
+            "(if (binary == (iden p) (iden q)) (block (tail (iden x))))"
+        );
+        assert_eq!(
+            expr_sexpr("for i in 0..n + 1 { }"),
+            "(for (binding i) (range .. (literal 0) (binary + (iden n) (literal 1))) (block))"
+        );
+        assert_eq!(
+            expr_sexpr("if (Point { x: 1 }) == p { }"),
+            "(if (binary == (paren (struct Point (field x (literal 1)))) (iden p)) (block))"
+        );
+        assert_eq!(
+            expr_sexpr("if f(Point { x: 1 }) { }"),
+            "(if (call (iden f) (struct Point (field x (literal 1)))) (block))"
+        );
+
+        let parse = |src: &str| parser::parse(lex_str(src), Path::new("test.feo"));
+        assert!(parse("func f() { if a { } -1; }").is_ok());
+        assert!(parse("func f() { if a { } b }").is_ok());
+        assert!(parse("func f() { if a b }").is_err());
+        assert!(parse("func f() { for x y { } }").is_err());
+        assert!(parse("func f() { while { } }").is_err());
+    }
+
+    #[test]
+    fn parser_parses_match_expressions() {
+        let path = Path::new("./examples/ex07.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+        let module = parser::parse(lexer::lex(&file, path).unwrap(), path).unwrap();
+
+        let dump = flat_sexpr(&module);
+        assert!(dump.contains(
+            "(match (iden n) (arm (literal 0) (literal \"zero\")) \
+             (arm (or (literal 1) (literal 2) (literal 3)) (literal \"few\")) \
+             (arm (range 4 ..= 9) (literal \"some\")) \
+             (arm (binding n) (guard (binary < (iden n) (literal 0))) (literal \"negative\")) \
+             (arm (wildcard) (literal \"many\")))"
+        ));
+        assert!(dump.contains(
+            "(match (iden shape) (arm (variant Shape.Dot) (literal 0.0)) \
+             (arm (variant Shape.Circle tuple (wildcard) (binding r)) \
+             (binary * (binary * (iden r) (iden r)) (literal 3.14))) \
+             (arm (struct Shape.Rect (field width (binding w)) (field height (binding h))) \
+             (binary * (iden w) (iden h))))"
+        ));
+        assert!(dump.contains(
+            "(match (iden p) \
+             (arm (struct Point (field x (range 0.0 .. 1.0)) (field y (literal 0.0))) \
+             (literal true)) (arm (struct Point rest (field x (binding x))) \
+             (block (tail (binary == (iden x) (literal 1.0))))))"
+        ));
+        assert!(dump.contains(
+            "(match (iden pair) (arm (tuple (literal 0) (wildcard)) (literal 0)) \
+             (arm (tuple (binding a) (or (literal 1) (literal 2))) (iden a)) \
+             (arm (tuple (binding a) (binding b)) (binary + (iden a) (iden b))))"
+        ));
+
+        // the dump of `let <pattern> = x;`, without the declaration around the pattern
+        let pattern = |src: &str| {
+            let src = format!("let {} = x;", src);
+            let dump = flat_sexpr(&parser::parse(lex_str(&src), Path::new("test.feo")).unwrap());
+
+            dump.strip_prefix("(module (let ")
+                .and_then(|d| d.strip_suffix(" (iden x)))"))
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(pattern("(a)"), "(binding a)");
+        assert_eq!(pattern("(a,)"), "(tuple (binding a))");
+        assert_eq!(pattern("()"), "(tuple)");
+        assert_eq!(pattern("'a'..='z'"), "(range 'a' ..= 'z')");
+        assert_eq!(pattern("-5..-1"), "(range -5 .. -1)");
+        assert_eq!(
+            pattern("Color.Red | Color.Blue"),
+            "(or (variant Color.Red) (variant Color.Blue))"
+        );
+        assert_eq!(
+            pattern("Wrapper((a, b))"),
+            "(variant Wrapper tuple (tuple (binding a) (binding b)))"
+        );
+
+        let parse = |src: &str| parser::parse(lex_str(src), Path::new("test.feo"));
+        assert!(parse("let x = match y { _ => 1 2 => 3 };").is_err());
+        assert!(parse("let x = match y { _ => { } 2 => 3 };").is_ok());
+        assert!(parse("let x = match y { 1.. => 1 };").is_err());
+        assert!(parse("let x = match y { P { .., x } => 1 };").is_err());
+        assert!(parse("let x = match y { a if => 1 };").is_err());
+    }
+
+    #[test]
+    fn parser_parses_types_and_generics() {
+        let path = Path::new("./examples/ex08.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+        let module = parser::parse(lexer::lex(&file, path).unwrap(), path).unwrap();
+
+        let ItemKind::ALIAS(grid) = &module.items[0].kind else {
+            panic!("expected an alias, found {:?}", module.items[0].kind);
+        };
+        assert_eq!(grid.name.name, "Grid");
+        assert!(matches!(&module.items[1].kind, ItemKind::TYPE(t) if t.name.name == "Meters"));
+
+        let ItemKind::STRUCT(pair) = &module.items[2].kind else {
+            panic!("expected a struct, found {:?}", module.items[2].kind);
+        };
+        let generics: Vec<&str> = pair.generics.iter().map(|g| g.name.name.as_str()).collect();
+        assert_eq!(generics, ["A", "B"]);
+
+        let ItemKind::FUNC(largest) = &module.items[5].kind else {
+            panic!("expected a function, found {:?}", module.items[5].kind);
+        };
+        assert_eq!(largest.generics[0].name.name, "T");
+        let bounds: Vec<&str> = largest.generics[0]
+            .bounds
+            .iter()
+            .map(|b| b.name.as_str())
+            .collect();
+        assert_eq!(bounds, ["Shape", "Describe"]);
+
+        let dump = flat_sexpr(&module);
+        assert!(dump
+            .contains("(alias Grid (fixed_array (fixed_array (float) (literal 3)) (literal 3)))"));
+        assert!(dump.contains("(type Meters (float))"));
+        assert!(dump.contains("(field first (named A))"));
+        assert!(dump
+            .contains("(param (binding shapes) (array (named T))) (returns (optional (named T)))"));
+        assert!(dump.contains(
+            "(param (binding f) (func (int) (returns (bool)))) \
+             (param (binding values) (tuple (int) (int)))"
+        ));
+        assert!(dump.contains(
+            "(let (binding row) (fixed_array (float) (literal 3)) \
+             (array_repeat (literal 0.0) (literal 3)))"
+        ));
+        assert!(dump.contains(
+            "(let (binding pair) (named Pair (int) (String)) \
+             (struct Pair (field first (literal 1)) (field second (literal \"one\"))))"
+        ));
+        assert!(dump.contains("(let (binding unit) (tuple) (tuple))"));
+        assert!(dump.contains(
+            "(tail (array (array (literal 1.0) (literal 0.0) (literal 0.0)) \
+             (array (literal 0.0) (literal 1.0) (literal 0.0)) \
+             (array (literal 0.0) (literal 0.0) (literal 1.0))))"
+        ));
+
+        assert_eq!(
+            expr_sexpr("(1, a + b,)"),
+            "(tuple (literal 1) (binary + (iden a) (iden b)))"
+        );
+        assert_eq!(expr_sexpr("(1,)"), "(tuple (literal 1))");
+        assert_eq!(expr_sexpr("(1)"), "(paren (literal 1))");
+        assert_eq!(expr_sexpr("[]"), "(array)");
+
+        // the dump of `let x: <type> = y;`, without the declaration around the type
+        let ty = |src: &str| {
+            let src = format!("let x: {} = y;", src);
+            let dump = flat_sexpr(&parser::parse(lex_str(&src), Path::new("test.feo")).unwrap());
+
+            dump.strip_prefix("(module (let (binding x) ")
+                .and_then(|d| d.strip_suffix(" (iden y)))"))
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(ty("(int)"), "(int)");
+        assert_eq!(ty("(int,)"), "(tuple (int))");
+        assert_eq!(ty("func()"), "(func)");
+        assert_eq!(ty("[int?]?"), "(optional (array (optional (int))))");
+        assert_eq!(
+            ty("geometry.Map<String, [Point]>"),
+            "(named geometry.Map (String) (array (named Point)))"
+        );
+
+        let parse = |src: &str| parser::parse(lex_str(src), Path::new("test.feo"));
+        assert!(parse("alias X = ;").is_err());
+        assert!(parse("type X int;").is_err());
+        assert!(parse("func f<T:>() { }").is_err());
+        assert!(parse("func f<>() { }").is_ok());
+        assert!(parse("let x: [int; ] = y;").is_err());
+        assert!(parse("let x: Map<int = y;").is_err());
+    }
+
+    #[test]
+    fn parser_parses_imports() {
+        let parse = |src: &str| parser::parse(lex_str(src), Path::new("test.feo"));
+
+        let module =
+            parse("lib geometry; import a.b.{c, d.e as f, g.{h}}; import x as y;").unwrap();
+        assert!(matches!(&module.items[0].kind, ItemKind::LIB(name) if name.name == "geometry"));
+
+        let ItemKind::IMPORT(tree) = &module.items[1].kind else {
+            panic!("expected an import, found {:?}", module.items[1].kind);
+        };
+        let paths: Vec<String> = tree
+            .paths()
+            .iter()
+            .map(|(path, _)| {
+                let names: Vec<&str> = path.iter().map(|i| i.name.as_str()).collect();
+                names.join(".")
+            })
+            .collect();
+        assert_eq!(paths, ["a.b.c", "a.b.d.e", "a.b.g.h"]);
+
+        let ItemKind::IMPORT(tree) = &module.items[2].kind else {
+            panic!("expected an import, found {:?}", module.items[2].kind);
+        };
+        assert!(matches!(&tree.kind, ImportKind::SINGLE(Some(alias)) if alias.name == "y"));
+
+        let module = parse("import a.{b, c.d} as e;").unwrap();
+        let ItemKind::IMPORT(tree) = &module.items[0].kind else {
+            panic!("expected an import, found {:?}", module.items[0].kind);
+        };
+        assert!(matches!(
+            &tree.kind,
+            ImportKind::GROUP { trees, alias: Some(alias) } if trees.len() == 2 && alias.name == "e"
+        ));
+        assert_eq!(tree.paths().len(), 2);
+
+        assert!(parse("import a.{b, c} as;").is_err());
+        assert!(parse("import a.;").is_err());
+        assert!(parse("import a").is_err());
+    }
+
+    #[test]
+    fn parser_recovers_from_errors() {
+        let src = "func f() {\n\
+                   \x20   let x = 1\n\
+                   \x20   let y = ;\n\
+                   \x20   x + y\n\
+                   }\n\
+                   struct P { x int, y: float }\n\
+                   class A {\n\
+                   \x20   x: int\n\
+                   \x20   func bad( { }\n\
+                   \x20   func ok() { }\n\
+                   }\n\
+                   func unclosed() {\n\
+                   \x20   let a = 1;\n\
+                   func g() -> int { 1 }\n";
+
+        let (module, errors) = parser::parse_with_recovery(lex_str(src), Path::new("test.feo"));
+
+        let errors: Vec<(usize, usize, &str)> = errors
+            .iter()
+            .map(|e| (e.line(), e.col(), e.message()))
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                (3, 5, "Expected `;`, found `let`"),
+                (3, 13, "Expected an expression, found `;`"),
+                (6, 14, "Expected `:`, found `int`"),
+                (9, 5, "Expected `;`, found `func`"),
+                (9, 15, "Expected a pattern, found `{`"),
+                (14, 1, "Expected `}`, found `func`"),
+            ]
+        );
+
+        let kinds: Vec<&str> = module
+            .items
+            .iter()
+            .map(|item| match &item.kind {
+                ItemKind::FUNC(func) => func.name.name.as_str(),
+                ItemKind::CLASS(class) => class.name.name.as_str(),
+                ItemKind::ERROR => "error",
+                kind => panic!("unexpected item {:?}", kind),
+            })
+            .collect();
+        assert_eq!(kinds, ["f", "error", "A", "unclosed", "g"]);
+
+        let ItemKind::FUNC(f) = &module.items[0].kind else {
+            unreachable!()
+        };
+        let body = f.body.as_ref().unwrap();
+        assert!(body.stmts.iter().all(|s| s.kind == StmtKind::ERROR));
+        assert!(flat_sexpr(&module).contains("(tail (binary + (iden x) (iden y)))"));
+
+        let ItemKind::CLASS(class) = &module.items[2].kind else {
+            unreachable!()
+        };
+        let members: Vec<bool> = class
+            .members
+            .iter()
+            .map(|m| m.kind == MemberKind::ERROR)
+            .collect();
+        assert_eq!(members, [true, true, false]);
+
+        // without recovery, the first error is returned
+        let err = parser::parse(lex_str(src), Path::new("test.feo")).unwrap_err();
+        assert_eq!((err.line(), err.col()), (3, 5));
+
+        // recovery always makes progress, even when nothing fits
+        let (module, errors) =
+            parser::parse_with_recovery(lex_str("} ) func { let ; }"), Path::new("test.feo"));
+        assert!(!errors.is_empty());
+        assert!(module.items.iter().all(|i| i.kind == ItemKind::ERROR));
+
+        let pieces = [
+            "func", "class", "struct", "public", "let", "x", "=", "1", "+", ":", "int", ";", ",",
+            "(", ")", "{", "}", "if", "loop", "break", "match", "=>", "new", "type",
+        ];
+        let mut rng = Rng::new(0x5eed_0040);
+
+        for _ in 0..1000 {
+            let len = (rng.next() % 32) as usize;
+            let src: Vec<&str> = (0..len)
+                .map(|_| pieces[(rng.next() % pieces.len() as u64) as usize])
+                .collect();
+
+            // the parser must always finish, whatever it is given
+            let (_, errors) =
+                parser::parse_with_recovery(lex_str(&src.join(" ")), Path::new("random.feo"));
+            let result = parser::parse(lex_str(&src.join(" ")), Path::new("random.feo"));
+            assert_eq!(errors.is_empty(), result.is_ok(), "{:?}", src);
+        }
+    }
+
+    #[test]
+    fn parser_limits_nesting_depth() {
+        let path = Path::new("test.feo");
+        let nest = |open: &str, inner: &str, close: &str, n: usize| {
+            format!("{}{}{}", open.repeat(n), inner, close.repeat(n))
+        };
+
+        // deep enough to overflow the stack, were it not for the limit
+        let sources = [
+            format!("let x = {};", nest("(", "1", ")", 5000)),
+            format!("let x: {} = 1;", nest("[", "int", "]", 5000)),
+            format!("func f() {{ let {} = 1; }}", nest("(", "a", ",)", 5000)),
+            format!("func f() {{ {} }}", nest("{ ", "1", " }", 5000)),
+            format!("func f() {{ {} }}", nest("if a { ", "1", " }", 5000)),
+            // operators and `else if` nest what comes before them, however flat they look
+            format!("let x = {};", vec!["1"; 1000].join(" + ")),
+            format!("let x = a{};", ".b".repeat(100000)),
+            format!("let x = f{};", "()".repeat(100000)),
+            format!("let x = a{};", "[0]".repeat(100000)),
+            format!(
+                "func f() -> int {{ if a {{ 1 }}{} else {{ 1 }} }}",
+                " else if a { 1 }".repeat(1000)
+            ),
+        ];
+
+        for src in &sources {
+            let (_, errors) = parser::parse_with_recovery(lex_str(src), path);
+            let errors: Vec<_> = errors.iter().map(|e| (e.id(), e.message())).collect();
+
+            assert_eq!(
+                errors,
+                [(
+                    ErrorId::ERR_NESTING_TOO_DEEP,
+                    "Expressions, types and patterns cannot be nested more than 100 deep"
+                )]
+            );
+        }
+
+        // nesting within the limit is parsed, and checked, as usual
+        let sources = [
+            format!("let x: int = {};", nest("(", "1", ")", 90)),
+            format!("let x: int = {};", vec!["1"; 90].join(" + ")),
+            format!(
+                "func f(a: bool) -> int {{ if a {{ 1 }}{} else {{ 1 }} }}",
+                " else if a { 1 }".repeat(90)
+            ),
+        ];
+
+        for src in &sources {
+            let module = parser::parse(lex_str(src), path).unwrap();
+            let (resolution, errors) = sema::resolve(&module, path);
+            assert!(errors.is_empty(), "{:?}", errors);
+            assert!(sema::check(&module, &resolution, path).1.is_empty());
+        }
+    }
+
+    #[test]
+    fn syntax_trees_are_lossless() {
+        for n in 1..=8 {
+            let path: &'static Path = Box::leak(
+                std::path::PathBuf::from(format!("./examples/ex0{}.feo", n)).into_boxed_path(),
+            );
+            let file = std::fs::read_to_string(path).unwrap();
+            let tokens = lexer::lex(&file, path).unwrap();
+            let (module, _) = parser::parse_with_recovery(tokens.clone(), path);
+
+            let root = syntax::build(&file, &tokens, &module);
+            assert_eq!(root.to_string(), file, "{}", path.display());
+            assert_eq!(root.text_range(), 0..file.len());
+        }
+
+        let pieces = [
+            "func", "f", "(", ")", "{", "}", "let", "x", "=", "1", ";", ":", "int", "if", "class",
+            " ", "\n", "\t", "// c\n", "/* c */", "/*\n*/", "'é'", "\"ü\"",
+        ];
+        let mut rng = Rng::new(0x5eed_0041);
+
+        for _ in 0..1000 {
+            let len = (rng.next() % 32) as usize;
+            let src: String = (0..len)
+                .map(|_| pieces[(rng.next() % pieces.len() as u64) as usize])
+                .collect();
+
+            // even a broken file prints back exactly as it was
+            if let Ok(tokens) = lexer::lex(&src, Path::new("random.feo")) {
+                let (module, _) =
+                    parser::parse_with_recovery(tokens.clone(), Path::new("random.feo"));
+                let root = syntax::build(&src, &tokens, &module);
+                assert_eq!(root.to_string(), src);
+            }
+        }
+    }
+
+    #[test]
+    fn syntax_trees_support_views_and_edits() {
+        let path = Path::new("./examples/ex02.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+        let tokens = lexer::lex(&file, path).unwrap();
+        let module = parser::parse(tokens.clone(), path).unwrap();
+        let root = syntax::build(&file, &tokens, &module);
+
+        let source = SourceFile::cast(root.clone()).unwrap();
+        let funcs: Vec<view::FuncDecl> = source.funcs().collect();
+        let names: Vec<String> = funcs
+            .iter()
+            .map(|f| f.name().unwrap().text().to_string())
+            .collect();
+        assert_eq!(names, ["print", "add", "scale", "main"]);
+        assert!(funcs[0].body().is_none());
+
+        let add = &funcs[1];
+        let params: Vec<String> = add.params().map(|p| p.syntax().to_string()).collect();
+        assert_eq!(params, ["a: int", "b: int = 1"]);
+        assert_eq!(
+            add.params()
+                .nth(1)
+                .unwrap()
+                .default()
+                .unwrap()
+                .syntax()
+                .to_string(),
+            "1"
+        );
+        assert_eq!(add.ret().unwrap().syntax().to_string(), "int");
+
+        let body = funcs[2].body().unwrap();
+        let stmts: Vec<String> = body.stmts().map(|s| s.to_string()).collect();
+        assert_eq!(stmts, ["let result: float = value * factor;"]);
+        assert_eq!(body.tail().unwrap().syntax().to_string(), "result");
+
+        let decl = view::VarDecl::cast(body.stmts().next().unwrap()).unwrap();
+        assert_eq!(decl.value().unwrap().syntax().to_string(), "value * factor");
+        let range = decl.value().unwrap().syntax().text_range();
+        assert_eq!(&file[range], "value * factor");
+
+        // renaming `scale` only touches its name, and shares every other item with the
+        // original tree
+        let name = funcs[2].name().unwrap();
+        let renamed = name.replace_with(GreenToken::from_tok_type(TokType::IDEN(Symbol::intern(
+            "rescale",
+        ))));
+        assert_eq!(
+            renamed.to_string(),
+            file.replace("func scale(", "func rescale(")
+        );
+
+        let before: Vec<SyntaxNode> = root.children().collect();
+        let after: Vec<SyntaxNode> = renamed.children().collect();
+        for (i, (a, b)) in before.iter().zip(&after).enumerate() {
+            assert_eq!(Rc::ptr_eq(a.green(), b.green()), i != 2);
+        }
+        assert!(Rc::ptr_eq(
+            funcs[2].body().unwrap().syntax().green(),
+            view::FuncDecl::cast(after[2].clone())
+                .unwrap()
+                .body()
+                .unwrap()
+                .syntax()
+                .green()
+        ));
+
+        // replacing a whole node works the same way
+        let ret = add.ret().unwrap();
+        let float = GreenNode::new(
+            syntax::SyntaxKind::TYPE,
+            vec![GreenElement::TOKEN(Rc::new(GreenToken::from_tok_type(
+                TokType::KW_FLOAT,
+            )))],
+        );
+        let edited = ret.syntax().replace_with(float);
+        assert!(edited.to_string().contains("b: int = 1) -> float {"));
+        assert_eq!(edited.to_string().len(), file.len() + 2);
+    }
+
+    #[test]
+    fn parser_matches_golden_files() {
+        // set `FEO_BLESS=1` to (re)generate the expected `.sexpr` files
+        let bless = std::env::var_os("FEO_BLESS").is_some();
+        let dir = Path::new("./tests/golden/parser");
+
+        let mut inputs: Vec<_> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.extension().is_some_and(|e| e == "feo"))
+            .collect();
+        inputs.sort();
+
+        assert!(!inputs.is_empty(), "No golden files found in {:?}", dir);
+
+        for input in inputs {
+            let file = std::fs::read_to_string(&input).unwrap();
+            let path: &'static Path = Box::leak(input.clone().into_boxed_path());
+
+            let actual = match lexer::lex(&file, path) {
+                Ok(tokens) => {
+                    let (module, errors) = parser::parse_with_recovery(tokens, path);
+                    let errors: String = errors.iter().map(|e| format!("{}\n", e)).collect();
+
+                    ast::to_sexpr(&module, true) + &errors
+                }
+
+                Err(e) => format!("{}\n", e),
+            };
+
+            let expected_path = input.with_extension("sexpr");
+
+            if bless {
+                std::fs::write(&expected_path, &actual).unwrap();
+                continue;
+            }
+
+            let expected = std::fs::read_to_string(&expected_path)
+                .unwrap_or_else(|_| panic!("Missing golden file {:?}", expected_path));
+
+            assert_eq!(
+                expected, actual,
+                "Syntax tree dump mismatch for {:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn printer_round_trips() {
+        let mut files: Vec<_> = (1..=8)
+            .map(|n| std::path::PathBuf::from(format!("./examples/ex0{}.feo", n)))
+            .collect();
+        files.extend(
+            std::fs::read_dir("./tests/golden/parser")
+                .unwrap()
+                .map(|e| e.unwrap().path())
+                .filter(|p| p.extension().is_some_and(|e| e == "feo")),
+        );
+
+        for file in files {
+            let path: &'static Path = Box::leak(file.into_boxed_path());
+            let src = std::fs::read_to_string(path).unwrap();
+            let (module, errors) =
+                parser::parse_with_recovery(lexer::lex(&src, path).unwrap(), path);
+
+            // the printer skips what error recovery left behind, so only clean files round-trip
+            if !errors.is_empty() {
+                continue;
+            }
+
+            // the printed source parses back into the same tree, and prints the same again
+            let printed = ast::print(&module);
+            let reparsed = parser::parse(lexer::lex(&printed, path).unwrap(), path)
+                .unwrap_or_else(|e| panic!("{}\n{}", e, printed));
+
+            assert_eq!(
+                ast::to_sexpr(&module, false),
+                ast::to_sexpr(&reparsed, false),
+                "{}",
+                path.display()
+            );
+            assert_eq!(printed, ast::print(&reparsed), "{}", path.display());
+        }
+
+        let src = "func f(){let x=-1;let y=- 1;return (x+y)*2;}";
+        let module = parser::parse(lex_str(src), Path::new("test.feo")).unwrap();
+        assert_eq!(
+            ast::print(&module),
+            "func f() {\n    let x = -1;\n    let y = - 1;\n    return (x + y) * 2;\n}\n"
+        );
+    }
+
+    #[test]
+    fn ast_dumps_as_json() {
+        let src = "public func f(s: String) {\n    g(\"a\\\"b\");\n}";
+        let module = parser::parse(lex_str(src), Path::new("test.feo")).unwrap();
+
+        let span = |l1, c1, l2, c2| {
+            format!(
+                "\"span\":{{\"line\":{},\"col\":{},\"end_line\":{},\"end_col\":{}}}",
+                l1, c1, l2, c2
+            )
+        };
+
+        let expected = [
+            format!("{{\"kind\":\"module\",{},\"children\":[", span(1, 1, 3, 2)),
+            format!(
+                "{{\"kind\":\"func\",{},\"name\":\"f\",\"public\":true,\"children\":[",
+                span(1, 1, 3, 2)
+            ),
+            format!("{{\"kind\":\"param\",{},\"children\":[", span(1, 15, 1, 24)),
+            format!(
+                "{{\"kind\":\"binding\",{},\"name\":\"s\"}},",
+                span(1, 15, 1, 16)
+            ),
+            format!("{{\"kind\":\"String\",{}}}]}},", span(1, 18, 1, 24)),
+            format!("{{\"kind\":\"block\",{},\"children\":[", span(1, 26, 3, 2)),
+            format!("{{\"kind\":\"call\",{},\"children\":[", span(2, 5, 2, 14)),
+            format!("{{\"kind\":\"iden\",{},\"name\":\"g\"}},", span(2, 5, 2, 6)),
+            format!(
+                "{{\"kind\":\"literal\",{},\"value\":\"\\\"a\\\\\\\"b\\\"\"}}]}}]}}]}}]}}",
+                span(2, 7, 2, 13)
+            ),
+        ];
+
+        assert_eq!(ast::to_json(&module), expected.concat());
+    }
+
+    #[test]
+    fn ast_visitors_walk_the_tree() {
+        struct Names {
+            bindings: Vec<String>,
+            idens: Vec<String>,
+            types: usize,
+        }
+
+        impl ast::Visitor for Names {
+            fn visit_pattern(&mut self, pattern: &ast::Pattern) {
+                if let PatternKind::BINDING(name) = &pattern.kind {
+                    self.bindings.push(name.name.clone());
+                }
+            }
+
+            fn visit_expr(&mut self, expr: &ast::Expr) {
+                if let ExprKind::IDEN(name) = &expr.kind {
+                    self.idens.push(name.clone());
+                }
+
+                ast::visit::walk_expr(self, expr);
+            }
+
+            fn visit_type(&mut self, ty: &ast::Type) {
+                self.types += 1;
+                ast::visit::walk_type(self, ty);
+            }
+        }
+
+        let path = Path::new("./examples/ex02.feo");
+        let file = std::fs::read_to_string(path).unwrap();
+        let module = parser::parse(lexer::lex(&file, path).unwrap(), path).unwrap();
+
+        let mut names = Names {
+            bindings: Vec::new(),
+            idens: Vec::new(),
+            types: 0,
+        };
+        ast::Visitor::visit_module(&mut names, &module);
+
+        let expected = ["message", "a", "b", "value", "factor", "result", "total"];
+        assert_eq!(names.bindings, expected);
+
+        let expected = [
+            "a", "b", "value", "factor", "result", "add", "total", "add", "print",
+        ];
+        assert_eq!(names.idens, expected);
+        assert_eq!(names.types, 9);
+
+        // renaming in place
+        struct Rename;
+
+        impl ast::VisitorMut for Rename {
+            fn visit_expr_mut(&mut self, expr: &mut ast::Expr) {
+                if let ExprKind::IDEN(name) = &mut expr.kind {
+                    if name == "total" {
+                        *name = "sum".to_string();
+                    }
+                }
+
+                ast::visit_mut::walk_expr_mut(self, expr);
+            }
+
+            fn visit_pattern_mut(&mut self, pattern: &mut ast::Pattern) {
+                if let PatternKind::BINDING(name) = &mut pattern.kind {
+                    if name.name == "total" {
+                        name.name = "sum".to_string();
+                    }
+                }
+            }
+        }
+
+        let mut renamed = module.clone();
+        ast::VisitorMut::visit_module_mut(&mut Rename, &mut renamed);
+
+        let printed = ast::print(&renamed);
+        assert!(printed.contains("    var sum: int = add(1, 2);\n    sum += add(3);\n"));
+        assert!(!printed.contains("total"));
+
+        // folding integer additions, innermost first
+        struct ConstAdd;
+
+        impl ast::Fold for ConstAdd {
+            fn fold_expr(&mut self, expr: ast::Expr) -> ast::Expr {
+                let expr = ast::fold::fold_expr(self, expr);
+
+                match &expr.kind {
+                    ExprKind::BINARY {
+                        op: ast::BinaryOp::ADD,
+                        lhs,
+                        rhs,
+                    } => match (&lhs.kind, &rhs.kind) {
+                        (
+                            ExprKind::LITERAL(Literal::INT(a)),
+                            ExprKind::LITERAL(Literal::INT(b)),
+                        ) => ast::Expr {
+                            kind: ExprKind::LITERAL(Literal::INT(a + b)),
+                            span: expr.span,
+                        },
+
+                        _ => expr,
+                    },
+
+                    _ => expr,
+                }
+            }
+        }
+
+        let src = "func f() -> int { let x = [1 + 2 + 3; 4 + 5]; g(x + 1, 2 + 2) }";
+        let module = parser::parse(lex_str(src), Path::new("test.feo")).unwrap();
+        let folded = ast::Fold::fold_module(&mut ConstAdd, module);
+        assert_eq!(
+            ast::print(&folded),
+            "func f() -> int {\n    let x = [6; 9];\n    g(x + 1, 4)\n}\n"
+        );
+
+        // the default methods rebuild the tree as it was
+        struct Identity;
+        impl ast::Fold for Identity {}
+
+        for n in 1..=8 {
+            let path: &'static Path = Box::leak(
+                std::path::PathBuf::from(format!("./examples/ex0{}.feo", n)).into_boxed_path(),
+            );
+            let file = std::fs::read_to_string(path).unwrap();
+            let (module, _) = parser::parse_with_recovery(lexer::lex(&file, path).unwrap(), path);
+
+            assert_eq!(
+                ast::Fold::fold_module(&mut Identity, module.clone()),
+                module
+            );
+        }
+    }
+
+    #[test]
+    fn resolver_reports_undefined_and_duplicate_names() {
+        let src = "let baz: int = 1;\n\
+                   let foo: float = 0.1;\n\
+                   let bar: char = 'a';\n\
+                   const baz: bool = true;\n\
+                   const foo: String = \"Hello\";";
+        let path = Path::new("test.feo");
+        let module = parser::parse(lex_str(src), path).unwrap();
+        let (_, errors) = sema::resolve(&module, path);
+
+        let errors: Vec<_> = errors
+            .iter()
+            .map(|e| (e.id(), e.line(), e.col(), e.message(), e.notes()[0].span))
+            .collect();
+
+        let message = |name| format!("The name `{}` is defined more than once", name);
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_DUPLICATE_NAME,
+                    4,
+                    7,
+                    message("baz").as_str(),
+                    Span::new(1, 5, 1, 8)
+                ),
+                (
+                    ErrorId::ERR_DUPLICATE_NAME,
+                    5,
+                    7,
+                    message("foo").as_str(),
+                    Span::new(2, 5, 2, 8)
+                ),
+            ]
+        );
+
+        for n in 1..=8 {
+            let path: &'static Path = Box::leak(
+                std::path::PathBuf::from(format!("./examples/ex0{}.feo", n)).into_boxed_path(),
+            );
+            let file = std::fs::read_to_string(path).unwrap();
+            let module = parser::parse(lexer::lex(&file, path).unwrap(), path).unwrap();
+            let (_, errors) = sema::resolve(&module, path);
+
+            assert!(errors.is_empty(), "{}: {:?}", path.display(), errors);
+        }
+
+        let src = "func f(a: int, a: int) {
+    let (b, b) = (1, 2);
+    match a { (c, 1) | (1, d) => c, _ => e }
+    'outer: loop { continue 'outr; }
+    self.x;
+}
+struct S { x: int, x: float, func x() {} }
+class C { static func s() { self } func m() -> int { self.n } }";
+        let module = parser::parse(lex_str(src), Path::new("test.feo")).unwrap();
+        let (_, errors) = sema::resolve(&module, Path::new("test.feo"));
+
+        let errors: Vec<_> = errors
+            .iter()
+            .map(|e| (e.id(), e.line(), e.col(), e.message()))
+            .collect();
+
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_DUPLICATE_NAME,
+                    1,
+                    16,
+                    "The name `a` is defined more than once"
+                ),
+                (
+                    ErrorId::ERR_DUPLICATE_NAME,
+                    2,
+                    13,
+                    "The name `b` is defined more than once"
+                ),
+                (
+                    ErrorId::ERR_UNDEFINED_NAME,
+                    3,
+                    24,
+                    "`c` is not bound in every alternative of the pattern"
+                ),
+                (
+                    ErrorId::ERR_UNDEFINED_NAME,
+                    3,
+                    42,
+                    "Cannot find `e` in this scope"
+                ),
+                (
+                    ErrorId::ERR_UNDEFINED_LABEL,
+                    4,
+                    29,
+                    "Cannot find a loop labeled `'outr`"
+                ),
+                (
+                    ErrorId::ERR_INVALID_SELF,
+                    5,
+                    5,
+                    "`self` can only be used in constructors and non-static methods"
+                ),
+                (
+                    ErrorId::ERR_DUPLICATE_NAME,
+                    7,
+                    20,
+                    "The name `x` is defined more than once"
+                ),
+                (
+                    ErrorId::ERR_DUPLICATE_NAME,
+                    7,
+                    35,
+                    "The name `x` is defined more than once"
+                ),
+                (
+                    ErrorId::ERR_INVALID_SELF,
+                    8,
+                    29,
+                    "`self` can only be used in constructors and non-static methods"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolver_reports_break_and_continue_outside_loops() {
+        let src = "func f() { break; }
+func g() -> int { continue; }
+func h() { break 'outer 1; }
+func k() { loop { break; } while true { continue; } }
+let x: int = break;";
+        let module = parser::parse(lex_str(src), Path::new("test.feo")).unwrap();
+        let (_, errors) = sema::resolve(&module, Path::new("test.feo"));
+
+        let errors: Vec<_> = errors
+            .iter()
+            .map(|e| (e.id(), e.line(), e.col(), e.message()))
+            .collect();
+
+        let break_outside = "`break` can only be used inside a loop";
+        assert_eq!(
+            errors,
+            [
+                (ErrorId::ERR_OUTSIDE_LOOP, 1, 12, break_outside),
+                (
+                    ErrorId::ERR_OUTSIDE_LOOP,
+                    2,
+                    19,
+                    "`continue` can only be used inside a loop"
+                ),
+                (ErrorId::ERR_OUTSIDE_LOOP, 3, 12, break_outside),
+                (ErrorId::ERR_OUTSIDE_LOOP, 5, 14, break_outside),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolver_binds_names_to_declarations() {
+        let src = "const n: int = 10;
+func f(x: int) -> int {
+    let y = x + n;
+    let x = y * 2;
+    var total = x;
+    'outer: for n in [1, 2] {
+        total += n;
+        continue 'outer;
+    }
+    match total { n if n > 0 => n, _ => g() }
+}
+func g() -> int { n }";
+        let path = Path::new("test.feo");
+        let module = parser::parse(lex_str(src), path).unwrap();
+        let (resolution, errors) = sema::resolve(&module, path);
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        // the declaration each use (at line, col) refers to
+        let uses = [
+            ((3, 13), (2, 8), sema::SymbolKind::PARAM),
+            ((3, 17), (1, 7), sema::SymbolKind::VAR(BindingKind::CONST)),
+            ((4, 13), (3, 9), sema::SymbolKind::VAR(BindingKind::LET)),
+            ((5, 17), (4, 9), sema::SymbolKind::VAR(BindingKind::LET)),
+            ((7, 9), (5, 9), sema::SymbolKind::VAR(BindingKind::VAR)),
+            ((7, 18), (6, 17), sema::SymbolKind::VAR(BindingKind::LET)),
+            ((10, 11), (5, 9), sema::SymbolKind::VAR(BindingKind::VAR)),
+            ((10, 24), (10, 19), sema::SymbolKind::VAR(BindingKind::LET)),
+            ((10, 33), (10, 19), sema::SymbolKind::VAR(BindingKind::LET)),
+            ((10, 41), (12, 6), sema::SymbolKind::FUNC),
+            ((12, 19), (1, 7), sema::SymbolKind::VAR(BindingKind::CONST)),
+        ];
+
+        for ((line, col), (def_line, def_col), kind) in uses {
+            let (span, _) = resolution
+                .uses
+                .iter()
+                .find(|(s, _)| (s.line, s.col) == (line, col))
+                .unwrap_or_else(|| panic!("No use at {}:{}", line, col));
+            let symbol = resolution.use_at(*span).unwrap();
+
+            assert_eq!(
+                ((symbol.span.line, symbol.span.col), symbol.kind),
+                ((def_line, def_col), kind),
+                "{}:{}",
+                line,
+                col
+            );
+        }
+
+        assert_eq!(resolution.uses.len(), uses.len());
+    }
+
+    #[test]
+    fn checker_reports_mismatched_types() {
+        // every example is well typed
+        for n in 1..=8 {
+            let path: &'static Path = Box::leak(
+                std::path::PathBuf::from(format!("./examples/ex0{}.feo", n)).into_boxed_path(),
+            );
+            let file = std::fs::read_to_string(path).unwrap();
+            let module = parser::parse(lexer::lex(&file, path).unwrap(), path).unwrap();
+            let (resolution, errors) = sema::resolve(&module, path);
+            assert!(errors.is_empty(), "{}: {:?}", path.display(), errors);
+
+            let (_, errors) = sema::check(&module, &resolution, path);
+            assert!(errors.is_empty(), "{}: {:?}", path.display(), errors);
+        }
+
+        let src = "func sum(items: int) -> int {
+    var total: int = 0;
+    for i in items { total += i; }
+    total
+}
+func add(pair: int) -> int {
+    match pair { (0, _) => 0, (a, b) => a + b }
+}";
+        let checked = check_str(src);
+        let errors = checked.errors();
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    3,
+                    14,
+                    "Expected an array or a range, found `int`"
+                ),
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    7,
+                    18,
+                    "Expected `int`, found a tuple of 2 elements"
+                ),
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    7,
+                    31,
+                    "Expected `int`, found a tuple of 2 elements"
+                ),
+            ]
+        );
+
+        let src = "struct Point { x: float, y: float }
+enum Shape { Dot, Circle(Point, float) }
+class Animal { name: String; func speak() -> String { self.name } }
+class Dog: Animal { func fetch() {} }
+let count: int = \"many\";
+func area(shape: Shape) -> float {
+    match shape { Shape.Dot => 0, Shape.Circle(_, r) => r * r }
+}
+func scale(p: Point, by: float = 2.0) -> Point {
+    let q = Point { x: p.x * by };
+    let flag: bool = p.x + true;
+    let a: Animal = new Dog();
+    a.fetch();
+    scale(p, 1.0, 2.0);
+    let xs: [uint; 3] = [1, 2, 3];
+    q
+}
+func name(dog: Dog) -> int {
+    if dog.speak() == \"Rex\" { return \"Rex\"; }
+    dog.speak()
+}";
+        let checked = check_str(src);
+        let Checked {
+            resolution, types, ..
+        } = &checked;
+        let errors: Vec<_> = checked
+            .errors
+            .iter()
+            .map(|e| {
+                let notes: Vec<_> = e.notes().iter().map(|n| n.span).collect();
+                (e.id(), e.line(), e.col(), e.message(), notes)
+            })
+            .collect();
+
+        let annotation = vec![Span::new(5, 12, 5, 15)];
+        let ret = vec![Span::new(18, 24, 18, 27)];
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    5,
+                    18,
+                    "Expected `int`, found `String`",
+                    annotation
+                ),
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    7,
+                    57,
+                    "Expected `int`, found `float`",
+                    vec![Span::new(7, 32, 7, 33)]
+                ),
+                (
+                    ErrorId::ERR_MISSING_FIELD,
+                    10,
+                    13,
+                    "Missing field `y` in `Point`",
+                    vec![]
+                ),
+                (
+                    ErrorId::ERR_INVALID_OPERANDS,
+                    11,
+                    22,
+                    "Cannot apply `+` to `float` and `bool`",
+                    vec![]
+                ),
+                (
+                    ErrorId::ERR_UNDEFINED_MEMBER,
+                    13,
+                    7,
+                    "No field or method `fetch` on type `Animal`",
+                    vec![]
+                ),
+                (
+                    ErrorId::ERR_ARG_COUNT,
+                    14,
+                    5,
+                    "Expected 1 to 2 arguments, found 3",
+                    vec![]
+                ),
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    19,
+                    38,
+                    "Expected `int`, found `String`",
+                    ret.clone()
+                ),
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    20,
+                    5,
+                    "Expected `int`, found `String`",
+                    ret
+                ),
+            ]
+        );
+
+        // bindings without an annotation take the type of their value
+        let (_, q) = resolution
+            .defs
+            .iter()
+            .find(|(s, _)| (s.line, s.col) == (10, 9))
+            .unwrap();
+        assert_eq!(types.symbols[q].to_string(), "Point");
+        assert_eq!(
+            types.exprs[&Span::new(15, 25, 15, 34)].to_string(),
+            "[uint; 3]"
+        );
+    }
+
+    #[test]
+    fn checker_infers_local_types() {
+        let src = "struct Pair<A, B> { first: A, second: B }
+func id<T>(value: T) -> T { value }
+func swap<A, B>(pair: Pair<A, B>) -> Pair<B, A> { Pair { first: pair.second, second: pair.first } }
+func f(n: uint) -> uint {
+    let x = 1;
+    var total;
+    total = x + n;
+    let p = Pair { first: 1, second: \"one\" };
+    let q = swap(p);
+    let s: String = q.first;
+    let c = id(2.5);
+    let bad: int = id(c);
+    let d = 3;
+    let e = [];
+    var g;
+    let (a, b) = ([1, 2], 'c');
+    total
+}";
+        let checked = check_str(src);
+        let Checked {
+            resolution, types, ..
+        } = &checked;
+        let errors = checked.errors();
+
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    12,
+                    23,
+                    "Expected `int`, found `float`"
+                ),
+                (
+                    ErrorId::ERR_INVALID_TYPE,
+                    14,
+                    9,
+                    "Cannot infer the type `[_; 0]`; add a type annotation"
+                ),
+                (
+                    ErrorId::ERR_INVALID_TYPE,
+                    15,
+                    9,
+                    "Cannot infer the type `_`; add a type annotation"
+                ),
+            ]
+        );
+
+        // the type inferred for the binding declared at (line, col)
+        let inferred = [
+            ((5, 9), "uint"),
+            ((6, 9), "uint"),
+            ((8, 9), "Pair<int, String>"),
+            ((9, 9), "Pair<String, int>"),
+            ((11, 9), "float"),
+            ((13, 9), "int"),
+            ((14, 9), "[{unknown}; 0]"),
+            ((16, 10), "[int; 2]"),
+            ((16, 13), "char"),
+        ];
+
+        for ((line, col), ty) in inferred {
+            let (_, symbol) = resolution
+                .defs
+                .iter()
+                .find(|(s, _)| (s.line, s.col) == (line, col))
+                .unwrap();
+
+            assert_eq!(types.symbols[symbol].to_string(), ty, "{}:{}", line, col);
+        }
+    }
+
+    #[test]
+    fn checker_enforces_generic_bounds() {
+        let src = "protocol Shape { func area() -> float; }
+struct Square: Shape { side: float, func area() -> float { self.side * self.side } }
+struct Circle { radius: float }
+struct Box<T: Shape> { value: T }
+func largest<T: Shape>(shapes: [T]) -> T { shapes[0] }
+func wrap<U: Shape>(value: U) -> Box<U> { Box { value } }
+func f(b: Box<int>) -> Box<Square> {
+    let a = largest([Square { side: 1.0 }]);
+    let c = largest([Circle { radius: 1.0 }]);
+    let d: Box<Circle> = Box { value: Circle { radius: 1.0 } };
+    let e = Box { value: 1 };
+    let g = wrap(a);
+    wrap(a)
+}";
+        let checked = check_str(src);
+        let errors: Vec<_> = checked
+            .errors
+            .iter()
+            .map(|e| {
+                let note = e.notes()[0].span;
+                (e.id(), e.line(), e.col(), e.message().to_string(), note)
+            })
+            .collect();
+
+        // the note points at the generic parameter whose bound is not met
+        let (box_t, largest_t) = (Span::new(4, 12, 4, 13), Span::new(5, 14, 5, 15));
+        let unsatisfied = |line, col, ty, note| {
+            let message = format!(
+                "The type `{}` does not conform to `Shape`, which `T` requires",
+                ty
+            );
+            (ErrorId::ERR_UNSATISFIED_BOUND, line, col, message, note)
+        };
+        assert_eq!(
+            errors,
+            [
+                unsatisfied(7, 11, "int", box_t),
+                unsatisfied(9, 13, "Circle", largest_t),
+                unsatisfied(10, 12, "Circle", box_t),
+                unsatisfied(11, 13, "int", box_t),
+            ]
+        );
+    }
+
+    #[test]
+    fn checker_rejects_negative_uint_literals() {
+        let src = "func f(n: uint) -> uint {
+    let a: uint = -1;
+    let b: uint? = -2;
+    let c = -3;
+    let d: uint = c;
+    let e = -(4);
+    let sum = e + n;
+    let ok = 5;
+    ok + n
+}";
+        let checked = check_str(src);
+        let Checked {
+            resolution, types, ..
+        } = &checked;
+        let errors = checked.errors();
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    2,
+                    19,
+                    "Expected `uint`, found `int`"
+                ),
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    3,
+                    20,
+                    "Expected `uint?`, found `int`"
+                ),
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    5,
+                    19,
+                    "Expected `uint`, found `int`"
+                ),
+                (
+                    ErrorId::ERR_INVALID_OPERANDS,
+                    7,
+                    15,
+                    "Cannot apply `+` to `int` and `uint`"
+                ),
+            ]
+        );
+
+        // a literal that is not negative still becomes a `uint` when one is called for
+        let (_, ok) = resolution
+            .defs
+            .iter()
+            .find(|(s, _)| (s.line, s.col) == (8, 9))
+            .unwrap();
+        assert_eq!(types.symbols[ok].to_string(), "uint");
+    }
+
+    #[test]
+    fn checker_checks_ranges() {
+        let src = "func f(n: uint) -> uint {
+    var total = 0;
+    for i in 0..n { total += i; }
+    for c in 'a'..='z' { }
+    let r = 1..10;
+    for x in 1.0..2.0 { }
+    for y in 0..'z' { }
+    let s: [int] = 0..3;
+    total
+}";
+        let checked = check_str(src);
+        let Checked {
+            resolution, types, ..
+        } = &checked;
+        let errors = checked.errors();
+
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_INVALID_OPERANDS,
+                    6,
+                    14,
+                    "Expected a range of integers or characters, found a range of `float`"
+                ),
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    7,
+                    17,
+                    "Expected `int`, found `char`"
+                ),
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    8,
+                    20,
+                    "Expected `[int]`, found `Range<int>`"
+                ),
+            ]
+        );
+
+        // the type inferred for the binding declared at (line, col)
+        let inferred = [
+            ((2, 9), "uint"),
+            ((3, 9), "uint"),
+            ((4, 9), "char"),
+            ((5, 9), "Range<int>"),
+        ];
+
+        for ((line, col), ty) in inferred {
+            let (_, symbol) = resolution
+                .defs
+                .iter()
+                .find(|(s, _)| (s.line, s.col) == (line, col))
+                .unwrap();
+
+            assert_eq!(types.symbols[symbol].to_string(), ty, "{}:{}", line, col);
+        }
+    }
+
+    #[test]
+    fn checker_enforces_binding_mutability() {
+        let src = "struct Point { x: float, y: float }
+class Counter { count: int; static total: int = 0; }
+const LIMIT: int = 10 * 2;
+const ORIGIN: Point = Point { x: 0.0, y: LIMIT };
+const BAD: int = twice(2);
+func twice(n: int) -> int {
+    n = n * 2;
+    let total = 0;
+    total += n;
+    var ok = 0;
+    ok += 1;
+    let p = Point { x: 1.0, y: 2.0 };
+    p.x = 3.0;
+    let k = new Counter();
+    k.count += 1;
+    Counter.total = 1;
+    LIMIT = 3;
+    for i in [1, 2] { i = 1; }
+    total
+}";
+        let checked = check_str(src);
+        let errors: Vec<_> = checked
+            .errors
+            .iter()
+            .map(|e| {
+                let fixes: Vec<_> = e
+                    .fixes()
+                    .iter()
+                    .map(|f| (f.span, f.replacement.as_str()))
+                    .collect();
+                (e.id(), e.line(), e.col(), e.message(), fixes)
+            })
+            .collect();
+
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    4,
+                    42,
+                    "Expected `float`, found `int`",
+                    vec![]
+                ),
+                (
+                    ErrorId::ERR_NOT_CONST,
+                    5,
+                    18,
+                    "The call to `twice` cannot be evaluated at compile time",
+                    vec![]
+                ),
+                (
+                    ErrorId::ERR_ASSIGN_IMMUTABLE,
+                    7,
+                    5,
+                    "Cannot assign to `n`, which is a parameter",
+                    vec![]
+                ),
+                (
+                    ErrorId::ERR_ASSIGN_IMMUTABLE,
+                    9,
+                    5,
+                    "Cannot apply `+=` to `total`, which is immutable",
+                    vec![(Span::new(8, 5, 8, 8), "var")]
+                ),
+                (
+                    ErrorId::ERR_ASSIGN_IMMUTABLE,
+                    13,
+                    5,
+                    "Cannot assign to a part of `p`, which is immutable",
+                    vec![(Span::new(12, 5, 12, 8), "var")]
+                ),
+                (
+                    ErrorId::ERR_ASSIGN_IMMUTABLE,
+                    17,
+                    5,
+                    "Cannot assign to `LIMIT`, which is a constant",
+                    vec![]
+                ),
+                (
+                    ErrorId::ERR_ASSIGN_IMMUTABLE,
+                    18,
+                    23,
+                    "Cannot assign to `i`, which is immutable",
+                    vec![]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn checker_evaluates_constants() {
+        let src = r#"enum Color { Red, Green, Blue }
+struct Grid { cells: [int; SIZE * SIZE] }
+const SIZE: int = HALF * 2;
+const HALF: int = 3;
+const BIG: uint = 2000000000 * 2;
+const GREETING: String = "Hello, " + "world";
+const SMALL: bool = SIZE < 10 && GREETING != "";
+const FIB: int = fib(10);
+const PICK: Color = pick(2);
+const (P, Q): (int, uint) = (1, 2);
+const NEG: uint = 1 - 2;
+const MAX: int = 2147483647 + 1;
+const ZERO: int = 10 / (HALF - 3);
+const OUT: int = [1, 2, 3][3];
+const CYCLE: int = CYCLE + 1;
+var counter: int = 0;
+const COUNT: int = counter + 1;
+const IMPURE: int = bump();
+func fib(n: int) -> int {
+    if n < 2 { return n; }
+    let a = fib(n - 1);
+    a + fib(n - 2)
+}
+func pick(i: int) -> Color {
+    match i { 0 => Color.Red, 1 => Color.Green, _ => Color.Blue }
+}
+func bump() -> int { var x = 1; x }
+class Counter { static total: int = SIZE * 2; }
+func main() {
+    let n = 4;
+    let a: [int] = [0; n];
+    let b: [int; 6] = [0; SIZE];
+    let c: [int; counter] = [];
+}
+"#;
+        let checked = check_str(src);
+        let Checked {
+            resolution, types, ..
+        } = &checked;
+        let errors = checked.errors();
+
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_OVERFLOW,
+                    11,
+                    19,
+                    "The result of `1 - 2` does not fit in `uint`"
+                ),
+                (
+                    ErrorId::ERR_OVERFLOW,
+                    12,
+                    18,
+                    "The result of `2147483647 + 1` does not fit in `int`"
+                ),
+                (
+                    ErrorId::ERR_DIVISION_BY_ZERO,
+                    13,
+                    19,
+                    "Cannot divide `10` by zero"
+                ),
+                (
+                    ErrorId::ERR_OUT_OF_BOUNDS,
+                    14,
+                    18,
+                    "The index `3` is out of bounds for an array of 3 elements"
+                ),
+                (
+                    ErrorId::ERR_NOT_CONST,
+                    15,
+                    20,
+                    "The value of `CYCLE` depends on itself"
+                ),
+                (
+                    ErrorId::ERR_NOT_CONST,
+                    17,
+                    20,
+                    "This expression cannot be evaluated at compile time"
+                ),
+                (
+                    ErrorId::ERR_NOT_CONST,
+                    18,
+                    21,
+                    "The call to `bump` cannot be evaluated at compile time"
+                ),
+                (
+                    ErrorId::ERR_NOT_CONST,
+                    33,
+                    18,
+                    "This expression cannot be evaluated at compile time"
+                ),
+            ]
+        );
+
+        let value = |name: &str| -> Option<String> {
+            let (symbol, _) = types
+                .consts
+                .iter()
+                .find(|(s, _)| resolution.symbol(**s).name == name)?;
+            Some(types.consts[symbol].to_string())
+        };
+
+        assert_eq!(value("SIZE").as_deref(), Some("6"));
+        assert_eq!(value("BIG").as_deref(), Some("4000000000"));
+        assert_eq!(value("GREETING").as_deref(), Some("\"Hello, world\""));
+        assert_eq!(value("SMALL").as_deref(), Some("true"));
+        assert_eq!(value("FIB").as_deref(), Some("55"));
+        assert_eq!(value("PICK").as_deref(), Some("Color.Blue"));
+        assert_eq!(value("Q").as_deref(), Some("2"));
+        assert_eq!(value("MAX"), None);
+        assert_eq!(value("IMPURE"), None);
+
+        let statics: Vec<String> = types.statics.values().map(|v| v.to_string()).collect();
+        assert_eq!(statics, ["12"]);
+
+        // the length of an array type may be any constant expression
+        let grid = resolution
+            .defs
+            .values()
+            .find(|s| resolution.symbol(**s).name == "Grid");
+        let cells = &types.defs[grid.unwrap()].fields[0].ty;
+        assert_eq!(cells.to_string(), "[int; 36]");
+    }
+
+    #[test]
+    fn checker_limits_compile_time_evaluation() {
+        let src = r#"const GRID: [[int]] = [[0; 50000]; 50000];
+const LONG: String = grow("ab", 40);
+const SLOW: int = fib(40);
+const FAST: int = fib(15);
+func grow(s: String, n: int) -> String { n == 0 ? s : grow(s + s, n - 1) }
+func fib(n: int) -> int { n < 2 ? n : fib(n - 1) + fib(n - 2) }
+"#;
+        let checked = check_str(src);
+        let errors = checked.errors();
+
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_NOT_CONST,
+                    1,
+                    23,
+                    "This value is too large to build at compile time"
+                ),
+                (
+                    ErrorId::ERR_NOT_CONST,
+                    5,
+                    60,
+                    "This value is too large to build at compile time"
+                ),
+                (
+                    ErrorId::ERR_NOT_CONST,
+                    3,
+                    19,
+                    "This expression takes too long to evaluate at compile time"
+                ),
+            ]
+        );
+
+        // the string outgrows the budget while being built, several calls deep
+        assert!(checked.errors[1].notes().len() > 1);
+
+        let fast = checked.types.consts.values().map(|v| v.to_string());
+        assert_eq!(fast.collect::<Vec<_>>(), ["610"]);
+    }
+
+    #[test]
+    fn checker_checks_class_hierarchies() {
+        let src = "abstract class Animal {
+    name: String;
+    new(name: String) { self.name = name; }
+    abstract func speak() -> String;
+    virtual func describe() -> String { self.name }
+    virtual func sleep() {}
+    final virtual func id() -> int { 0 }
+}
+final class Dog: Animal {
+    new() { super(\"dog\"); }
+    override func speak() -> String { \"Woof\" }
+    override func describe() -> String { super.describe() }
+}
+class Cat: Animal {
+    override func speak() -> int { 1 }
+    func sleep() {}
+    override func id() -> int { 1 }
+    override func purr() {}
+}
+class Fish: Animal {}
+class Puppy: Dog {}
+class Rock {
+    abstract func roll();
+    func weigh() -> int { super.weigh() }
+}
+class A: B {}
+class B: C {}
+class C: A {}
+class Loop: Loop {}
+func main() {
+    let a = new Animal(\"cat\");
+    let d = new Dog();
+}";
+        let checked = check_str(src);
+        let errors = checked.errors();
+
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_INHERITANCE_CYCLE,
+                    26,
+                    10,
+                    "The class `A` inherits from itself (`A` -> `B` -> `C` -> `A`)"
+                ),
+                (
+                    ErrorId::ERR_INHERITANCE_CYCLE,
+                    29,
+                    13,
+                    "The class `Loop` extends itself"
+                ),
+                (
+                    ErrorId::ERR_INVALID_OVERRIDE,
+                    15,
+                    19,
+                    "The method `speak` does not match the method it overrides: expected \
+                     `func() -> String`, found `func() -> int`"
+                ),
+                (
+                    ErrorId::ERR_INVALID_OVERRIDE,
+                    16,
+                    10,
+                    "The method `sleep` overrides a method of `Animal`, and must be marked \
+                     `override`"
+                ),
+                (
+                    ErrorId::ERR_INVALID_OVERRIDE,
+                    17,
+                    19,
+                    "Cannot override `id`, which is `final` in `Animal`"
+                ),
+                (
+                    ErrorId::ERR_INVALID_OVERRIDE,
+                    18,
+                    19,
+                    "The method `purr` is marked `override`, but no superclass of `Cat` has a \
+                     method `purr`"
+                ),
+                (
+                    ErrorId::ERR_MISSING_METHOD,
+                    20,
+                    7,
+                    "The class `Fish` must implement the abstract method `speak` of `Animal`"
+                ),
+                (
+                    ErrorId::ERR_INVALID_SUPERCLASS,
+                    21,
+                    14,
+                    "Cannot extend `Dog`, which is `final`"
+                ),
+                (
+                    ErrorId::ERR_INVALID_ABSTRACT,
+                    23,
+                    19,
+                    "The method `roll` is `abstract`, so the class `Rock` must be too"
+                ),
+                (
+                    ErrorId::ERR_INVALID_SUPER,
+                    24,
+                    27,
+                    "`super` can only be used in the constructors and methods of a class that \
+                     extends another"
+                ),
+                (
+                    ErrorId::ERR_INVALID_ABSTRACT,
+                    31,
+                    17,
+                    "Cannot create an instance of `Animal`, which is `abstract`"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn checker_reports_shadowed_members() {
+        let src = "class A {
+    x: int;
+    static count: int = 0;
+    virtual func speak() {}
+    func sleep() {}
+    static func make() {}
+}
+class B: A {
+    static func speak() {}
+    x: int;
+    sleep: bool;
+    func x2() {}
+    static count: int = 1;
+    static func make() {}
+}
+class C: A {
+    func x() {}
+    override func sleep() {}
+}";
+        let checked = check_str(src);
+        let errors: Vec<_> = checked
+            .errors
+            .iter()
+            .map(|e| {
+                let notes: Vec<_> = e.notes().iter().map(|n| n.span).collect();
+                (e.id(), e.line(), e.col(), e.message(), notes)
+            })
+            .collect();
+
+        // static members only hide the static members of a superclass
+        let (x, sleep) = (Span::new(2, 5, 2, 6), Span::new(5, 10, 5, 15));
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_DUPLICATE_NAME,
+                    9,
+                    17,
+                    "The static method `speak` shadows the method `speak` inherited from `A`",
+                    vec![Span::new(4, 18, 4, 23)]
+                ),
+                (
+                    ErrorId::ERR_DUPLICATE_NAME,
+                    10,
+                    5,
+                    "The field `x` shadows the field `x` inherited from `A`",
+                    vec![x]
+                ),
+                (
+                    ErrorId::ERR_DUPLICATE_NAME,
+                    11,
+                    5,
+                    "The field `sleep` shadows the method `sleep` inherited from `A`",
+                    vec![sleep]
+                ),
+                // marking the method `override` does not make the inherited one `virtual`
+                (
+                    ErrorId::ERR_INVALID_OVERRIDE,
+                    18,
+                    19,
+                    "Cannot override `sleep`, which is not `virtual` in `A`",
+                    vec![sleep]
+                ),
+                (
+                    ErrorId::ERR_DUPLICATE_NAME,
+                    17,
+                    10,
+                    "The method `x` shadows the field `x` inherited from `A`",
+                    vec![x]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn checker_checks_protocol_conformance() {
+        let src = "protocol Printable {
+    type Output: Printable;
+    func print() -> String;
+    func describe() -> String { self.print() }
+}
+protocol Named {
+    func name() -> String;
+    func describe() -> String { self.name() }
+}
+protocol Shape {
+    func area(scale: float) -> float;
+}
+struct Point: Printable {
+    type Output = Point;
+    func print() -> String { \"Point\" }
+}
+struct Box: Printable, Shape {
+    type Output = int;
+    func area(scale: float) -> int { 1 }
+}
+enum Tag: Printable, Named {
+    Red,
+    type Output = Tag;
+    func print() -> String { \"Tag\" }
+    func name() -> String { \"tag\" }
+}
+class Animal {
+    func print() -> String { \"Animal\" }
+}
+class Dog: Animal, Printable {
+    type Output = Dog;
+}
+func show(p: Printable) -> String {
+    p.describe()
+}
+func main() {
+    let s: String = show(Point {}) + Point {}.describe() + show(new Dog());
+}";
+        let checked = check_str(src);
+        let Checked {
+            module,
+            resolution,
+            types,
+            ..
+        } = &checked;
+        let errors = checked.errors();
+
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    18,
+                    19,
+                    "The type `Output` of `Box` must conform to `Printable`, found `int`"
+                ),
+                (
+                    ErrorId::ERR_MISSING_METHOD,
+                    17,
+                    8,
+                    "The type `Box` must implement the method `print` required by `Printable`"
+                ),
+                (
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    19,
+                    10,
+                    "The method `area` of `Box` does not match the one `Shape` requires: \
+                     expected `func(float) -> float`, found `func(float) -> int`"
+                ),
+                (
+                    ErrorId::ERR_AMBIGUOUS_METHOD,
+                    21,
+                    6,
+                    "The method `describe` has a default implementation in both `Printable` \
+                     and `Named`, so `Tag` must implement it"
+                ),
+            ]
+        );
+
+        // `Point` gets the default `describe`, and `Dog` its superclass's `print`
+        let symbol = |name: &str| {
+            let decl = module.items.iter().find_map(|item| match &item.kind {
+                ItemKind::STRUCT(decl) if decl.name.name == name => Some(&decl.name),
+                ItemKind::CLASS(decl) if decl.name.name == name => Some(&decl.name),
+                ItemKind::PROTOCOL(decl) if decl.name.name == name => Some(&decl.name),
+                _ => None,
+            });
+            resolution.defs[&decl.unwrap().span]
+        };
+        let (point, dog, animal) = (symbol("Point"), symbol("Dog"), symbol("Animal"));
+        let (printable, named) = (symbol("Printable"), symbol("Named"));
+
+        assert!(types.defs[&point].method("describe").is_some());
+        assert_eq!(
+            types.conformances[&(point, printable)],
+            [
+                ("print".to_string(), point),
+                ("describe".to_string(), printable)
+            ]
+        );
+        assert_eq!(
+            types.conformances[&(dog, printable)],
+            [
+                ("print".to_string(), animal),
+                ("describe".to_string(), printable)
+            ]
+        );
+
+        // calls through a protocol are dispatched through its witness tables
+        let calls: Vec<_> = types.dispatch.iter().map(|(s, d)| (s.line, *d)).collect();
+        assert!(calls.contains(&(4, (printable, 0))));
+        assert!(calls.contains(&(8, (named, 0))));
+        assert!(calls.contains(&(34, (printable, 1))));
+        assert_eq!(calls.len(), 3);
+    }
+
+    #[test]
+    fn checker_checks_bounds_against_declared_conformances() {
+        // a bound is only met by a type that both declares and implements the protocol
+        let src = "protocol Shape { func area() -> float; }
+struct Square: Shape { side: float }
+struct Disc { radius: float, func area() -> float { self.radius } }
+class Base: Shape { func area() -> float { 1.0 } }
+class Derived: Base { }
+func largest<T: Shape>(shapes: [T]) -> T { shapes[0] }
+func f(s: Square, d: Disc, x: Derived) {
+    largest([s]);
+    largest([d]);
+    largest([x]);
+}";
+        let checked = check_str(src);
+        let errors = checked.errors();
+        assert_eq!(
+            errors,
+            [
+                (
+                    ErrorId::ERR_MISSING_METHOD,
+                    2,
+                    8,
+                    "The type `Square` must implement the method `area` required by `Shape`"
+                ),
+                (
+                    ErrorId::ERR_UNSATISFIED_BOUND,
+                    8,
+                    5,
+                    "The type `Square` does not conform to `Shape`, which `T` requires"
+                ),
+                (
+                    ErrorId::ERR_UNSATISFIED_BOUND,
+                    9,
+                    5,
+                    "The type `Disc` does not conform to `Shape`, which `T` requires"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn checker_checks_conformances_after_superclasses() {
+        // `B` takes `d` from its superclass, which takes it from `P`, however the two
+        // classes are ordered
+        let src = "protocol P { func d() -> int { 1 } }
+protocol Q { func d() -> int; }
+class B: A, Q { }
+class A: P { }
+struct S: P, P { }
+func f(b: B) -> int { b.d() }";
+        let checked = check_str(src);
+        let errors: Vec<_> = checked
+            .errors
+            .iter()
+            .map(|e| {
+                let notes: Vec<_> = e.notes().iter().map(|n| n.span).collect();
+                (e.id(), e.line(), e.col(), e.message(), notes)
+            })
+            .collect();
+
+        assert_eq!(
+            errors,
+            [(
+                ErrorId::ERR_DUPLICATE_CONFORMANCE,
+                5,
+                14,
+                "`P` is listed more than once",
+                vec![Span::new(5, 11, 5, 12)]
+            )]
+        );
+    }
+
+    #[test]
+    fn resolver_binds_renamed_import_groups() {
+        let path = Path::new("test.feo");
+        let resolve = |src: &str| {
+            let module = parser::parse(lex_str(src), path).unwrap();
+            let (_, errors) = sema::resolve(&module, path);
+
+            errors
+                .iter()
+                .map(|e| (e.line(), e.col(), e.message().to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        let errors = resolve(
+            "import geometry.{Point, shapes.Circle as Round} as geo;\n\
+             func f(p: geo.Point) -> geo.Round { return geo.Round(p, geo.PI); }\n\
+             func g() { Point(); }",
+        );
+        assert_eq!(
+            errors,
+            [
+                (2, 61, "Cannot find `PI` in `geo`".to_string()),
+                (3, 12, "Cannot find `Point` in this scope".to_string()),
+            ]
+        );
+
+        let errors = resolve("import a.{b, c.b} as d;");
+        assert_eq!(
+            errors,
+            [(1, 16, "The name `b` is defined more than once".to_string())]
+        );
+    }
+
+    #[test]
+    fn modules_load_in_dependency_order() {
+        let mut resolver = ModuleResolver::new("./examples/project");
+        resolver.add_lib("geometry", "./examples/project/geometry");
+
+        assert!(resolver.resolve(&["util"]).is_some());
+        assert!(resolver
+            .resolve(&["geometry"])
+            .unwrap()
+            .ends_with("lib.feo"));
+        assert!(resolver.resolve(&["geometry", "shapes"]).is_some());
+        assert!(resolver.resolve(&["geometry", "PI"]).is_none());
+
+        let program =
+            module::load(Path::new("./examples/project/main.feo"), &mut resolver).unwrap();
+        let names: Vec<&str> = program.modules.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["geometry.shapes", "geometry", "util", "main"]);
+
+        // loading a file again reuses its path rather than leaking another
+        let again = module::load(Path::new("./examples/project/main.feo"), &mut resolver).unwrap();
+        for (first, second) in program.modules.iter().zip(&again.modules) {
+            assert!(std::ptr::eq(first.path, second.path));
+        }
+
+        // a library root can also be the entry module
+        let mut resolver = ModuleResolver::new("./examples/project");
+        let program = module::load(
+            Path::new("./examples/project/geometry/lib.feo"),
+            &mut resolver,
+        )
+        .unwrap();
+        let names: Vec<&str> = program.modules.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["geometry.shapes", "geometry"]);
+    }
+
+    #[test]
+    fn modules_report_cycles_and_bad_imports() {
+        let mut resolver = ModuleResolver::new("./examples/cycle");
+        let err = module::load(Path::new("./examples/cycle/a.feo"), &mut resolver).unwrap_err();
+        assert_eq!(err.id(), ErrorId::ERR_IMPORT_CYCLE);
+        assert!(err.file().ends_with("c.feo"));
+        assert_eq!((err.line(), err.col()), (1, 8));
+        assert_eq!(err.message(), "`a` imports itself (a -> b -> c -> a)");
+
+        let dir = std::env::temp_dir().join(format!("feolang-modules-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("lib")).unwrap();
+
+        let load = |files: &[(&str, &str)]| {
+            for (name, source) in files {
+                std::fs::write(dir.join(name), source).unwrap();
+            }
+
+            let mut resolver = ModuleResolver::new(&dir);
+            resolver.add_lib("mylib", dir.join("lib"));
+            module::load(&dir.join(files[0].0), &mut resolver).unwrap_err()
+        };
+
+        let err = load(&[("main.feo", "import missing.thing;")]);
+        assert_eq!(err.id(), ErrorId::ERR_UNRESOLVED_IMPORT);
+        assert_eq!(err.message(), "Cannot find a module for `missing.thing`");
+
+        let err = load(&[("main.feo", "import mylib;"), ("lib/lib.feo", "lib other;")]);
+        assert_eq!(err.id(), ErrorId::ERR_INVALID_LIB);
+        assert_eq!(err.message(), "Expected `lib mylib;`, found `lib other;`");
+
+        let err = load(&[
+            ("main.feo", "import mylib;"),
+            ("lib/lib.feo", "func f() { }"),
+        ]);
+        assert_eq!(err.id(), ErrorId::ERR_INVALID_LIB);
+
+        let err = load(&[("main.feo", "func f() { }\nlib main;")]);
+        assert_eq!((err.id(), err.line()), (ErrorId::ERR_INVALID_LIB, 2));
+
+        let err = load(&[
+            ("main.feo", "import self_import;"),
+            ("self_import.feo", "import main;"),
+        ]);
+        assert_eq!(err.id(), ErrorId::ERR_IMPORT_CYCLE);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parser_reports_unexpected_tokens() {
+        let path = Path::new("test.feo");
+        let parse = |src: &str| parser::parse(lex_str(src), path);
+
+        let err = parse("let x: int = ;").unwrap_err();
+        assert_eq!(err.id(), ErrorId::ERR_UNEXPECTED_TOKEN);
+        assert_eq!((err.line(), err.col()), (1, 14));
+        assert_eq!(err.message(), "Expected an expression, found `;`");
+
+        let err = parse("var y = { let z = 1; z }").unwrap_err();
+        assert_eq!(err.id(), ErrorId::ERR_UNEXPECTED_EOF);
+        assert_eq!(err.message(), "Expected `;`");
+
+        assert!(parse("const a = { let b = 2; b };").is_ok());
+    }
+
+    #[test]
+    fn parser_respects_precedence_and_associativity() {
+        let cases = [
+            (
+                "1 + 2 * 3",
+                "(binary + (literal 1) (binary * (literal 2) (literal 3)))",
+            ),
+            (
+                "a - b - c",
+                "(binary - (binary - (iden a) (iden b)) (iden c))",
+            ),
+            (
+                "a = b = c",
+                "(assign = (iden a) (assign = (iden b) (iden c)))",
+            ),
+            (
+                "a ? b : c ? d : e",
+                "(ternary (iden a) (iden b) (ternary (iden c) (iden d) (iden e)))",
+            ),
+            (
+                "a = b ? c : d",
+                "(assign = (iden a) (ternary (iden b) (iden c) (iden d)))",
+            ),
+            (
+                "!a && b || c",
+                "(binary || (binary && (unary ! (iden a)) (iden b)) (iden c))",
+            ),
+            (
+                "x += y * 2 % z",
+                "(assign += (iden x) (binary % (binary * (iden y) (literal 2)) (iden z)))",
+            ),
+            (
+                "a & b | c == d",
+                "(binary | (binary & (iden a) (iden b)) (binary == (iden c) (iden d)))",
+            ),
+            (
+                "a < b == c >= d",
+                "(binary == (binary < (iden a) (iden b)) (binary >= (iden c) (iden d)))",
+            ),
+            (
+                "a.b[c](d, e).f",
+                "(field f (call (index (field b (iden a)) (iden c)) (iden d) (iden e)))",
+            ),
+            (
+                "-a.b - -1",
+                "(binary - (unary - (field b (iden a))) (literal -1))",
+            ),
+            ("x -1", "(binary - (iden x) (literal 1))"),
+            (
+                "f(-1, (a + b) * c)",
+                "(call (iden f) (literal -1) \
+                 (binary * (paren (binary + (iden a) (iden b))) (iden c)))",
+            ),
+            (
+                "items[i] -= 1",
+                "(assign -= (index (iden items) (iden i)) (literal 1))",
+            ),
+            (
+                "0..n + 1",
+                "(range .. (literal 0) (binary + (iden n) (literal 1)))",
+            ),
+            (
+                "a..=b == c",
+                "(range ..= (iden a) (binary == (iden b) (iden c)))",
+            ),
+            (
+                "x = c ? 0..1 : 2..3",
+                "(assign = (iden x) (ternary (iden c) \
+                 (range .. (literal 0) (literal 1)) (range .. (literal 2) (literal 3))))",
+            ),
+        ];
+
+        for (src, expected) in cases {
+            assert_eq!(expr_sexpr(src), expected, "{}", src);
+        }
+
+        let err = parser::parse(lex_str("let x = a + b = c;"), Path::new("test.feo")).unwrap_err();
+        assert_eq!((err.line(), err.col()), (1, 15));
+
+        let err = parser::parse(lex_str("let x = 0..1..2;"), Path::new("test.feo")).unwrap_err();
+        assert_eq!((err.line(), err.col()), (1, 13));
+    }
+
+    /// Returns the dump of a syntax tree on one line, so that a subtree can be checked for
+    /// with `contains`
+    fn flat_sexpr(module: &ast::Module) -> String {
+        let dump = ast::to_sexpr(module, false);
+        dump.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Returns the one-line dump of an expression, parsed as the value of `let x = <src>;`
+    fn expr_sexpr(src: &str) -> String {
+        let src = format!("let x = {};", src);
+        let dump = flat_sexpr(&parser::parse(lex_str(&src), Path::new("test.feo")).unwrap());
+
+        dump.strip_prefix("(module (let (binding x) ")
+            .and_then(|d| d.strip_suffix("))"))
+            .unwrap()
+            .to_string()
+    }
+
+    /// Minimal deterministic pseudo-random number generator (xorshift64) for property tests
+    struct Rng(u64);
+
+    impl Rng {
+        fn new(seed: u64) -> Self {
+            Self(seed)
+        }
+
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    /// The result of resolving and checking a snippet that resolves without errors
+    struct Checked {
+        module: ast::Module,
+        resolution: sema::Resolution,
+        types: sema::Types,
+        errors: Vec<error::FeoError>,
+    }
+
+    impl Checked {
+        /// Returns the id, position and message of each error the checker reported
+        fn errors(&self) -> Vec<(ErrorId, usize, usize, &str)> {
+            self.errors
+                .iter()
+                .map(|e| (e.id(), e.line(), e.col(), e.message()))
+                .collect()
+        }
+    }
+
+    /// Parses, resolves and checks a snippet, asserting that every name in it resolves
+    fn check_str(src: &str) -> Checked {
+        let path = Path::new("test.feo");
+        let module = parser::parse(lex_str(src), path).unwrap();
+        let (resolution, errors) = sema::resolve(&module, path);
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        let (types, errors) = sema::check(&module, &resolution, path);
+        Checked {
+            module,
+            resolution,
+            types,
+            errors,
+        }
+    }
+
+    fn lex_str(src: &str) -> Vec<Vec<Token>> {
+        lexer::lex(src, Path::new("test.feo")).unwrap()
+    }
+
+    fn dump_tokens(lines: &[Vec<Token>]) -> String {
+        lines
+            .iter()
+            .flatten()
+            .map(|t| format!("{}:{} {:?}\n", t.line(), t.col(), t.tok_type()))
+            .collect()
+    }
 
-        let value = lexer::lex(&file, path).unwrap();
-        println!("{:?}", value);
+    fn tok_types(lines: &[Vec<Token>]) -> Vec<Vec<&TokType>> {
+        lines
+            .iter()
+            .map(|l| l.iter().map(|t| t.tok_type()).collect())
+            .collect()
     }
 
     #[test]
@@ -0,0 +1,344 @@
+use crate::ast::{Iden, ItemKind, Module};
+use crate::error::{ErrorId, FeoError};
+use crate::span::Span;
+use crate::{lexer, parser};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Maps module paths (e.g. `geometry.shapes`) to source files
+///
+/// A path starting with the name of a library added with `add_lib` is relative to that
+/// library's directory, and any other path to the project root. `a.b.c` is the file
+/// `a/b/c.feo`, except that a library's own name refers to its root file, `lib.feo`
+///
+#[derive(Debug, Clone)]
+pub struct ModuleResolver {
+    root: PathBuf,
+    libs: HashMap<String, PathBuf>,
+
+    /// the path of each file loaded so far, leaked once so that loading a file again (e.g.
+    /// in a later call to `load`) reuses it
+    paths: HashMap<PathBuf, &'static Path>,
+}
+
+impl ModuleResolver {
+    /// Returns a resolver for a project
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - the directory containing the project's modules
+    ///
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            libs: HashMap::new(),
+            paths: HashMap::new(),
+        }
+    }
+
+    /// Makes a library available to `import`
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name the library is imported by, which its root file must declare with
+    ///   `lib <name>;`
+    /// * `dir` - the directory containing the library's `lib.feo` and its other modules
+    ///
+    pub fn add_lib(&mut self, name: &str, dir: impl Into<PathBuf>) {
+        self.libs.insert(name.to_string(), dir.into());
+    }
+
+    /// Returns the source file of a module, if it exists
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the names making up the module's path
+    ///
+    pub fn resolve(&self, path: &[&str]) -> Option<PathBuf> {
+        let (dir, rest): (&PathBuf, &[&str]) = match self.libs.get(*path.first()?) {
+            Some(dir) => (dir, &path[1..]),
+            None => (&self.root, path),
+        };
+
+        let file: PathBuf = match rest.is_empty() {
+            true => dir.join("lib.feo"),
+            false => dir
+                .join(rest.iter().collect::<PathBuf>())
+                .with_extension("feo"),
+        };
+
+        file.is_file().then_some(file)
+    }
+
+    /// Returns the path of a file for the rest of the compilation, leaking it the first time
+    ///
+    fn intern(&mut self, file: &Path) -> &'static Path {
+        self.paths
+            .entry(file.to_path_buf())
+            .or_insert_with(|| Box::leak(file.to_path_buf().into_boxed_path()))
+    }
+
+    /// Returns the name of the library whose root file is `file`, if any
+    ///
+    fn lib_root_of(&self, file: &Path) -> Option<&str> {
+        self.libs.iter().find_map(|(name, dir)| {
+            let root: PathBuf = fs::canonicalize(dir.join("lib.feo")).ok()?;
+            (root == file).then_some(name.as_str())
+        })
+    }
+}
+
+/// A parsed source file
+///
+#[derive(Debug)]
+pub struct SourceModule {
+    /// the module's path (e.g. `geometry.shapes`), or the file name of the entry module
+    /// unless it is a library root
+    pub name: String,
+    pub path: &'static Path,
+    pub ast: Module,
+}
+
+/// Every module of a program, each listed after all of the modules it imports, so that the
+/// entry module comes last
+///
+#[derive(Debug)]
+pub struct Program {
+    pub modules: Vec<SourceModule>,
+}
+
+/// Loads, lexes and parses a program's entry file and, transitively, every module it imports
+///
+/// The path of each file is leaked (once per resolver), as the tokens, syntax tree and any
+/// errors of a file all refer to it for the rest of the compilation
+///
+/// # Arguments
+///
+/// * `entry` - the program's main source file
+/// * `resolver` - maps imports to source files. If `entry` is the root of a library
+///   (i.e. starts with `lib <name>;`), that library is added to it
+///
+/// # Errors
+///
+/// Returns a `FeoError` if a file cannot be read, lexed or parsed, if an import names a
+/// module that does not exist, if modules import each other in a cycle, or if a `lib`
+/// declaration is missing or misplaced
+///
+pub fn load(entry: &Path, resolver: &mut ModuleResolver) -> Result<Program, FeoError> {
+    let mut loader = Loader {
+        resolver,
+        modules: Vec::new(),
+        loaded: HashSet::new(),
+        stack: Vec::new(),
+    };
+
+    let name: String = entry
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    loader.load_module(entry, name, None)?;
+
+    Ok(Program {
+        modules: loader.modules,
+    })
+}
+
+struct Loader<'a> {
+    resolver: &'a mut ModuleResolver,
+    modules: Vec<SourceModule>,
+    loaded: HashSet<PathBuf>,
+
+    /// the files currently being loaded and their module names, each imported by the one
+    /// before it
+    stack: Vec<(PathBuf, String)>,
+}
+
+impl Loader<'_> {
+    /// Loads a module and the modules it imports, unless it was already loaded
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - the module's source file
+    /// * `name` - the module's path
+    /// * `import` - the file and span of the import that named the module (`None` for the
+    ///   entry module)
+    ///
+    fn load_module(
+        &mut self,
+        file: &Path,
+        mut name: String,
+        import: Option<(&'static Path, Span)>,
+    ) -> Result<(), FeoError> {
+        let key: PathBuf = match fs::canonicalize(file) {
+            Ok(key) => key,
+            Err(e) => {
+                let message = format!("Unable to read `{}`: {}", file.display(), e);
+                return Err(self.error_at_import(file, import, ErrorId::ERR_INVALID_DATA, message));
+            }
+        };
+
+        if self.loaded.contains(&key) {
+            return Ok(());
+        }
+
+        if let Some(i) = self.stack.iter().position(|(k, _)| *k == key) {
+            let mut cycle: Vec<&str> = self.stack[i..].iter().map(|(_, n)| n.as_str()).collect();
+            cycle.push(&name);
+
+            let message = format!("`{}` imports itself ({})", name, cycle.join(" -> "));
+            return Err(self.error_at_import(file, import, ErrorId::ERR_IMPORT_CYCLE, message));
+        }
+
+        let source: String = match fs::read_to_string(&key) {
+            Ok(source) => source,
+            Err(e) => {
+                let message = format!("Unable to read `{}`: {}", file.display(), e);
+                return Err(self.error_at_import(file, import, ErrorId::ERR_INVALID_DATA, message));
+            }
+        };
+
+        let path: &'static Path = self.resolver.intern(file);
+        let ast: Module = parser::parse(lexer::lex(&source, path)?, path)?;
+
+        if let Some(lib) = self.check_lib(&ast, path, &key, import.is_none())? {
+            name = lib;
+        }
+
+        self.stack.push((key.clone(), name.clone()));
+
+        for item in &ast.items {
+            let ItemKind::IMPORT(tree) = &item.kind else {
+                continue;
+            };
+
+            for (import_path, span) in tree.paths() {
+                let names: Vec<&str> = import_path.iter().map(|i| i.name.as_str()).collect();
+
+                // `a.b.c` is either the module `a/b/c.feo` or the item `c` of `a/b.feo`
+                let resolved = match self.resolver.resolve(&names) {
+                    Some(file) => Some((file, names.join("."))),
+                    None if names.len() > 1 => {
+                        let module: &[&str] = &names[..names.len() - 1];
+                        self.resolver.resolve(module).map(|f| (f, module.join(".")))
+                    }
+                    None => None,
+                };
+
+                let Some((child, child_name)) = resolved else {
+                    let message = format!("Cannot find a module for `{}`", names.join("."));
+                    let err = FeoError::new(
+                        ErrorId::ERR_UNRESOLVED_IMPORT,
+                        None,
+                        path,
+                        span.line,
+                        span.col,
+                        message,
+                    );
+
+                    return Err(err);
+                };
+
+                self.load_module(&child, child_name, Some((path, span)))?;
+            }
+        }
+
+        self.stack.pop();
+        self.loaded.insert(key);
+        self.modules.push(SourceModule { name, path, ast });
+
+        Ok(())
+    }
+
+    /// Returns an error about a file itself, reported at the import naming it, if any
+    ///
+    fn error_at_import(
+        &mut self,
+        file: &Path,
+        import: Option<(&'static Path, Span)>,
+        id: ErrorId,
+        message: String,
+    ) -> FeoError {
+        match import {
+            Some((path, span)) => FeoError::new(id, None, path, span.line, span.col, message),
+            None => FeoError::new(id, None, self.resolver.intern(file), 1, 1, message),
+        }
+    }
+
+    /// Checks that a file declares a library if and only if it is a library's root file,
+    /// returning the name of the library, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `ast` - the file's syntax tree
+    /// * `path` - the file's path, for errors
+    /// * `key` - the file's canonical path
+    /// * `is_entry` - whether the file is the program's entry module, which may declare a new
+    ///   library if it is named `lib.feo`
+    ///
+    fn check_lib(
+        &mut self,
+        ast: &Module,
+        path: &'static Path,
+        key: &Path,
+        is_entry: bool,
+    ) -> Result<Option<String>, FeoError> {
+        let error = |span: Span, message: String| {
+            FeoError::new(
+                ErrorId::ERR_INVALID_LIB,
+                None,
+                path,
+                span.line,
+                span.col,
+                message,
+            )
+        };
+
+        let mut declared: Option<&Iden> = None;
+
+        for (i, item) in ast.items.iter().enumerate() {
+            if let ItemKind::LIB(name) = &item.kind {
+                if i > 0 {
+                    let message = "A `lib` declaration must be the first item in its file";
+                    return Err(error(item.span, message.to_string()));
+                }
+
+                declared = Some(name);
+            }
+        }
+
+        let is_lib_file: bool = key.file_name().is_some_and(|f| f == "lib.feo");
+
+        match (declared, self.resolver.lib_root_of(key)) {
+            (Some(name), Some(lib)) if name.name != lib => {
+                let message = format!("Expected `lib {};`, found `lib {};`", lib, name.name);
+                Err(error(name.span, message))
+            }
+
+            (Some(name), Some(_)) => Ok(Some(name.name.clone())),
+
+            (Some(name), None) if is_entry && is_lib_file => {
+                let dir: &Path = key.parent().unwrap_or(key);
+                self.resolver.add_lib(&name.name, dir);
+
+                Ok(Some(name.name.clone()))
+            }
+
+            (Some(name), None) => {
+                let message = format!(
+                    "`lib {};` can only be declared in the root file (`lib.feo`) of a library",
+                    name.name
+                );
+                Err(error(name.span, message))
+            }
+
+            (None, Some(lib)) => {
+                let message = format!("Expected the library root to start with `lib {};`", lib);
+                Err(error(Span::new(1, 1, 1, 1), message))
+            }
+
+            (None, None) => Ok(None),
+        }
+    }
+}
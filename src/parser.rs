@@ -0,0 +1,412 @@
+use crate::ast::{Iden, Item, ItemKind, Module};
+use crate::error::{ErrorId, FeoError};
+use crate::lexer::token::{TokType, Token};
+use crate::span::Span;
+use std::path::Path;
+
+mod class;
+mod expr;
+mod flow;
+mod import;
+mod item;
+mod pattern;
+mod protocol;
+mod stmt;
+mod ty;
+
+/// The keywords that start an item (or a class member) but never a statement or an
+/// expression, so that a block reaching one must be missing its closing `}`
+///
+const ITEM_KEYWORDS: [TokType; 16] = [
+    TokType::KW_ABSTRACT,
+    TokType::KW_ALIAS,
+    TokType::KW_CLASS,
+    TokType::KW_ENUM,
+    TokType::KW_EXTERN,
+    TokType::KW_FINAL,
+    TokType::KW_FUNC,
+    TokType::KW_IMPORT,
+    TokType::KW_LIB,
+    TokType::KW_OVERRIDE,
+    TokType::KW_PROTOCOL,
+    TokType::KW_PUBLIC,
+    TokType::KW_STATIC,
+    TokType::KW_STRUCT,
+    TokType::KW_TYPE,
+    TokType::KW_VIRTUAL,
+];
+
+/// The most expressions, types and patterns that can be nested within one another. Deeper
+/// input is reported rather than parsed, so that it cannot overflow the stack
+///
+const MAX_NESTING: usize = 100;
+
+/// Returns the syntax tree of a source file
+///
+/// # Arguments
+///
+/// * `tokens` - the file's tokens, as returned by `lex`
+/// * `path` - the location of the source file in memory
+///
+/// # Errors
+///
+/// Returns a `FeoError` pointing at the first token that does not fit the grammar
+///
+pub fn parse(tokens: Vec<Vec<Token>>, path: &'static Path) -> Result<Module, FeoError> {
+    let (module, errors): (Module, Vec<FeoError>) = parse_with_recovery(tokens, path);
+
+    match errors.into_iter().next() {
+        Some(err) => Err(err),
+        None => Ok(module),
+    }
+}
+
+/// Returns a best-effort syntax tree of a source file, along with every syntax error in it
+///
+/// After an error, the parser skips ahead to the end of the statement, member or item it was
+/// parsing (i.e. past the next `;`, or up to the next `}` or item keyword), which is replaced
+/// by an `ERROR` node in the tree, and carries on from there
+///
+/// # Arguments
+///
+/// * `tokens` - the file's tokens, as returned by `lex`
+/// * `path` - the location of the source file in memory
+///
+pub fn parse_with_recovery(
+    tokens: Vec<Vec<Token>>,
+    path: &'static Path,
+) -> (Module, Vec<FeoError>) {
+    let mut parser = Parser::new(tokens, path);
+    let module: Module = parser.parse_module();
+
+    (module, parser.errors)
+}
+
+/// A recursive-descent parser over the tokens of a file. The grammar is split by node type
+/// across the `parser` submodules, each adding its own `parse_*` methods
+///
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    path: &'static Path,
+
+    /// Set while parsing the condition of an `if` or `while` (or the iterable of a `for`),
+    /// where a `{` after a name opens the body rather than a struct literal
+    no_struct_literal: bool,
+
+    /// The syntax errors recovered from so far, in order
+    errors: Vec<FeoError>,
+
+    /// The number of expressions, types and patterns being parsed within one another
+    depth: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Vec<Token>>, path: &'static Path) -> Self {
+        Self {
+            tokens: tokens.into_iter().flatten().collect(),
+            pos: 0,
+            path,
+            no_struct_literal: false,
+            errors: Vec::new(),
+            depth: 0,
+        }
+    }
+
+    fn parse_module(&mut self) -> Module {
+        let start: Span = self.span();
+        let mut items = Vec::new();
+
+        while !self.is_at_end() {
+            let item_start: usize = self.pos;
+
+            match self.parse_item() {
+                Ok(item) => items.push(item),
+
+                Err(err) => {
+                    let span: Span = self.recover(err, item_start, false);
+
+                    items.push(Item {
+                        kind: ItemKind::ERROR,
+                        is_public: false,
+                        span,
+                    });
+                }
+            }
+        }
+
+        Module {
+            items,
+            span: start.to(self.prev_span()),
+        }
+    }
+
+    /// Records a syntax error and skips the rest of the statement, member or item in which it
+    /// occurred, returning the span of the skipped tokens
+    ///
+    /// Skipping stops after a `;`, or before `let`, `var`, `const` or an item keyword, unless
+    /// they are nested in braces. A `}` closing the enclosing block or body is not skipped,
+    /// while a `}` at the top level (i.e. closing a broken item) is
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - the error to record
+    /// * `start` - the position of the first token of the statement, member or item
+    /// * `in_braces` - whether the error occurred in a block or body, rather than at the top
+    ///   level
+    ///
+    fn recover(&mut self, err: FeoError, start: usize, in_braces: bool) -> Span {
+        self.errors.push(err);
+
+        // whatever was being parsed when the error occurred is abandoned
+        self.no_struct_literal = false;
+
+        let start_span: Span = match self.tokens.get(start) {
+            Some(t) => t.span(),
+            None => self.span(),
+        };
+
+        let mut depth: usize = 0;
+
+        while let Some(tok_type) = self.peek() {
+            match tok_type {
+                TokType::PUNC_OPEN_CRL_BRC => depth += 1,
+
+                TokType::PUNC_CLS_CRL_BRC if depth > 0 => depth -= 1,
+
+                TokType::PUNC_CLS_CRL_BRC => {
+                    if !in_braces {
+                        self.bump();
+                    }
+
+                    break;
+                }
+
+                TokType::PUNC_SEMICOLON if depth == 0 => {
+                    self.bump();
+                    break;
+                }
+
+                // if the first token was the problem, it must be skipped to make progress
+                _ if self.pos == start => (),
+
+                TokType::KW_LET | TokType::KW_VAR | TokType::KW_CONST if depth == 0 => break,
+
+                t if depth == 0 && ITEM_KEYWORDS.contains(t) => break,
+
+                _ => (),
+            }
+
+            self.bump();
+        }
+
+        start_span.to(self.prev_span())
+    }
+
+    /// Returns `true` if the current token can only start an item or class member (see
+    /// `ITEM_KEYWORDS`)
+    ///
+    fn at_item_keyword(&self) -> bool {
+        self.peek().is_some_and(|t| ITEM_KEYWORDS.contains(t))
+    }
+
+    /// Returns the type of the current token, if any
+    ///
+    fn peek(&self) -> Option<&TokType> {
+        self.peek_nth(0)
+    }
+
+    /// Returns the type of the token `n` places after the current one, if any
+    ///
+    fn peek_nth(&self, n: usize) -> Option<&TokType> {
+        self.tokens.get(self.pos + n).map(Token::tok_type)
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    /// Returns `true` if the current token is of the given type
+    ///
+    fn check(&self, tok_type: &TokType) -> bool {
+        self.peek() == Some(tok_type)
+    }
+
+    /// Consumes the current token if it is of the given type, returning whether it did
+    ///
+    fn eat(&mut self, tok_type: &TokType) -> bool {
+        let is_match: bool = self.check(tok_type);
+
+        if is_match {
+            self.pos += 1;
+        }
+
+        is_match
+    }
+
+    /// Consumes the current token and returns its type
+    ///
+    fn bump(&mut self) -> Option<TokType> {
+        let tok_type: Option<TokType> = self.peek().cloned();
+
+        if tok_type.is_some() {
+            self.pos += 1;
+        }
+
+        tok_type
+    }
+
+    /// Consumes the current token if it is of the given type, returning its span
+    ///
+    fn expect(&mut self, tok_type: TokType) -> Result<Span, FeoError> {
+        let span: Span = self.span();
+
+        if self.eat(&tok_type) {
+            Ok(span)
+        } else {
+            Err(self.unexpected(&format!("`{}`", tok_type)))
+        }
+    }
+
+    /// Consumes the current token if it is an identifier
+    ///
+    fn expect_iden(&mut self) -> Result<Iden, FeoError> {
+        let span: Span = self.span();
+
+        match self.peek() {
+            Some(TokType::IDEN(name)) => {
+                let name: String = name.to_string();
+                self.pos += 1;
+
+                Ok(Iden { name, span })
+            }
+
+            _ => Err(self.unexpected("an identifier")),
+        }
+    }
+
+    /// Consumes the current token if it is a loop label, such as `'outer`
+    ///
+    fn expect_label(&mut self) -> Result<Iden, FeoError> {
+        let span: Span = self.span();
+
+        match self.peek() {
+            Some(TokType::LABEL(name)) => {
+                let name: String = name.to_string();
+                self.pos += 1;
+
+                Ok(Iden { name, span })
+            }
+
+            _ => Err(self.unexpected("a label")),
+        }
+    }
+
+    /// Returns the span of the current token, or an empty span after the last token if the
+    /// end of the file has been reached
+    ///
+    fn span(&self) -> Span {
+        match self.tokens.get(self.pos) {
+            Some(t) => t.span(),
+            None => self.prev_span().end(),
+        }
+    }
+
+    /// Returns the span of the last consumed token
+    ///
+    fn prev_span(&self) -> Span {
+        match self.pos.checked_sub(1).and_then(|i| self.tokens.get(i)) {
+            Some(t) => t.span(),
+            None => Span::new(1, 1, 1, 1),
+        }
+    }
+
+    /// Returns an error at the given span
+    ///
+    fn error_at(&self, span: Span, message: impl Into<String>) -> FeoError {
+        FeoError::new(
+            ErrorId::ERR_UNEXPECTED_TOKEN,
+            None,
+            self.path,
+            span.line,
+            span.col,
+            message,
+        )
+    }
+
+    /// Runs a parsing function one level of nesting deeper, failing if the input is nested
+    /// more than `MAX_NESTING` deep
+    ///
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, FeoError>,
+    ) -> Result<T, FeoError> {
+        if self.depth >= MAX_NESTING {
+            return Err(self.too_deep());
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+
+        result
+    }
+
+    /// Goes one level of nesting deeper without coming back up, failing if the input is
+    /// nested more than `MAX_NESTING` deep. The caller restores `depth` when it is done
+    ///
+    fn deepen(&mut self) -> Result<(), FeoError> {
+        if self.depth >= MAX_NESTING {
+            return Err(self.too_deep());
+        }
+
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    /// Returns the error for input nested more than `MAX_NESTING` deep. It is built here
+    /// rather than in `nested` to keep that frame, which is on the stack once per level, small
+    ///
+    fn too_deep(&self) -> FeoError {
+        let message = format!(
+            "Expressions, types and patterns cannot be nested more than {} deep",
+            MAX_NESTING
+        );
+
+        FeoError::at(
+            ErrorId::ERR_NESTING_TOO_DEEP,
+            self.path,
+            self.span(),
+            message,
+        )
+    }
+
+    /// Returns an error for the current token, describing what was expected in its place
+    ///
+    fn unexpected(&self, expected: &str) -> FeoError {
+        match self.tokens.get(self.pos) {
+            Some(t) => FeoError::new(
+                ErrorId::ERR_UNEXPECTED_TOKEN,
+                None,
+                self.path,
+                t.line(),
+                t.col(),
+                format!("Expected {}, found `{}`", expected, t.tok_type()),
+            ),
+
+            None => {
+                let span: Span = self.span();
+
+                FeoError::new(
+                    ErrorId::ERR_UNEXPECTED_EOF,
+                    None,
+                    self.path,
+                    span.line,
+                    span.col,
+                    format!("Expected {}", expected),
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use super::item::FnBody;
+use super::Parser;
+use crate::ast::{
+    ClassDecl, ClassField, Constructor, GenericParam, Iden, Member, MemberKind, Modifiers, Param,
+    TypeAlias,
+};
+use crate::error::FeoError;
+use crate::lexer::token::TokType;
+use crate::span::Span;
+
+/// The keywords that may precede a class or class member
+///
+const MODIFIERS: [TokType; 6] = [
+    TokType::KW_PUBLIC,
+    TokType::KW_STATIC,
+    TokType::KW_ABSTRACT,
+    TokType::KW_VIRTUAL,
+    TokType::KW_OVERRIDE,
+    TokType::KW_FINAL,
+];
+
+impl Parser {
+    /// Parses `[abstract] [final] class <name>[<generics>] [: <supertypes>] { <members> }`
+    ///
+    pub(super) fn parse_class_decl(&mut self) -> Result<ClassDecl, FeoError> {
+        let modifiers: Modifiers =
+            self.parse_modifiers(&[TokType::KW_ABSTRACT, TokType::KW_FINAL], "a class")?;
+
+        self.expect(TokType::KW_CLASS)?;
+
+        let name: Iden = self.expect_iden()?;
+        let generics: Vec<GenericParam> = self.parse_generic_params()?;
+
+        let supertypes: Vec<Iden> = self.parse_conformance()?;
+
+        self.expect(TokType::PUNC_OPEN_CRL_BRC)?;
+        let members: Vec<Member> = self.parse_members(true);
+
+        Ok(ClassDecl {
+            name,
+            generics,
+            supertypes,
+            members,
+            is_abstract: modifiers.is_abstract,
+            is_final: modifiers.is_final,
+        })
+    }
+
+    /// Parses members up to and including the closing `}` of a class, struct or enum
+    ///
+    /// A member containing a syntax error is recorded as an `ERROR` member. A body that runs
+    /// into the end of the file or the start of another item is missing its `}`, which is
+    /// recorded as an error, and ends there
+    ///
+    /// # Arguments
+    ///
+    /// * `in_class` - whether the members belong to a class, and so may be fields,
+    ///   constructors or non-`static` modifiers
+    ///
+    pub(super) fn parse_members(&mut self, in_class: bool) -> Vec<Member> {
+        let mut members: Vec<Member> = Vec::new();
+
+        while !self.eat(&TokType::PUNC_CLS_CRL_BRC) {
+            let is_field: bool = in_class
+                && matches!(
+                    self.peek_nth(self.count_modifiers()),
+                    Some(TokType::IDEN(_))
+                );
+
+            if self.is_at_end() || (self.at_item_keyword() && !self.at_member() && !is_field) {
+                self.errors.push(self.unexpected("`}`"));
+                break;
+            }
+
+            let start: usize = self.pos;
+
+            match self.parse_member(in_class) {
+                Ok(member) => members.push(member),
+
+                Err(err) => {
+                    let span: Span = self.recover(err, start, true);
+
+                    members.push(Member {
+                        kind: MemberKind::ERROR,
+                        modifiers: Modifiers::default(),
+                        span,
+                    });
+                }
+            }
+        }
+
+        members
+    }
+
+    /// Returns `true` if the next tokens start a method, constructor or associated type
+    /// binding (as opposed to a field or variant)
+    ///
+    pub(super) fn at_member(&self) -> bool {
+        matches!(
+            self.peek_nth(self.count_modifiers()),
+            Some(TokType::KW_FUNC | TokType::KW_NEW | TokType::KW_TYPE)
+        )
+    }
+
+    /// Returns the number of modifier keywords from the current token onwards
+    ///
+    fn count_modifiers(&self) -> usize {
+        let mut n: usize = 0;
+
+        while self.peek_nth(n).is_some_and(|t| MODIFIERS.contains(t)) {
+            n += 1;
+        }
+
+        n
+    }
+
+    /// Parses a field, constructor, method or associated type binding, along with its
+    /// modifiers
+    ///
+    /// # Arguments
+    ///
+    /// * `in_class` - whether the member belongs to a class (see `parse_members`)
+    ///
+    fn parse_member(&mut self, in_class: bool) -> Result<Member, FeoError> {
+        let start: Span = self.span();
+
+        // the member kind is only known after the modifiers, so look past them first
+        let n: usize = self.count_modifiers();
+
+        let (modifiers, kind): (Modifiers, MemberKind) = match self.peek_nth(n) {
+            Some(TokType::KW_FUNC) => {
+                let allowed: &[TokType] = match in_class {
+                    true => &MODIFIERS,
+                    false => &[TokType::KW_PUBLIC, TokType::KW_STATIC],
+                };
+
+                let modifiers: Modifiers = self.parse_modifiers(allowed, "a method")?;
+                let body: FnBody = match modifiers.is_abstract {
+                    true => FnBody::NONE,
+                    false => FnBody::REQUIRED,
+                };
+
+                (modifiers, MemberKind::METHOD(self.parse_fn_decl(body)?))
+            }
+
+            Some(TokType::KW_TYPE) => {
+                let modifiers: Modifiers = self.parse_modifiers(&[], "a type")?;
+
+                self.expect(TokType::KW_TYPE)?;
+                let name: Iden = self.expect_iden()?;
+                self.expect(TokType::OP_ASSIGN)?;
+                let ty = self.parse_type()?;
+                self.expect(TokType::PUNC_SEMICOLON)?;
+
+                (
+                    modifiers,
+                    MemberKind::TYPE(TypeAlias {
+                        name,
+                        generics: Vec::new(),
+                        ty,
+                    }),
+                )
+            }
+
+            Some(TokType::KW_NEW) if in_class => {
+                let modifiers: Modifiers =
+                    self.parse_modifiers(&[TokType::KW_PUBLIC], "a constructor")?;
+
+                self.expect(TokType::KW_NEW)?;
+                let params: Vec<Param> = self.parse_params()?;
+                let body = self.parse_block()?;
+
+                (
+                    modifiers,
+                    MemberKind::CONSTRUCTOR(Constructor { params, body }),
+                )
+            }
+
+            Some(TokType::IDEN(_)) if in_class => {
+                let modifiers: Modifiers =
+                    self.parse_modifiers(&[TokType::KW_PUBLIC, TokType::KW_STATIC], "a field")?;
+
+                let name: Iden = self.expect_iden()?;
+                self.expect(TokType::PUNC_COLON)?;
+                let ty = self.parse_type()?;
+
+                let value = match self.eat(&TokType::OP_ASSIGN) {
+                    true => Some(self.parse_expr()?),
+                    false => None,
+                };
+
+                self.expect(TokType::PUNC_SEMICOLON)?;
+
+                (modifiers, MemberKind::FIELD(ClassField { name, ty, value }))
+            }
+
+            _ => {
+                self.pos += n;
+
+                return match in_class {
+                    true => Err(self.unexpected("a field, constructor or method")),
+                    false => Err(self.unexpected("a method or `}`")),
+                };
+            }
+        };
+
+        Ok(Member {
+            kind,
+            modifiers,
+            span: start.to(self.prev_span()),
+        })
+    }
+
+    /// Parses a sequence of modifier keywords, in any order
+    ///
+    /// # Arguments
+    ///
+    /// * `allowed` - the modifiers that may appear
+    /// * `target` - what the modifiers apply to, for error messages (e.g. "a field")
+    ///
+    fn parse_modifiers(
+        &mut self,
+        allowed: &[TokType],
+        target: &str,
+    ) -> Result<Modifiers, FeoError> {
+        let mut modifiers = Modifiers::default();
+
+        while let Some(tok_type) = self.peek().filter(|t| MODIFIERS.contains(t)).cloned() {
+            let span: Span = self.span();
+
+            let flag: &mut bool = match tok_type {
+                TokType::KW_PUBLIC => &mut modifiers.is_public,
+                TokType::KW_STATIC => &mut modifiers.is_static,
+                TokType::KW_ABSTRACT => &mut modifiers.is_abstract,
+                TokType::KW_VIRTUAL => &mut modifiers.is_virtual,
+                TokType::KW_OVERRIDE => &mut modifiers.is_override,
+                _ => &mut modifiers.is_final,
+            };
+
+            if !allowed.contains(&tok_type) {
+                let message = format!("`{}` is not allowed on {}", tok_type, target);
+                return Err(self.error_at(span, message));
+            }
+
+            if *flag {
+                return Err(self.error_at(span, format!("Duplicate modifier `{}`", tok_type)));
+            }
+
+            *flag = true;
+            self.bump();
+        }
+
+        Ok(modifiers)
+    }
+}
@@ -0,0 +1,580 @@
+use super::Parser;
+use crate::ast::{AssignOp, BinaryOp, Expr, ExprKind, FieldInit, Iden, Literal, UnaryOp};
+use crate::error::{ErrorId, FeoError};
+use crate::lexer::token::TokType;
+use crate::span::Span;
+
+/// Operator precedence, from loosest to tightest binding. Each infix operator has a left and
+/// right binding power: an operator binds to its right operand only while the next operator's
+/// left binding power is at least its right binding power, so `l < r` makes it
+/// left-associative and `l > r` right-associative
+///
+/// | operators                        | binding power | associativity |
+/// |----------------------------------|---------------|---------------|
+/// | `=` `+=` `-=` `*=` `/=` `%=`     | 2, 1          | right         |
+/// | `? :`                            | 4, 3          | right         |
+/// | `..` `..=`                       | 5, 6          | none          |
+/// | `\|\|`                           | 7, 8          | left          |
+/// | `&&`                             | 9, 10         | left          |
+/// | `\|`                             | 11, 12        | left          |
+/// | `&`                              | 13, 14        | left          |
+/// | `==` `!=`                        | 15, 16        | left          |
+/// | `<` `<=` `>` `>=`                | 17, 18        | left          |
+/// | `+` `-`                          | 19, 20        | left          |
+/// | `*` `/` `%`                      | 21, 22        | left          |
+/// | prefix `-` `!`                   | 23            | right         |
+/// | postfix `.field` `[index]` `()`  | 25            | left          |
+///
+/// A path such as `Point` or `Shape.Rect` followed by `{` and a field (or `}`) starts a struct
+/// literal, which binds like a postfix operator
+///
+/// A `-` directly followed by a number is lexed as part of a negative literal, so `-1.abs()`
+/// is `(-1).abs()` whereas `-x.abs()` is `-(x.abs())`
+///
+const PREFIX_BP: u8 = 23;
+const POSTFIX_BP: u8 = 25;
+
+/// Returns the left and right binding power of an infix operator
+///
+fn infix_bp(tok_type: &TokType) -> Option<(u8, u8)> {
+    let bp: (u8, u8) = match tok_type {
+        TokType::OP_ASSIGN
+        | TokType::OP_PLUS_EQ
+        | TokType::OP_MINUS_EQ
+        | TokType::OP_MULT_EQ
+        | TokType::OP_DIV_EQ
+        | TokType::OP_MOD_EQ => (2, 1),
+        TokType::OP_TERNARY => (4, 3),
+        TokType::OP_RANGE | TokType::OP_RANGE_INCL => (5, 6),
+        TokType::OP_OR => (7, 8),
+        TokType::OP_AND => (9, 10),
+        TokType::OP_PIPE => (11, 12),
+        TokType::OP_AMPERSAND => (13, 14),
+        TokType::OP_EQ | TokType::OP_NOT_EQ => (15, 16),
+        TokType::OP_LESS | TokType::OP_LESS_EQ | TokType::OP_GRTR | TokType::OP_GRTR_EQ => (17, 18),
+        TokType::OP_PLUS | TokType::OP_MINUS => (19, 20),
+        TokType::OP_MULT | TokType::OP_DIV | TokType::OP_MOD => (21, 22),
+        _ => return None,
+    };
+
+    Some(bp)
+}
+
+fn binary_op(tok_type: &TokType) -> Option<BinaryOp> {
+    let op: BinaryOp = match tok_type {
+        TokType::OP_PLUS => BinaryOp::ADD,
+        TokType::OP_MINUS => BinaryOp::SUB,
+        TokType::OP_MULT => BinaryOp::MULT,
+        TokType::OP_DIV => BinaryOp::DIV,
+        TokType::OP_MOD => BinaryOp::MOD,
+        TokType::OP_EQ => BinaryOp::EQ,
+        TokType::OP_NOT_EQ => BinaryOp::NOT_EQ,
+        TokType::OP_LESS => BinaryOp::LESS,
+        TokType::OP_LESS_EQ => BinaryOp::LESS_EQ,
+        TokType::OP_GRTR => BinaryOp::GRTR,
+        TokType::OP_GRTR_EQ => BinaryOp::GRTR_EQ,
+        TokType::OP_AND => BinaryOp::AND,
+        TokType::OP_OR => BinaryOp::OR,
+        TokType::OP_AMPERSAND => BinaryOp::BIT_AND,
+        TokType::OP_PIPE => BinaryOp::BIT_OR,
+        _ => return None,
+    };
+
+    Some(op)
+}
+
+fn assign_op(tok_type: &TokType) -> Option<AssignOp> {
+    let op: AssignOp = match tok_type {
+        TokType::OP_ASSIGN => AssignOp::ASSIGN,
+        TokType::OP_PLUS_EQ => AssignOp::ADD,
+        TokType::OP_MINUS_EQ => AssignOp::SUB,
+        TokType::OP_MULT_EQ => AssignOp::MULT,
+        TokType::OP_DIV_EQ => AssignOp::DIV,
+        TokType::OP_MOD_EQ => AssignOp::MOD,
+        _ => return None,
+    };
+
+    Some(op)
+}
+
+impl Parser {
+    pub(super) fn parse_expr(&mut self) -> Result<Expr, FeoError> {
+        self.parse_expr_bp(0)
+    }
+
+    /// Parses an expression inside brackets, where struct literals are always allowed
+    ///
+    pub(super) fn parse_nested_expr(&mut self) -> Result<Expr, FeoError> {
+        let no_struct_literal: bool = std::mem::replace(&mut self.no_struct_literal, false);
+        let expr = self.parse_expr();
+        self.no_struct_literal = no_struct_literal;
+
+        expr
+    }
+
+    /// Parses an expression statement. One starting with a block-like expression (e.g. `if`)
+    /// ends with that expression, as in `if a { ... } -1;`, which is two statements
+    ///
+    pub(super) fn parse_expr_stmt(&mut self) -> Result<Expr, FeoError> {
+        match self.at_block_like() {
+            true => self.nested(Self::parse_primary),
+            false => self.parse_expr(),
+        }
+    }
+
+    /// Parses an expression whose operators all bind at least as tightly as `min_bp`
+    ///
+    fn parse_expr_bp(&mut self, min_bp: u8) -> Result<Expr, FeoError> {
+        self.nested(|p| p.parse_operators(min_bp))
+    }
+
+    /// Parses the operators of an expression one level of nesting down from `parse_expr_bp`
+    ///
+    fn parse_operators(&mut self, min_bp: u8) -> Result<Expr, FeoError> {
+        let depth: usize = self.depth;
+        let expr = self.fold_operators(min_bp);
+        self.depth = depth;
+
+        expr
+    }
+
+    /// Folds the postfix and infix operators that follow a prefix expression into it. Each
+    /// operator nests what came before it one level deeper, as in `((a + b) + c).d`, so it
+    /// counts towards `MAX_NESTING` like a bracket would
+    ///
+    fn fold_operators(&mut self, min_bp: u8) -> Result<Expr, FeoError> {
+        let mut lhs: Expr = self.parse_prefix()?;
+
+        while let Some(next) = self.peek() {
+            if matches!(
+                next,
+                TokType::PUNC_DOT | TokType::PUNC_OPEN_SQ_BKT | TokType::PUNC_OPEN_PAREN
+            ) {
+                if POSTFIX_BP < min_bp {
+                    break;
+                }
+
+                self.deepen()?;
+                lhs = self.parse_postfix(lhs)?;
+                continue;
+            }
+
+            if next == &TokType::PUNC_OPEN_CRL_BRC
+                && !self.no_struct_literal
+                && self.at_struct_literal()
+            {
+                if POSTFIX_BP < min_bp {
+                    break;
+                }
+
+                if let Some(path) = lhs.as_path() {
+                    self.deepen()?;
+                    lhs = self.parse_struct_literal(lhs.span, path)?;
+                    continue;
+                }
+            }
+
+            let (l_bp, r_bp) = match infix_bp(next) {
+                Some(bp) => bp,
+                None => break,
+            };
+
+            if l_bp < min_bp {
+                break;
+            }
+
+            let op: TokType = next.clone();
+            self.deepen()?;
+            lhs = self.parse_infix(lhs, op, r_bp)?;
+        }
+
+        Ok(lhs)
+    }
+
+    /// Parses the infix operator `tok_type`, which is the current token, and its right-hand
+    /// side, whose operators all bind at least as tightly as `r_bp`
+    ///
+    fn parse_infix(&mut self, lhs: Expr, tok_type: TokType, r_bp: u8) -> Result<Expr, FeoError> {
+        let op_span: Span = self.span();
+        self.bump();
+
+        let kind: ExprKind = if let Some(op) = assign_op(&tok_type) {
+            if !lhs.is_place() {
+                let err = FeoError::new(
+                    ErrorId::ERR_UNEXPECTED_TOKEN,
+                    None,
+                    self.path,
+                    op_span.line,
+                    op_span.col,
+                    format!(
+                        "Invalid left-hand side of `{}`. Expected a variable, field or index",
+                        op
+                    ),
+                );
+
+                return Err(err);
+            }
+
+            let value = self.parse_expr_bp(r_bp)?;
+
+            ExprKind::ASSIGN {
+                op,
+                target: Box::new(lhs),
+                value: Box::new(value),
+            }
+        } else if matches!(tok_type, TokType::OP_RANGE | TokType::OP_RANGE_INCL) {
+            // ranges do not chain, so `a..b..c` must be written `(a..b)..c`
+            if matches!(lhs.kind, ExprKind::RANGE { .. }) {
+                let message = format!(
+                    "Ranges cannot be chained. Put parentheses around the range before `{}`",
+                    tok_type
+                );
+                return Err(FeoError::at(
+                    ErrorId::ERR_UNEXPECTED_TOKEN,
+                    self.path,
+                    op_span,
+                    message,
+                ));
+            }
+
+            let end = self.parse_expr_bp(r_bp)?;
+
+            ExprKind::RANGE {
+                start: Box::new(lhs),
+                end: Box::new(end),
+                is_inclusive: tok_type == TokType::OP_RANGE_INCL,
+            }
+        } else if let Some(op) = binary_op(&tok_type) {
+            let rhs = self.parse_expr_bp(r_bp)?;
+
+            ExprKind::BINARY {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            }
+        } else {
+            // the only other infix operator is the ternary `? :`
+            let then = self.parse_expr_bp(0)?;
+            self.expect(TokType::PUNC_COLON)?;
+            let els = self.parse_expr_bp(r_bp)?;
+
+            ExprKind::TERNARY {
+                cond: Box::new(lhs),
+                then: Box::new(then),
+                els: Box::new(els),
+            }
+        };
+
+        let span: Span = match &kind {
+            ExprKind::TERNARY { cond, els, .. } => cond.span.to(els.span),
+            ExprKind::ASSIGN { target, value, .. } => target.span.to(value.span),
+            ExprKind::RANGE { start, end, .. } => start.span.to(end.span),
+            ExprKind::BINARY { lhs, rhs, .. } => lhs.span.to(rhs.span),
+            _ => op_span,
+        };
+
+        Ok(Expr { kind, span })
+    }
+
+    /// Parses a prefix operator and its operand, or a primary expression
+    ///
+    fn parse_prefix(&mut self) -> Result<Expr, FeoError> {
+        let start: Span = self.span();
+
+        let op: UnaryOp = match self.peek() {
+            Some(TokType::OP_MINUS) => UnaryOp::NEG,
+            Some(TokType::OP_BANG) => UnaryOp::NOT,
+            _ => return self.parse_primary(),
+        };
+
+        self.bump();
+
+        let expr: Expr = self.parse_expr_bp(PREFIX_BP)?;
+
+        Ok(Expr {
+            span: start.to(expr.span),
+            kind: ExprKind::UNARY {
+                op,
+                expr: Box::new(expr),
+            },
+        })
+    }
+
+    /// Parses a field access, index or call applied to `expr`
+    ///
+    fn parse_postfix(&mut self, expr: Expr) -> Result<Expr, FeoError> {
+        let start: Span = expr.span;
+        let expr: Box<Expr> = Box::new(expr);
+
+        let kind: ExprKind = match self.bump() {
+            Some(TokType::PUNC_DOT) => ExprKind::FIELD {
+                expr,
+                field: self.expect_iden()?,
+            },
+
+            Some(TokType::PUNC_OPEN_SQ_BKT) => {
+                let index: Expr = self.parse_nested_expr()?;
+                self.expect(TokType::PUNC_CLS_SQ_BKT)?;
+
+                ExprKind::INDEX {
+                    expr,
+                    index: Box::new(index),
+                }
+            }
+
+            _ => ExprKind::CALL {
+                callee: expr,
+                args: self.parse_args()?,
+            },
+        };
+
+        Ok(Expr {
+            kind,
+            span: start.to(self.prev_span()),
+        })
+    }
+
+    /// Parses a comma-separated list of arguments up to and including the closing `)`
+    ///
+    pub(super) fn parse_args(&mut self) -> Result<Vec<Expr>, FeoError> {
+        let mut args: Vec<Expr> = Vec::new();
+
+        while !self.eat(&TokType::PUNC_CLS_PAREN) {
+            args.push(self.parse_nested_expr()?);
+
+            if !self.eat(&TokType::PUNC_COMMA) {
+                self.expect(TokType::PUNC_CLS_PAREN)?;
+                break;
+            }
+        }
+
+        Ok(args)
+    }
+
+    /// Returns `true` if the upcoming `{` opens the fields of a struct literal rather than a
+    /// block, i.e. it is followed by `}`, or by an identifier and then `:`, `,` or `}`
+    ///
+    fn at_struct_literal(&self) -> bool {
+        match self.peek_nth(1) {
+            Some(TokType::PUNC_CLS_CRL_BRC) => true,
+            Some(TokType::IDEN(_)) => matches!(
+                self.peek_nth(2),
+                Some(TokType::PUNC_COLON | TokType::PUNC_COMMA | TokType::PUNC_CLS_CRL_BRC)
+            ),
+            _ => false,
+        }
+    }
+
+    /// Parses the `{ <name>: <expr>, ... }` part of a struct literal, where a lone `<name>` is
+    /// short for `<name>: <name>`
+    ///
+    fn parse_struct_literal(&mut self, start: Span, path: Vec<Iden>) -> Result<Expr, FeoError> {
+        self.expect(TokType::PUNC_OPEN_CRL_BRC)?;
+        let mut fields: Vec<FieldInit> = Vec::new();
+
+        while !self.eat(&TokType::PUNC_CLS_CRL_BRC) {
+            let name: Iden = self.expect_iden()?;
+
+            let value: Expr = match self.eat(&TokType::PUNC_COLON) {
+                true => self.parse_expr()?,
+                false => Expr {
+                    kind: ExprKind::IDEN(name.name.clone()),
+                    span: name.span,
+                },
+            };
+
+            fields.push(FieldInit {
+                span: name.span.to(value.span),
+                name,
+                value,
+            });
+
+            if !self.eat(&TokType::PUNC_COMMA) {
+                self.expect(TokType::PUNC_CLS_CRL_BRC)?;
+                break;
+            }
+        }
+
+        Ok(Expr {
+            kind: ExprKind::STRUCT { path, fields },
+            span: start.to(self.prev_span()),
+        })
+    }
+
+    /// Parses a literal, identifier, `self`, `super`, `new` expression, parenthesised expression,
+    /// tuple, array, block, or control-flow expression
+    ///
+    pub(super) fn parse_primary(&mut self) -> Result<Expr, FeoError> {
+        let start: Span = self.span();
+
+        if let Some(lit) = self.parse_literal() {
+            return Ok(Expr {
+                kind: ExprKind::LITERAL(lit),
+                span: start,
+            });
+        }
+
+        // the arms share a single `?`, since each one would otherwise add its own temporaries
+        // to this frame, which is on the stack once for every level of nesting
+        let kind: ExprKind = match self.peek() {
+            Some(TokType::IDEN(_)) => self.expect_iden().map(|iden| ExprKind::IDEN(iden.name)),
+
+            Some(TokType::KW_SELF) => {
+                self.bump();
+                Ok(ExprKind::SELF)
+            }
+
+            Some(TokType::KW_SUPER) => {
+                self.bump();
+                Ok(ExprKind::SUPER)
+            }
+
+            Some(TokType::KW_NEW) => self.parse_new(),
+
+            Some(TokType::PUNC_OPEN_PAREN) => self.parse_paren(),
+
+            Some(TokType::PUNC_OPEN_SQ_BKT) => self.parse_array(),
+
+            Some(TokType::PUNC_OPEN_CRL_BRC) => self.parse_block().map(ExprKind::BLOCK),
+
+            Some(TokType::KW_IF) => self.parse_if(),
+
+            Some(TokType::KW_MATCH) => self.parse_match(),
+
+            Some(TokType::KW_WHILE | TokType::KW_LOOP | TokType::KW_FOR) => self.parse_loop(None),
+
+            Some(TokType::LABEL(_)) => self.parse_labelled_loop(),
+
+            Some(TokType::KW_BREAK) => self.parse_break(),
+
+            Some(TokType::KW_CONTINUE) => self.parse_continue(),
+
+            Some(TokType::KW_RETURN) => {
+                self.bump();
+                self.parse_optional_operand().map(ExprKind::RETURN)
+            }
+
+            _ => Err(self.unexpected("an expression")),
+        }?;
+
+        Ok(Expr {
+            kind,
+            span: start.to(self.prev_span()),
+        })
+    }
+
+    /// Parses a `new` expression, creating an instance of a class
+    ///
+    fn parse_new(&mut self) -> Result<ExprKind, FeoError> {
+        self.bump();
+        let mut class: Vec<Iden> = vec![self.expect_iden()?];
+
+        while self.eat(&TokType::PUNC_DOT) {
+            class.push(self.expect_iden()?);
+        }
+
+        self.expect(TokType::PUNC_OPEN_PAREN)?;
+
+        Ok(ExprKind::NEW {
+            class,
+            args: self.parse_args()?,
+        })
+    }
+
+    /// Parses a parenthesised expression or a tuple
+    ///
+    fn parse_paren(&mut self) -> Result<ExprKind, FeoError> {
+        self.bump();
+
+        if self.eat(&TokType::PUNC_CLS_PAREN) {
+            return Ok(ExprKind::TUPLE(Vec::new()));
+        }
+
+        let first: Expr = self.parse_nested_expr()?;
+
+        // `(a)` is just `a` in parentheses, whereas `(a,)` is a tuple
+        match self.eat(&TokType::PUNC_COMMA) {
+            true => {
+                let mut elems: Vec<Expr> = vec![first];
+                elems.extend(self.parse_args()?);
+                Ok(ExprKind::TUPLE(elems))
+            }
+
+            false => {
+                self.expect(TokType::PUNC_CLS_PAREN)?;
+                Ok(ExprKind::PAREN(Box::new(first)))
+            }
+        }
+    }
+
+    /// Parses an array, either as a list of elements or as `[<value>; <len>]`
+    ///
+    fn parse_array(&mut self) -> Result<ExprKind, FeoError> {
+        self.bump();
+
+        if self.eat(&TokType::PUNC_CLS_SQ_BKT) {
+            return Ok(ExprKind::ARRAY(Vec::new()));
+        }
+
+        let first: Expr = self.parse_nested_expr()?;
+
+        if self.eat(&TokType::PUNC_SEMICOLON) {
+            let len: Expr = self.parse_nested_expr()?;
+            self.expect(TokType::PUNC_CLS_SQ_BKT)?;
+
+            return Ok(ExprKind::ARRAY_REPEAT {
+                value: Box::new(first),
+                len: Box::new(len),
+            });
+        }
+
+        let mut elems: Vec<Expr> = vec![first];
+
+        while self.eat(&TokType::PUNC_COMMA) {
+            if self.check(&TokType::PUNC_CLS_SQ_BKT) {
+                break;
+            }
+
+            elems.push(self.parse_nested_expr()?);
+        }
+
+        self.expect(TokType::PUNC_CLS_SQ_BKT)?;
+
+        Ok(ExprKind::ARRAY(elems))
+    }
+
+    /// Parses the value of a `return` (or similar) expression, unless the next token ends the
+    /// expression
+    ///
+    pub(super) fn parse_optional_operand(&mut self) -> Result<Option<Box<Expr>>, FeoError> {
+        match self.peek() {
+            None
+            | Some(
+                TokType::PUNC_SEMICOLON
+                | TokType::PUNC_COMMA
+                | TokType::PUNC_CLS_CRL_BRC
+                | TokType::PUNC_CLS_PAREN
+                | TokType::PUNC_CLS_SQ_BKT,
+            ) => Ok(None),
+
+            _ => Ok(Some(Box::new(self.parse_expr()?))),
+        }
+    }
+
+    /// Consumes the current token if it is a literal
+    ///
+    pub(super) fn parse_literal(&mut self) -> Option<Literal> {
+        let lit: Literal = match self.peek()? {
+            TokType::LIT_BOOL(b) => Literal::BOOL(*b),
+            TokType::LIT_CHAR(c) => Literal::CHAR(*c),
+            TokType::LIT_FLOAT(n) => Literal::FLOAT(*n),
+            TokType::LIT_INT(n) => Literal::INT(*n),
+            TokType::LIT_STRING(s) => Literal::STRING(s.clone()),
+            _ => return None,
+        };
+
+        self.bump();
+
+        Some(lit)
+    }
+}
@@ -0,0 +1,198 @@
+use super::Parser;
+use crate::ast::{Block, Expr, ExprKind, Iden, MatchArm, Pattern};
+use crate::error::FeoError;
+use crate::lexer::token::TokType;
+use crate::span::Span;
+
+impl Parser {
+    /// Returns `true` if the next tokens start a block-like expression, which ends an
+    /// expression statement without a `;`
+    ///
+    pub(super) fn at_block_like(&self) -> bool {
+        matches!(
+            self.peek(),
+            Some(
+                TokType::PUNC_OPEN_CRL_BRC
+                    | TokType::KW_IF
+                    | TokType::KW_WHILE
+                    | TokType::KW_LOOP
+                    | TokType::KW_FOR
+                    | TokType::KW_MATCH
+                    | TokType::LABEL(_)
+            )
+        )
+    }
+
+    /// Parses an expression in which a `{` ends the expression instead of opening a struct
+    /// literal, i.e. the condition of an `if` or `while`, the iterable of a `for` or the value
+    /// of a `match`
+    ///
+    fn parse_cond(&mut self) -> Result<Expr, FeoError> {
+        let no_struct_literal: bool = std::mem::replace(&mut self.no_struct_literal, true);
+        let cond = self.parse_expr();
+        self.no_struct_literal = no_struct_literal;
+
+        cond
+    }
+
+    /// Parses `if <cond> <block> [else (<block> | <if>)]`
+    ///
+    pub(super) fn parse_if(&mut self) -> Result<ExprKind, FeoError> {
+        self.expect(TokType::KW_IF)?;
+
+        let cond: Expr = self.parse_cond()?;
+        let then: Block = self.parse_block()?;
+
+        let els: Option<Box<Expr>> = match self.eat(&TokType::KW_ELSE) {
+            true => {
+                let start: Span = self.span();
+
+                let kind: ExprKind = match self.check(&TokType::KW_IF) {
+                    // each `else if` is nested in the one before it
+                    true => self.nested(Self::parse_if)?,
+                    false => ExprKind::BLOCK(self.parse_block()?),
+                };
+
+                Some(Box::new(Expr {
+                    kind,
+                    span: start.to(self.prev_span()),
+                }))
+            }
+
+            false => None,
+        };
+
+        Ok(ExprKind::IF {
+            cond: Box::new(cond),
+            then,
+            els,
+        })
+    }
+
+    /// Parses `while <cond> <block>`, `loop <block>` or `for <pattern> in <expr> <block>`
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - the loop's label, if it has one (e.g. `'outer: loop { ... }`)
+    ///
+    pub(super) fn parse_loop(&mut self, label: Option<Iden>) -> Result<ExprKind, FeoError> {
+        let kind: ExprKind = match self.bump() {
+            Some(TokType::KW_WHILE) => {
+                let cond: Expr = self.parse_cond()?;
+
+                ExprKind::WHILE {
+                    cond: Box::new(cond),
+                    body: self.parse_block()?,
+                    label,
+                }
+            }
+
+            Some(TokType::KW_LOOP) => ExprKind::LOOP {
+                body: self.parse_block()?,
+                label,
+            },
+
+            Some(TokType::KW_FOR) => {
+                let pattern = self.parse_pattern()?;
+                self.expect(TokType::KW_IN)?;
+                let iter: Expr = self.parse_cond()?;
+
+                ExprKind::FOR {
+                    pattern: Box::new(pattern),
+                    iter: Box::new(iter),
+                    body: self.parse_block()?,
+                    label,
+                }
+            }
+
+            _ => {
+                self.pos -= 1;
+                return Err(self.unexpected("`while`, `loop` or `for`"));
+            }
+        };
+
+        Ok(kind)
+    }
+
+    /// Parses `'<label>: <loop>`
+    ///
+    pub(super) fn parse_labelled_loop(&mut self) -> Result<ExprKind, FeoError> {
+        let label: Iden = self.expect_label()?;
+        self.expect(TokType::PUNC_COLON)?;
+
+        self.parse_loop(Some(label))
+    }
+
+    /// Parses `break ['<label>] [<expr>]`
+    ///
+    pub(super) fn parse_break(&mut self) -> Result<ExprKind, FeoError> {
+        self.expect(TokType::KW_BREAK)?;
+
+        let label: Option<Iden> = match self.peek() {
+            Some(TokType::LABEL(_)) => Some(self.expect_label()?),
+            _ => None,
+        };
+
+        Ok(ExprKind::BREAK {
+            label,
+            value: self.parse_optional_operand()?,
+        })
+    }
+
+    /// Parses `continue ['<label>]`
+    ///
+    pub(super) fn parse_continue(&mut self) -> Result<ExprKind, FeoError> {
+        self.expect(TokType::KW_CONTINUE)?;
+
+        let label: Option<Iden> = match self.peek() {
+            Some(TokType::LABEL(_)) => Some(self.expect_label()?),
+            _ => None,
+        };
+
+        Ok(ExprKind::CONTINUE { label })
+    }
+
+    /// Parses `match <expr> { <arm>, ... }`. The `,` after an arm is optional if its body is
+    /// block-like
+    ///
+    pub(super) fn parse_match(&mut self) -> Result<ExprKind, FeoError> {
+        self.expect(TokType::KW_MATCH)?;
+
+        let scrutinee: Expr = self.parse_cond()?;
+
+        self.expect(TokType::PUNC_OPEN_CRL_BRC)?;
+        let mut arms: Vec<MatchArm> = Vec::new();
+
+        while !self.eat(&TokType::PUNC_CLS_CRL_BRC) {
+            let start: Span = self.span();
+            let pattern: Pattern = self.parse_pattern()?;
+
+            let guard: Option<Expr> = match self.eat(&TokType::KW_IF) {
+                true => Some(self.parse_nested_expr()?),
+                false => None,
+            };
+
+            self.expect(TokType::OP_FAT_ARW)?;
+            let body: Expr = self.parse_nested_expr()?;
+
+            arms.push(MatchArm {
+                span: start.to(body.span),
+                pattern,
+                guard,
+                body,
+            });
+
+            if !self.eat(&TokType::PUNC_COMMA)
+                && !self.check(&TokType::PUNC_CLS_CRL_BRC)
+                && !arms.last().is_some_and(|arm| arm.body.is_block_like())
+            {
+                return Err(self.unexpected("`,` or `}`"));
+            }
+        }
+
+        Ok(ExprKind::MATCH {
+            scrutinee: Box::new(scrutinee),
+            arms,
+        })
+    }
+}
@@ -0,0 +1,67 @@
+use super::Parser;
+use crate::ast::{Iden, ImportKind, ImportTree};
+use crate::error::FeoError;
+use crate::lexer::token::TokType;
+use crate::span::Span;
+
+impl Parser {
+    /// Parses `import <tree>;`
+    ///
+    pub(super) fn parse_import(&mut self) -> Result<ImportTree, FeoError> {
+        self.expect(TokType::KW_IMPORT)?;
+
+        let tree: ImportTree = self.parse_import_tree()?;
+        self.expect(TokType::PUNC_SEMICOLON)?;
+
+        Ok(tree)
+    }
+
+    /// Parses `<name>.<name>... [as <alias>]` or `<name>.<name>... .{<tree>, ...} [as <alias>]`
+    ///
+    fn parse_import_tree(&mut self) -> Result<ImportTree, FeoError> {
+        let start: Span = self.span();
+        let mut path: Vec<Iden> = vec![self.expect_iden()?];
+
+        while self.eat(&TokType::PUNC_DOT) {
+            if self.eat(&TokType::PUNC_OPEN_CRL_BRC) {
+                let mut trees: Vec<ImportTree> = Vec::new();
+
+                while !self.eat(&TokType::PUNC_CLS_CRL_BRC) {
+                    trees.push(self.parse_import_tree()?);
+
+                    if !self.eat(&TokType::PUNC_COMMA) {
+                        self.expect(TokType::PUNC_CLS_CRL_BRC)?;
+                        break;
+                    }
+                }
+
+                let alias: Option<Iden> = self.parse_import_alias()?;
+
+                return Ok(ImportTree {
+                    path,
+                    kind: ImportKind::GROUP { trees, alias },
+                    span: start.to(self.prev_span()),
+                });
+            }
+
+            path.push(self.expect_iden()?);
+        }
+
+        let alias: Option<Iden> = self.parse_import_alias()?;
+
+        Ok(ImportTree {
+            path,
+            kind: ImportKind::SINGLE(alias),
+            span: start.to(self.prev_span()),
+        })
+    }
+
+    /// Parses an optional `as <alias>`
+    ///
+    fn parse_import_alias(&mut self) -> Result<Option<Iden>, FeoError> {
+        match self.eat(&TokType::KW_AS) {
+            true => Ok(Some(self.expect_iden()?)),
+            false => Ok(None),
+        }
+    }
+}
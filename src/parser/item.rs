@@ -0,0 +1,367 @@
+use super::Parser;
+use crate::ast::{
+    BindingKind, Block, EnumDecl, Field, FnDecl, GenericParam, Iden, Item, ItemKind, Member, Param,
+    StructDecl, TypeAlias, VarDecl, Variant, VariantKind,
+};
+use crate::error::FeoError;
+use crate::lexer::token::TokType;
+use crate::span::Span;
+
+/// Whether a function declaration has a body
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub(super) enum FnBody {
+    REQUIRED,
+    NONE,
+    OPTIONAL,
+}
+
+impl Parser {
+    pub(super) fn parse_item(&mut self) -> Result<Item, FeoError> {
+        let start: Span = self.span();
+        let is_public: bool = self.eat(&TokType::KW_PUBLIC);
+
+        let kind: ItemKind = match self.peek() {
+            Some(TokType::KW_CONST | TokType::KW_LET | TokType::KW_VAR) => {
+                ItemKind::VAR(self.parse_var_decl()?)
+            }
+
+            Some(TokType::KW_FUNC | TokType::KW_EXTERN) => {
+                ItemKind::FUNC(self.parse_fn_decl(FnBody::REQUIRED)?)
+            }
+
+            Some(TokType::KW_STRUCT) => ItemKind::STRUCT(self.parse_struct_decl()?),
+
+            Some(TokType::KW_ENUM) => ItemKind::ENUM(self.parse_enum_decl()?),
+
+            Some(TokType::KW_ABSTRACT | TokType::KW_FINAL | TokType::KW_CLASS) => {
+                ItemKind::CLASS(self.parse_class_decl()?)
+            }
+
+            Some(TokType::KW_PROTOCOL) => ItemKind::PROTOCOL(self.parse_protocol_decl()?),
+
+            Some(TokType::KW_LIB) => {
+                self.bump();
+                let name = self.expect_iden()?;
+                self.expect(TokType::PUNC_SEMICOLON)?;
+
+                ItemKind::LIB(name)
+            }
+
+            Some(TokType::KW_IMPORT) => ItemKind::IMPORT(self.parse_import()?),
+
+            Some(TokType::KW_ALIAS) => {
+                self.bump();
+                ItemKind::ALIAS(self.parse_type_alias()?)
+            }
+
+            Some(TokType::KW_TYPE) => {
+                self.bump();
+                ItemKind::TYPE(self.parse_type_alias()?)
+            }
+
+            _ => return Err(self.unexpected("an item")),
+        };
+
+        Ok(Item {
+            kind,
+            is_public,
+            span: start.to(self.prev_span()),
+        })
+    }
+
+    /// Parses `let|var|const <pattern> [: <type>] [= <expr>];`
+    ///
+    pub(super) fn parse_var_decl(&mut self) -> Result<VarDecl, FeoError> {
+        let binding: BindingKind = match self.peek() {
+            Some(TokType::KW_CONST) => BindingKind::CONST,
+            Some(TokType::KW_LET) => BindingKind::LET,
+            Some(TokType::KW_VAR) => BindingKind::VAR,
+            _ => return Err(self.unexpected("`let`, `var` or `const`")),
+        };
+
+        let keyword: Span = self.span();
+        self.bump();
+
+        let pattern = self.parse_pattern()?;
+
+        let ty = match self.eat(&TokType::PUNC_COLON) {
+            true => Some(self.parse_type()?),
+            false => None,
+        };
+
+        let value = match self.eat(&TokType::OP_ASSIGN) {
+            true => Some(self.parse_expr()?),
+            false => None,
+        };
+
+        self.expect(TokType::PUNC_SEMICOLON)?;
+
+        Ok(VarDecl {
+            binding,
+            keyword,
+            pattern,
+            ty,
+            value,
+        })
+    }
+
+    /// Parses `[extern] func <name>[<generics>](<params>) [-> <type>] (<block> | ;)`
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - whether the function must, must not or may have a body rather than ending
+    ///   in `;` (`extern` functions never do)
+    ///
+    pub(super) fn parse_fn_decl(&mut self, body: FnBody) -> Result<FnDecl, FeoError> {
+        let is_extern: bool = self.eat(&TokType::KW_EXTERN);
+        self.expect(TokType::KW_FUNC)?;
+
+        let name = self.expect_iden()?;
+        let generics: Vec<GenericParam> = self.parse_generic_params()?;
+        let params: Vec<Param> = self.parse_params()?;
+
+        let ret = match self.eat(&TokType::OP_THIN_ARW) {
+            true => Some(self.parse_type()?),
+            false => None,
+        };
+
+        let has_body: bool = match body {
+            _ if is_extern => false,
+            FnBody::REQUIRED => true,
+            FnBody::NONE => false,
+            FnBody::OPTIONAL => self.check(&TokType::PUNC_OPEN_CRL_BRC),
+        };
+
+        let body: Option<Block> = if has_body {
+            Some(self.parse_block()?)
+        } else {
+            self.expect(TokType::PUNC_SEMICOLON)?;
+            None
+        };
+
+        Ok(FnDecl {
+            name,
+            generics,
+            params,
+            ret,
+            body,
+            is_extern,
+        })
+    }
+
+    /// Parses a parenthesised, comma-separated list of parameters
+    ///
+    pub(super) fn parse_params(&mut self) -> Result<Vec<Param>, FeoError> {
+        self.expect(TokType::PUNC_OPEN_PAREN)?;
+        let mut params: Vec<Param> = Vec::new();
+
+        while !self.eat(&TokType::PUNC_CLS_PAREN) {
+            params.push(self.parse_param()?);
+
+            if !self.eat(&TokType::PUNC_COMMA) {
+                self.expect(TokType::PUNC_CLS_PAREN)?;
+                break;
+            }
+        }
+
+        Ok(params)
+    }
+
+    /// Parses `<pattern>: <type> [= <expr>]`
+    ///
+    fn parse_param(&mut self) -> Result<Param, FeoError> {
+        let start: Span = self.span();
+        let pattern = self.parse_pattern()?;
+
+        self.expect(TokType::PUNC_COLON)?;
+        let ty = self.parse_type()?;
+
+        let default = match self.eat(&TokType::OP_ASSIGN) {
+            true => Some(self.parse_expr()?),
+            false => None,
+        };
+
+        Ok(Param {
+            pattern,
+            ty,
+            default,
+            span: start.to(self.prev_span()),
+        })
+    }
+
+    /// Parses `<name>[<generics>] = <type>;`, after `alias` or `type`
+    ///
+    fn parse_type_alias(&mut self) -> Result<TypeAlias, FeoError> {
+        let name = self.expect_iden()?;
+        let generics: Vec<GenericParam> = self.parse_generic_params()?;
+
+        self.expect(TokType::OP_ASSIGN)?;
+        let ty = self.parse_type()?;
+        self.expect(TokType::PUNC_SEMICOLON)?;
+
+        Ok(TypeAlias { name, generics, ty })
+    }
+
+    /// Parses `struct <name>[<generics>] [: <protocols>] { <fields> <members> }`
+    ///
+    fn parse_struct_decl(&mut self) -> Result<StructDecl, FeoError> {
+        self.expect(TokType::KW_STRUCT)?;
+
+        let name = self.expect_iden()?;
+        let generics: Vec<GenericParam> = self.parse_generic_params()?;
+        let protocols: Vec<Iden> = self.parse_conformance()?;
+
+        self.expect(TokType::PUNC_OPEN_CRL_BRC)?;
+        let mut fields: Vec<Field> = Vec::new();
+
+        while !self.check(&TokType::PUNC_CLS_CRL_BRC) && !self.at_member() {
+            fields.push(self.parse_field(true)?);
+
+            if !self.eat(&TokType::PUNC_COMMA) {
+                break;
+            }
+        }
+
+        let members: Vec<Member> = self.parse_members(false);
+
+        Ok(StructDecl {
+            name,
+            generics,
+            protocols,
+            fields,
+            members,
+        })
+    }
+
+    /// Parses `[public] <name>: <type>`
+    ///
+    /// # Arguments
+    ///
+    /// * `allow_public` - whether the field may be marked `public` (not so in enum variants,
+    ///   whose fields are as visible as the enum itself)
+    ///
+    fn parse_field(&mut self, allow_public: bool) -> Result<Field, FeoError> {
+        let start: Span = self.span();
+
+        let is_public: bool = match self.check(&TokType::KW_PUBLIC) {
+            true if allow_public => self.eat(&TokType::KW_PUBLIC),
+            true => return Err(self.unexpected("a field name")),
+            false => false,
+        };
+
+        let name = self.expect_iden()?;
+        self.expect(TokType::PUNC_COLON)?;
+        let ty = self.parse_type()?;
+
+        Ok(Field {
+            name,
+            ty,
+            is_public,
+            span: start.to(self.prev_span()),
+        })
+    }
+
+    /// Parses `enum <name>[<generics>] [: <protocols>] { <variants> <members> }`
+    ///
+    fn parse_enum_decl(&mut self) -> Result<EnumDecl, FeoError> {
+        self.expect(TokType::KW_ENUM)?;
+
+        let name = self.expect_iden()?;
+        let generics: Vec<GenericParam> = self.parse_generic_params()?;
+        let protocols: Vec<Iden> = self.parse_conformance()?;
+
+        self.expect(TokType::PUNC_OPEN_CRL_BRC)?;
+        let mut variants: Vec<Variant> = Vec::new();
+
+        while !self.check(&TokType::PUNC_CLS_CRL_BRC) && !self.at_member() {
+            variants.push(self.parse_variant()?);
+
+            if !self.eat(&TokType::PUNC_COMMA) {
+                break;
+            }
+        }
+
+        let members: Vec<Member> = self.parse_members(false);
+
+        Ok(EnumDecl {
+            name,
+            generics,
+            protocols,
+            variants,
+            members,
+        })
+    }
+
+    /// Parses an optional conformance clause, `: <name>, <name>, ...`
+    ///
+    pub(super) fn parse_conformance(&mut self) -> Result<Vec<Iden>, FeoError> {
+        let mut names: Vec<Iden> = Vec::new();
+
+        if self.eat(&TokType::PUNC_COLON) {
+            names.push(self.expect_iden()?);
+
+            while self.eat(&TokType::PUNC_COMMA) {
+                names.push(self.expect_iden()?);
+            }
+        }
+
+        Ok(names)
+    }
+
+    /// Parses `<name> [(<types>) | { <fields> }] [= <expr>]`
+    ///
+    fn parse_variant(&mut self) -> Result<Variant, FeoError> {
+        let start: Span = self.span();
+        let name = self.expect_iden()?;
+
+        let kind: VariantKind = match self.peek() {
+            Some(TokType::PUNC_OPEN_PAREN) => {
+                self.bump();
+                let mut types = Vec::new();
+
+                while !self.eat(&TokType::PUNC_CLS_PAREN) {
+                    types.push(self.parse_type()?);
+
+                    if !self.eat(&TokType::PUNC_COMMA) {
+                        self.expect(TokType::PUNC_CLS_PAREN)?;
+                        break;
+                    }
+                }
+
+                VariantKind::TUPLE(types)
+            }
+
+            Some(TokType::PUNC_OPEN_CRL_BRC) => {
+                self.bump();
+                let mut fields = Vec::new();
+
+                while !self.eat(&TokType::PUNC_CLS_CRL_BRC) {
+                    fields.push(self.parse_field(false)?);
+
+                    if !self.eat(&TokType::PUNC_COMMA) {
+                        self.expect(TokType::PUNC_CLS_CRL_BRC)?;
+                        break;
+                    }
+                }
+
+                VariantKind::STRUCT(fields)
+            }
+
+            _ => VariantKind::UNIT,
+        };
+
+        let discriminant = match self.eat(&TokType::OP_ASSIGN) {
+            true => Some(self.parse_expr()?),
+            false => None,
+        };
+
+        Ok(Variant {
+            name,
+            kind,
+            discriminant,
+            span: start.to(self.prev_span()),
+        })
+    }
+}
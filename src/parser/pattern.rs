@@ -0,0 +1,183 @@
+use super::Parser;
+use crate::ast::{FieldPattern, Iden, Literal, Pattern, PatternKind};
+use crate::error::FeoError;
+use crate::lexer::token::TokType;
+use crate::span::Span;
+
+impl Parser {
+    /// Parses a pattern, including or-patterns `<pattern> | <pattern> | ...`
+    ///
+    pub(super) fn parse_pattern(&mut self) -> Result<Pattern, FeoError> {
+        self.nested(Self::parse_or_pattern)
+    }
+
+    fn parse_or_pattern(&mut self) -> Result<Pattern, FeoError> {
+        let start: Span = self.span();
+        let first: Pattern = self.parse_single_pattern()?;
+
+        if !self.check(&TokType::OP_PIPE) {
+            return Ok(first);
+        }
+
+        let mut alternatives: Vec<Pattern> = vec![first];
+
+        while self.eat(&TokType::OP_PIPE) {
+            alternatives.push(self.parse_single_pattern()?);
+        }
+
+        Ok(Pattern {
+            kind: PatternKind::OR(alternatives),
+            span: start.to(self.prev_span()),
+        })
+    }
+
+    /// Parses a pattern other than an or-pattern
+    ///
+    fn parse_single_pattern(&mut self) -> Result<Pattern, FeoError> {
+        let start: Span = self.span();
+
+        if let Some(lit) = self.parse_literal() {
+            let is_inclusive: bool = match self.peek() {
+                Some(TokType::OP_RANGE) => false,
+                Some(TokType::OP_RANGE_INCL) => true,
+                _ => {
+                    return Ok(Pattern {
+                        kind: PatternKind::LITERAL(lit),
+                        span: start,
+                    })
+                }
+            };
+
+            self.bump();
+
+            let end: Literal = match self.parse_literal() {
+                Some(end) => end,
+                None => return Err(self.unexpected("a literal")),
+            };
+
+            return Ok(Pattern {
+                kind: PatternKind::RANGE {
+                    start: lit,
+                    end,
+                    is_inclusive,
+                },
+                span: start.to(self.prev_span()),
+            });
+        }
+
+        let kind: PatternKind = match self.peek() {
+            Some(TokType::PUNC_UNDERSCORE) => {
+                self.bump();
+                PatternKind::WILDCARD
+            }
+
+            Some(TokType::PUNC_OPEN_PAREN) => {
+                self.bump();
+                let (mut elems, has_comma) = self.parse_pattern_list()?;
+
+                // `(p)` is just `p` in parentheses, whereas `(p,)` is a tuple
+                match elems.len() {
+                    1 if !has_comma => return Ok(elems.remove(0)),
+                    _ => PatternKind::TUPLE(elems),
+                }
+            }
+
+            Some(TokType::IDEN(_)) => {
+                let mut path: Vec<Iden> = vec![self.expect_iden()?];
+
+                while self.eat(&TokType::PUNC_DOT) {
+                    path.push(self.expect_iden()?);
+                }
+
+                match self.peek() {
+                    Some(TokType::PUNC_OPEN_PAREN) => {
+                        self.bump();
+
+                        PatternKind::VARIANT {
+                            path,
+                            elems: Some(self.parse_pattern_list()?.0),
+                        }
+                    }
+
+                    Some(TokType::PUNC_OPEN_CRL_BRC) => self.parse_struct_pattern(path)?,
+
+                    _ if path.len() == 1 => PatternKind::BINDING(path.remove(0)),
+
+                    _ => PatternKind::VARIANT { path, elems: None },
+                }
+            }
+
+            _ => return Err(self.unexpected("a pattern")),
+        };
+
+        Ok(Pattern {
+            kind,
+            span: start.to(self.prev_span()),
+        })
+    }
+
+    /// Parses a comma-separated list of patterns up to and including the closing `)`,
+    /// returning whether the list contained a comma
+    ///
+    fn parse_pattern_list(&mut self) -> Result<(Vec<Pattern>, bool), FeoError> {
+        let mut patterns: Vec<Pattern> = Vec::new();
+        let mut has_comma: bool = false;
+
+        while !self.eat(&TokType::PUNC_CLS_PAREN) {
+            patterns.push(self.parse_pattern()?);
+
+            if !self.eat(&TokType::PUNC_COMMA) {
+                self.expect(TokType::PUNC_CLS_PAREN)?;
+                break;
+            }
+
+            has_comma = true;
+        }
+
+        Ok((patterns, has_comma))
+    }
+
+    /// Parses the `{ <name>[: <pattern>], ... [..] }` part of a struct pattern
+    ///
+    fn parse_struct_pattern(&mut self, path: Vec<Iden>) -> Result<PatternKind, FeoError> {
+        self.expect(TokType::PUNC_OPEN_CRL_BRC)?;
+        let mut fields: Vec<FieldPattern> = Vec::new();
+        let mut has_rest: bool = false;
+
+        while !self.eat(&TokType::PUNC_CLS_CRL_BRC) {
+            // `..` can only come last
+            if self.eat(&TokType::OP_RANGE) {
+                has_rest = true;
+                self.expect(TokType::PUNC_CLS_CRL_BRC)?;
+                break;
+            }
+
+            let name: Iden = self.expect_iden()?;
+
+            let pattern: Pattern = match self.eat(&TokType::PUNC_COLON) {
+                true => self.parse_pattern()?,
+                false => Pattern {
+                    kind: PatternKind::BINDING(name.clone()),
+                    span: name.span,
+                },
+            };
+
+            fields.push(FieldPattern {
+                span: name.span.to(pattern.span),
+                name,
+                pattern,
+            });
+
+            if !self.eat(&TokType::PUNC_COMMA) {
+                self.expect(TokType::PUNC_CLS_CRL_BRC)?;
+                break;
+            }
+        }
+
+        Ok(PatternKind::STRUCT {
+            path,
+            fields,
+            has_rest,
+        })
+    }
+}
@@ -0,0 +1,53 @@
+use super::item::FnBody;
+use super::Parser;
+use crate::ast::{AssocType, Iden, ProtocolDecl, ProtocolMember, ProtocolMemberKind};
+use crate::error::FeoError;
+use crate::lexer::token::TokType;
+use crate::span::Span;
+
+impl Parser {
+    /// Parses `protocol <name> { <members> }`, where each member is an associated type
+    /// `type <name> [: <protocol> + ...];` or a method, with a body only if it has a default
+    /// implementation
+    ///
+    pub(super) fn parse_protocol_decl(&mut self) -> Result<ProtocolDecl, FeoError> {
+        self.expect(TokType::KW_PROTOCOL)?;
+
+        let name: Iden = self.expect_iden()?;
+
+        self.expect(TokType::PUNC_OPEN_CRL_BRC)?;
+        let mut members: Vec<ProtocolMember> = Vec::new();
+
+        while !self.eat(&TokType::PUNC_CLS_CRL_BRC) {
+            let start: Span = self.span();
+
+            let kind: ProtocolMemberKind = match self.peek() {
+                Some(TokType::KW_TYPE) => {
+                    self.bump();
+
+                    let name: Iden = self.expect_iden()?;
+                    let bounds: Vec<Iden> = match self.eat(&TokType::PUNC_COLON) {
+                        true => self.parse_bounds()?,
+                        false => Vec::new(),
+                    };
+                    self.expect(TokType::PUNC_SEMICOLON)?;
+
+                    ProtocolMemberKind::TYPE(AssocType { name, bounds })
+                }
+
+                Some(TokType::KW_FUNC) => {
+                    ProtocolMemberKind::METHOD(self.parse_fn_decl(FnBody::OPTIONAL)?)
+                }
+
+                _ => return Err(self.unexpected("`type`, `func` or `}`")),
+            };
+
+            members.push(ProtocolMember {
+                kind,
+                span: start.to(self.prev_span()),
+            });
+        }
+
+        Ok(ProtocolDecl { name, members })
+    }
+}
@@ -0,0 +1,97 @@
+use super::Parser;
+use crate::ast::{Block, Expr, Stmt, StmtKind};
+use crate::error::FeoError;
+use crate::lexer::token::TokType;
+use crate::span::Span;
+
+impl Parser {
+    /// Parses `{ <stmt>* [<expr>] }`
+    ///
+    /// A statement containing a syntax error is recorded as an `ERROR` statement. A block
+    /// that runs into the end of the file or an item keyword is missing its `}`, which is
+    /// recorded as an error, and ends there
+    ///
+    pub(super) fn parse_block(&mut self) -> Result<Block, FeoError> {
+        let start: Span = self.expect(TokType::PUNC_OPEN_CRL_BRC)?;
+        let no_struct_literal: bool = std::mem::replace(&mut self.no_struct_literal, false);
+        let mut stmts: Vec<Stmt> = Vec::new();
+        let mut tail = None;
+
+        while !self.check(&TokType::PUNC_CLS_CRL_BRC) {
+            if self.is_at_end() || self.at_item_keyword() {
+                self.errors.push(self.unexpected("`}`"));
+                break;
+            }
+
+            // skip empty statements
+            if self.eat(&TokType::PUNC_SEMICOLON) {
+                continue;
+            }
+
+            let stmt_start: usize = self.pos;
+
+            match self.parse_stmt(&mut tail) {
+                Ok(Some(stmt)) => stmts.push(stmt),
+                Ok(None) => (),
+
+                Err(err) => {
+                    let span: Span = self.recover(err, stmt_start, true);
+
+                    stmts.push(Stmt {
+                        kind: StmtKind::ERROR,
+                        span,
+                    });
+                }
+            }
+        }
+
+        // the `}`, or the last token before it went missing
+        self.eat(&TokType::PUNC_CLS_CRL_BRC);
+        let end: Span = self.prev_span();
+
+        self.no_struct_literal = no_struct_literal;
+
+        Ok(Block {
+            stmts,
+            tail,
+            span: start.to(end),
+        })
+    }
+
+    /// Parses a statement, unless it is an expression followed by the `}` of the block, in
+    /// which case that expression is the block's tail
+    ///
+    /// # Arguments
+    ///
+    /// * `tail` - set to the expression at the end of the block, if there is one
+    ///
+    fn parse_stmt(&mut self, tail: &mut Option<Box<Expr>>) -> Result<Option<Stmt>, FeoError> {
+        let start: Span = self.span();
+
+        let kind: StmtKind = match self.peek() {
+            Some(TokType::KW_CONST | TokType::KW_LET | TokType::KW_VAR) => {
+                StmtKind::VAR(Box::new(self.parse_var_decl()?))
+            }
+
+            _ => {
+                let expr = self.parse_expr_stmt()?;
+
+                if self.eat(&TokType::PUNC_SEMICOLON) {
+                    StmtKind::EXPR(expr)
+                } else if self.check(&TokType::PUNC_CLS_CRL_BRC) {
+                    *tail = Some(Box::new(expr));
+                    return Ok(None);
+                } else if expr.is_block_like() {
+                    StmtKind::EXPR(expr)
+                } else {
+                    return Err(self.unexpected("`;`"));
+                }
+            }
+        };
+
+        Ok(Some(Stmt {
+            kind,
+            span: start.to(self.prev_span()),
+        }))
+    }
+}
@@ -0,0 +1,190 @@
+use super::Parser;
+use crate::ast::{GenericParam, Iden, Type, TypeKind};
+use crate::error::FeoError;
+use crate::lexer::token::TokType;
+use crate::span::Span;
+
+impl Parser {
+    /// Parses a type, followed by any number of `?` making it optional
+    ///
+    pub(super) fn parse_type(&mut self) -> Result<Type, FeoError> {
+        self.nested(Self::parse_optional_type)
+    }
+
+    fn parse_optional_type(&mut self) -> Result<Type, FeoError> {
+        let start: Span = self.span();
+        let mut ty: Type = self.parse_base_type()?;
+
+        while self.eat(&TokType::OP_TERNARY) {
+            ty = Type {
+                kind: TypeKind::OPTIONAL(Box::new(ty)),
+                span: start.to(self.prev_span()),
+            };
+        }
+
+        Ok(ty)
+    }
+
+    /// Parses a type other than an optional type
+    ///
+    fn parse_base_type(&mut self) -> Result<Type, FeoError> {
+        let start: Span = self.span();
+
+        if let Some(kind) = self.peek().and_then(primitive_type) {
+            self.bump();
+            return Ok(Type { kind, span: start });
+        }
+
+        let kind: TypeKind = match self.peek() {
+            Some(TokType::IDEN(_)) => {
+                let mut path: Vec<Iden> = vec![self.expect_iden()?];
+
+                while self.eat(&TokType::PUNC_DOT) {
+                    path.push(self.expect_iden()?);
+                }
+
+                let mut args: Vec<Type> = Vec::new();
+
+                if self.eat(&TokType::OP_LESS) {
+                    while !self.eat(&TokType::OP_GRTR) {
+                        args.push(self.parse_type()?);
+
+                        if !self.eat(&TokType::PUNC_COMMA) {
+                            self.expect(TokType::OP_GRTR)?;
+                            break;
+                        }
+                    }
+                }
+
+                TypeKind::NAMED { path, args }
+            }
+
+            Some(TokType::PUNC_OPEN_SQ_BKT) => {
+                self.bump();
+                let elem: Box<Type> = Box::new(self.parse_type()?);
+
+                let kind: TypeKind = match self.eat(&TokType::PUNC_SEMICOLON) {
+                    true => TypeKind::FIXED_ARRAY {
+                        elem,
+                        len: Box::new(self.parse_nested_expr()?),
+                    },
+                    false => TypeKind::ARRAY(elem),
+                };
+
+                self.expect(TokType::PUNC_CLS_SQ_BKT)?;
+                kind
+            }
+
+            Some(TokType::PUNC_OPEN_PAREN) => {
+                self.bump();
+                let (mut types, has_comma) = self.parse_type_list()?;
+
+                // `(T)` is just `T` in parentheses, whereas `(T,)` is a tuple
+                match types.len() {
+                    1 if !has_comma => return Ok(types.remove(0)),
+                    _ => TypeKind::TUPLE(types),
+                }
+            }
+
+            Some(TokType::KW_FUNC) => {
+                self.bump();
+                self.expect(TokType::PUNC_OPEN_PAREN)?;
+                let params: Vec<Type> = self.parse_type_list()?.0;
+
+                let ret: Option<Box<Type>> = match self.eat(&TokType::OP_THIN_ARW) {
+                    true => Some(Box::new(self.parse_type()?)),
+                    false => None,
+                };
+
+                TypeKind::FUNC { params, ret }
+            }
+
+            _ => return Err(self.unexpected("a type")),
+        };
+
+        Ok(Type {
+            kind,
+            span: start.to(self.prev_span()),
+        })
+    }
+
+    /// Parses a comma-separated list of types up to and including the closing `)`, returning
+    /// whether the list contained a comma
+    ///
+    pub(super) fn parse_type_list(&mut self) -> Result<(Vec<Type>, bool), FeoError> {
+        let mut types: Vec<Type> = Vec::new();
+        let mut has_comma: bool = false;
+
+        while !self.eat(&TokType::PUNC_CLS_PAREN) {
+            types.push(self.parse_type()?);
+
+            if !self.eat(&TokType::PUNC_COMMA) {
+                self.expect(TokType::PUNC_CLS_PAREN)?;
+                break;
+            }
+
+            has_comma = true;
+        }
+
+        Ok((types, has_comma))
+    }
+
+    /// Parses an optional list of generic parameters, `<T, U: A + B, ...>`
+    ///
+    pub(super) fn parse_generic_params(&mut self) -> Result<Vec<GenericParam>, FeoError> {
+        let mut params: Vec<GenericParam> = Vec::new();
+
+        if !self.eat(&TokType::OP_LESS) {
+            return Ok(params);
+        }
+
+        while !self.eat(&TokType::OP_GRTR) {
+            let start: Span = self.span();
+            let name: Iden = self.expect_iden()?;
+
+            let bounds: Vec<Iden> = match self.eat(&TokType::PUNC_COLON) {
+                true => self.parse_bounds()?,
+                false => Vec::new(),
+            };
+
+            params.push(GenericParam {
+                name,
+                bounds,
+                span: start.to(self.prev_span()),
+            });
+
+            if !self.eat(&TokType::PUNC_COMMA) {
+                self.expect(TokType::OP_GRTR)?;
+                break;
+            }
+        }
+
+        Ok(params)
+    }
+
+    /// Parses a list of protocols joined by `+`, e.g. `Printable + Eq`
+    ///
+    pub(super) fn parse_bounds(&mut self) -> Result<Vec<Iden>, FeoError> {
+        let mut bounds: Vec<Iden> = vec![self.expect_iden()?];
+
+        while self.eat(&TokType::OP_PLUS) {
+            bounds.push(self.expect_iden()?);
+        }
+
+        Ok(bounds)
+    }
+}
+
+/// Returns the primitive type named by a keyword, if any
+///
+fn primitive_type(tok_type: &TokType) -> Option<TypeKind> {
+    match tok_type {
+        TokType::KW_BOOL => Some(TypeKind::BOOL),
+        TokType::KW_CHAR => Some(TypeKind::CHAR),
+        TokType::KW_FLOAT => Some(TypeKind::FLOAT),
+        TokType::KW_INT => Some(TypeKind::INT),
+        TokType::KW_STRING => Some(TypeKind::STRING),
+        TokType::KW_UINT => Some(TypeKind::UINT),
+        _ => None,
+    }
+}
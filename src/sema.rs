@@ -0,0 +1,14 @@
+//! Semantic analysis
+//!
+//! The passes that check a parsed module, each walking the syntax tree with a `Visitor` and
+//! reporting every problem it finds rather than stopping at the first
+
+pub mod check;
+pub mod resolve;
+pub mod types;
+pub mod value;
+
+pub use check::{check, Types};
+pub use resolve::{resolve, Resolution, Symbol, SymbolId, SymbolKind};
+pub use types::Ty;
+pub use value::Value;
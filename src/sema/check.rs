@@ -0,0 +1,586 @@
+//! Type checking
+//!
+//! Works out the type of every expression and binding in a module, and reports each value
+//! whose type does not fit where it is used. A mismatch names both the expected and the
+//! found type, and is reported at the value, with a note pointing at the annotation (or
+//! return type) the expectation comes from
+//!
+//! Types are checked in three passes: the declarations of types and functions are collected
+//! first, then the module's variables are checked in order, and finally every function and
+//! member body. Class hierarchies, and the conformance of each type to the protocols it
+//! declares, are checked once the declarations are collected
+//!
+//! Within a body, the types of bindings without an annotation are inferred by unification:
+//! each unknown type is an inference variable, worked out from every use of the value, so
+//! `var total = 0;` followed by `total += n` with `n: uint` makes `total` a `uint`. Integer
+//! literals that nothing else constrains are `int`s. Checking is bidirectional at function
+//! boundaries: parameters and return types are always annotated, and the type a value is
+//! expected to have flows into it, which also works out the generic arguments of calls
+//!
+//! The values of constants, static fields and the lengths of array types are evaluated at
+//! compile time, from literals, other constants, operators and calls to functions whose
+//! bodies only bind `let`s and evaluate expressions. Integer overflow, division by zero and
+//! anything that can only be evaluated when the program runs are reported, as is a value
+//! that takes too long to work out or is too large to build
+
+use super::types::Ty;
+use super::value::Value;
+use super::{Resolution, SymbolId};
+use crate::ast::{
+    Block, ClassDecl, EnumDecl, FnDecl, Item, ItemKind, Member, MemberKind, Module, Param,
+    ProtocolDecl, ProtocolMemberKind, StmtKind, StructDecl, VarDecl,
+};
+use crate::error::{ErrorId, FeoError};
+use crate::span::Span;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+mod classes;
+mod consts;
+mod defs;
+mod expr;
+mod infer;
+mod mutability;
+mod pattern;
+mod protocols;
+
+pub use defs::{FnSig, TypeDef, VariantDef};
+
+/// The types worked out by the checker
+///
+#[derive(Debug, Default)]
+pub struct Types {
+    /// the type of each expression, by its span
+    pub exprs: HashMap<Span, Ty>,
+
+    /// the type of each variable and parameter
+    pub symbols: HashMap<SymbolId, Ty>,
+
+    /// the user-defined types of the module
+    pub defs: HashMap<SymbolId, TypeDef>,
+
+    /// the signatures of the module's functions
+    pub funcs: HashMap<SymbolId, FnSig>,
+
+    /// the value of each constant that could be evaluated
+    pub consts: HashMap<SymbolId, Value>,
+
+    /// the initial value of each static field, by its type and name
+    pub statics: HashMap<(SymbolId, String), Value>,
+
+    /// how each type implements each protocol it conforms to, by the type and the protocol:
+    /// the type providing each of the protocol's methods (the type, a superclass, or the
+    /// protocol itself for a default), in the order the protocol declares them
+    pub conformances: HashMap<(SymbolId, SymbolId), Vec<(String, SymbolId)>>,
+
+    /// the methods called through a value whose type is a protocol, or a generic parameter
+    /// bound by one, by the span of the method's name: the protocol, and the method's index
+    /// in its witness tables
+    pub dispatch: HashMap<Span, (SymbolId, usize)>,
+}
+
+/// Checks the types of a module, returning them along with any type errors
+///
+/// # Arguments
+///
+/// * `module` - the syntax tree of a file
+/// * `resolution` - the file's names, as returned by `resolve`
+/// * `file` - the file's path, for errors
+///
+pub fn check(
+    module: &Module,
+    resolution: &Resolution,
+    file: &'static Path,
+) -> (Types, Vec<FeoError>) {
+    let mut checker = Checker {
+        file,
+        resolution,
+        errors: Vec::new(),
+        types: Types::default(),
+        aliases: HashMap::new(),
+        bounds: HashMap::new(),
+        bound_checks: Vec::new(),
+        checked_bounds: HashSet::new(),
+        alias_depth: 0,
+        ret: None,
+        loops: Vec::new(),
+        self_ty: None,
+        vars: Vec::new(),
+        trail: Vec::new(),
+        finished: 0,
+        keywords: HashMap::new(),
+        inferred: Vec::new(),
+        const_items: HashMap::new(),
+        fn_decls: HashMap::new(),
+        consts: HashMap::new(),
+        evaluating: Vec::new(),
+        calls: Vec::new(),
+        budget: None,
+        lengths: HashMap::new(),
+        classes: HashMap::new(),
+        protocols: HashMap::new(),
+    };
+
+    checker.collect(module);
+    checker.class_hierarchy(module);
+    checker.conformances(module);
+
+    for item in &module.items {
+        if let ItemKind::VAR(decl) = &item.kind {
+            checker.var_decl(decl);
+        }
+    }
+
+    checker.finish_inference();
+
+    for item in &module.items {
+        checker.item(item);
+        checker.finish_inference();
+    }
+
+    checker.finish_types();
+
+    for (symbol, value) in checker.consts {
+        if let Some(value) = value {
+            checker.types.consts.insert(symbol, value);
+        }
+    }
+
+    (checker.types, checker.errors)
+}
+
+/// The type a value must fit, and the annotation that calls for it, if any
+///
+#[derive(Clone, Copy)]
+struct Expected<'t> {
+    ty: &'t Ty,
+    because: Option<(Span, &'static str)>,
+}
+
+/// A loop enclosing the expression being checked
+///
+struct Loop {
+    label: Option<String>,
+
+    /// whether the loop is a `loop`, which (unlike `while` and `for`) can break with a value
+    has_value: bool,
+
+    /// the type of the values the loop breaks with, once one is found
+    value: Option<Ty>,
+    breaks: bool,
+}
+
+struct Checker<'a> {
+    file: &'static Path,
+    resolution: &'a Resolution,
+    errors: Vec<FeoError>,
+    types: Types,
+
+    /// the declarations of type aliases and associated type bindings
+    aliases: HashMap<SymbolId, &'a crate::ast::TypeAlias>,
+
+    /// the protocols each generic parameter is bound by
+    bounds: HashMap<SymbolId, Vec<Ty>>,
+
+    /// the generic arguments to check against the bounds of their parameters once they are
+    /// worked out: where each is given, its parameter, and the argument
+    bound_checks: Vec<(Span, SymbolId, Ty)>,
+
+    /// the generic arguments already checked, by where they are given and their parameter,
+    /// as a type written in a signature is lowered more than once
+    checked_bounds: HashSet<(Span, SymbolId)>,
+
+    /// the number of aliases being expanded, to catch aliases that refer to themselves
+    alias_depth: usize,
+
+    /// the return type of the function being checked, and the span of its annotation
+    ret: Option<(Ty, Option<Span>)>,
+
+    /// the loops enclosing the expression being checked, innermost last
+    loops: Vec<Loop>,
+
+    /// the type of `self` in the method being checked
+    self_ty: Option<Ty>,
+
+    /// the inference variables, by number
+    vars: Vec<infer::Var>,
+
+    /// the inference variables worked out, in order, so that a failed `fits` can undo them
+    trail: Vec<usize>,
+
+    /// the `let` keyword of each binding declared with one, for the fix that changes it to
+    /// `var`
+    keywords: HashMap<SymbolId, Span>,
+
+    /// the number of inference variables in the items already checked
+    finished: usize,
+
+    /// the bindings without an annotation in the item being checked, by the span of their
+    /// pattern, whose types must be worked out by its end
+    inferred: Vec<(Span, Ty)>,
+
+    /// the declarations of the module's constants, by each name they bind, so that a
+    /// constant can be evaluated where it is used before its declaration
+    const_items: HashMap<SymbolId, &'a VarDecl>,
+
+    /// the declarations of the module's functions, whose calls may be evaluated
+    fn_decls: HashMap<SymbolId, &'a FnDecl>,
+
+    /// the value of each constant evaluated so far, or `None` if it could not be
+    consts: HashMap<SymbolId, Option<Value>>,
+
+    /// the constants being evaluated, to catch those whose values depend on themselves
+    evaluating: Vec<SymbolId>,
+
+    /// the spans of the calls being evaluated, innermost last
+    calls: Vec<Span>,
+
+    /// what is left of the steps and size the evaluation under way may use up
+    budget: Option<consts::Budget>,
+
+    /// the length of each array type or value, by the span of its length expression
+    lengths: HashMap<Span, Option<usize>>,
+
+    /// the declarations of the module's classes, whose modifiers the hierarchy checks need
+    classes: HashMap<SymbolId, &'a ClassDecl>,
+
+    /// the declarations of the module's protocols, whose associated types and default
+    /// methods the conformance checks need
+    protocols: HashMap<SymbolId, &'a ProtocolDecl>,
+}
+
+impl<'a> Checker<'a> {
+    fn error(&mut self, id: ErrorId, span: Span, message: impl Into<String>) {
+        self.errors.push(FeoError::at(id, self.file, span, message));
+    }
+
+    /// Returns `true` if a value of type `found` can be used where `expected` is called for,
+    /// working out any inference variables in either type so that it can. If it cannot, the
+    /// variables are left as they were
+    ///
+    fn fits(&mut self, found: &Ty, expected: &Ty) -> bool {
+        let mark: usize = self.trail.len();
+        let fits: bool = self.unify(&self.resolved(found), &self.resolved(expected));
+
+        if !fits {
+            self.undo(mark);
+        }
+
+        fits
+    }
+
+    fn unify(&mut self, found: &Ty, expected: &Ty) -> bool {
+        match (found, expected) {
+            (Ty::NEVER, _) => true,
+            (found, expected) if found == expected => true,
+            (Ty::INFER(id), ty) | (ty, Ty::INFER(id)) => self.bind(*id, ty),
+            (Ty::ERROR, _) | (_, Ty::ERROR) => true,
+
+            (Ty::ARRAY(a), Ty::ARRAY(b)) | (Ty::FIXED_ARRAY(a, _), Ty::ARRAY(b)) => self.fits(a, b),
+
+            (Ty::FIXED_ARRAY(a, n), Ty::FIXED_ARRAY(b, m)) => n == m && self.fits(a, b),
+            (Ty::RANGE(a), Ty::RANGE(b)) => self.fits(a, b),
+            (Ty::OPTIONAL(a), Ty::OPTIONAL(b)) => self.fits(a, b),
+            (found, Ty::OPTIONAL(b)) => self.fits(found, b),
+
+            (Ty::TUPLE(a), Ty::TUPLE(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| self.fits(a, b))
+            }
+
+            (
+                Ty::FUNC { params, ret },
+                Ty::FUNC {
+                    params: expected_params,
+                    ret: expected_ret,
+                },
+            ) => {
+                params.len() == expected_params.len()
+                    && params
+                        .iter()
+                        .zip(expected_params)
+                        .all(|(a, b)| self.fits(b, a))
+                    && self.fits(ret, expected_ret)
+            }
+
+            (
+                Ty::NAMED { symbol, args, .. },
+                Ty::NAMED {
+                    symbol: expected_symbol,
+                    args: expected_args,
+                    ..
+                },
+            ) => {
+                if symbol == expected_symbol {
+                    return args.iter().zip(expected_args).all(|(a, b)| self.fits(a, b));
+                }
+
+                // a class fits its superclasses, and any type the protocols it conforms to
+                self.ancestors(found).iter().any(|s| match s {
+                    Ty::NAMED { symbol, .. } => symbol == expected_symbol && self.fits(s, expected),
+                    _ => false,
+                })
+            }
+
+            _ => false,
+        }
+    }
+
+    /// Reports a value whose type does not fit where it is used
+    ///
+    /// # Arguments
+    ///
+    /// * `found` - the value's type
+    /// * `expected` - the type called for
+    /// * `span` - the value's span
+    ///
+    fn expect(&mut self, found: &Ty, expected: Expected, span: Span) {
+        if self.fits(found, expected.ty) {
+            return;
+        }
+
+        let message = format!(
+            "Expected `{}`, found `{}`",
+            self.shown(expected.ty),
+            self.shown(found)
+        );
+        let mut err = FeoError::at(ErrorId::ERR_TYPE_MISMATCH, self.file, span, message);
+
+        if let Some((span, note)) = expected.because {
+            err = err.with_note(span, note);
+        }
+
+        self.errors.push(err);
+    }
+
+    fn symbol_of(&self, name: &crate::ast::Iden) -> Option<SymbolId> {
+        self.resolution.defs.get(&name.span).copied()
+    }
+
+    fn item(&mut self, item: &Item) {
+        match &item.kind {
+            ItemKind::FUNC(func) => self.fn_decl(func, None),
+            ItemKind::STRUCT(decl) => self.struct_decl(decl),
+            ItemKind::ENUM(decl) => self.enum_decl(decl),
+            ItemKind::CLASS(decl) => self.class_decl(decl),
+
+            ItemKind::PROTOCOL(decl) => {
+                let self_ty: Option<Ty> = self.symbol_of(&decl.name).map(|s| self.self_type(s));
+
+                for member in &decl.members {
+                    if let ProtocolMemberKind::METHOD(func) = &member.kind {
+                        self.fn_decl(func, self_ty.clone());
+                    }
+                }
+            }
+
+            // variables were checked before the functions that might use them
+            ItemKind::VAR(_)
+            | ItemKind::LIB(_)
+            | ItemKind::IMPORT(_)
+            | ItemKind::ALIAS(_)
+            | ItemKind::TYPE(_)
+            | ItemKind::ERROR => (),
+        }
+    }
+
+    fn struct_decl(&mut self, decl: &StructDecl) {
+        let self_ty: Option<Ty> = self.symbol_of(&decl.name).map(|s| self.self_type(s));
+        self.members(&decl.members, self_ty);
+    }
+
+    fn enum_decl(&mut self, decl: &EnumDecl) {
+        for variant in &decl.variants {
+            if let Some(value) = &variant.discriminant {
+                let found: Ty = self.expr(value, Some(&Ty::INT));
+                let expected = Expected {
+                    ty: &Ty::INT,
+                    because: None,
+                };
+                self.expect(&found, expected, value.span);
+            }
+        }
+
+        let self_ty: Option<Ty> = self.symbol_of(&decl.name).map(|s| self.self_type(s));
+        self.members(&decl.members, self_ty);
+    }
+
+    fn class_decl(&mut self, decl: &ClassDecl) {
+        let self_ty: Option<Ty> = self.symbol_of(&decl.name).map(|s| self.self_type(s));
+        self.members(&decl.members, self_ty);
+    }
+
+    fn members(&mut self, members: &[Member], self_ty: Option<Ty>) {
+        let owner: Option<SymbolId> = match &self_ty {
+            Some(Ty::NAMED { symbol, .. }) => Some(*symbol),
+            _ => None,
+        };
+
+        for member in members {
+            let self_ty: Option<Ty> = match member.modifiers.is_static {
+                true => None,
+                false => self_ty.clone(),
+            };
+
+            match &member.kind {
+                MemberKind::FIELD(field) => {
+                    if let Some(value) = &field.value {
+                        let ty: Ty = self.lower_type(&field.ty);
+                        let found: Ty = self.expr(value, Some(&ty));
+
+                        let expected = Expected {
+                            ty: &ty,
+                            because: Some((field.ty.span, "expected because of this annotation")),
+                        };
+                        self.expect(&found, expected, value.span);
+
+                        if let (true, Some(owner)) = (member.modifiers.is_static, owner) {
+                            self.static_field(owner, field, &ty);
+                        }
+                    }
+                }
+
+                MemberKind::CONSTRUCTOR(constructor) => {
+                    self.body(&constructor.params, None, Some(&constructor.body), self_ty);
+                }
+
+                MemberKind::METHOD(func) => self.fn_decl(func, self_ty),
+                MemberKind::TYPE(_) | MemberKind::ERROR => (),
+            }
+        }
+    }
+
+    fn fn_decl(&mut self, func: &FnDecl, self_ty: Option<Ty>) {
+        self.body(&func.params, func.ret.as_ref(), func.body.as_ref(), self_ty);
+    }
+
+    /// Checks a function or constructor
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - the parameters, whose types and defaults are checked
+    /// * `ret` - the return type, if any is declared
+    /// * `body` - the body, if there is one
+    /// * `self_ty` - the type of `self` within the body, if it can be used
+    ///
+    fn body(
+        &mut self,
+        params: &[Param],
+        ret: Option<&crate::ast::Type>,
+        body: Option<&Block>,
+        self_ty: Option<Ty>,
+    ) {
+        let outer_self: Option<Ty> = std::mem::replace(&mut self.self_ty, self_ty);
+
+        for param in params {
+            let ty: Ty = self.lower_type(&param.ty);
+
+            if let Some(default) = &param.default {
+                let found: Ty = self.expr(default, Some(&ty));
+                let expected = Expected {
+                    ty: &ty,
+                    because: Some((param.ty.span, "expected because of this annotation")),
+                };
+                self.expect(&found, expected, default.span);
+            }
+
+            self.pattern(&param.pattern, &ty);
+        }
+
+        let ret_ty: Ty = ret.map_or(Ty::unit(), |t| self.lower_type(t));
+
+        if let Some(body) = body {
+            let outer_ret = self.ret.replace((ret_ty.clone(), ret.map(|t| t.span)));
+            let outer_loops: Vec<Loop> = std::mem::take(&mut self.loops);
+
+            let found: Ty = self.block(body, Some(&ret_ty));
+            let expected = Expected {
+                ty: &ret_ty,
+                because: ret.map(|t| (t.span, "expected because of the return type")),
+            };
+            self.expect(&found, expected, Self::block_value_span(body));
+
+            self.ret = outer_ret;
+            self.loops = outer_loops;
+        }
+
+        self.self_ty = outer_self;
+    }
+
+    /// Returns the span of the value of a block: its tail, or else its closing `}`
+    ///
+    fn block_value_span(block: &Block) -> Span {
+        match &block.tail {
+            Some(tail) => tail.span,
+            None => {
+                let end: Span = block.span.end();
+                Span::new(end.line, end.col.saturating_sub(1), end.line, end.col)
+            }
+        }
+    }
+
+    fn var_decl(&mut self, decl: &VarDecl) {
+        let annotated: Option<Ty> = decl.ty.as_ref().map(|t| self.lower_type(t));
+        let found: Option<Ty> = decl
+            .value
+            .as_ref()
+            .map(|v| self.expr(v, annotated.as_ref()));
+
+        let ty: Ty = match (annotated, found) {
+            (Some(ty), Some(found)) => {
+                let expected = Expected {
+                    ty: &ty,
+                    because: decl
+                        .ty
+                        .as_ref()
+                        .map(|t| (t.span, "expected because of this annotation")),
+                };
+                self.expect(&found, expected, decl.value.as_ref().unwrap().span);
+
+                ty
+            }
+
+            (Some(ty), None) => ty,
+
+            // a declaration without an annotation takes the type of its value, unless the
+            // value never produces one, or is inferred from how it is used if it has no value
+            (None, Some(Ty::NEVER)) => Ty::ERROR,
+
+            (None, found) => {
+                let ty: Ty = found.unwrap_or_else(|| self.fresh());
+                self.inferred.push((decl.pattern.span, ty.clone()));
+
+                ty
+            }
+        };
+
+        self.pattern(&decl.pattern, &ty);
+        self.binding_rules(decl);
+    }
+
+    /// Returns the type of a block: that of its tail, `never` if a statement in it never
+    /// completes, or else the unit type
+    ///
+    fn block(&mut self, block: &Block, expected: Option<&Ty>) -> Ty {
+        let mut diverges: bool = false;
+
+        for stmt in &block.stmts {
+            match &stmt.kind {
+                StmtKind::VAR(decl) => self.var_decl(decl),
+
+                StmtKind::EXPR(expr) => {
+                    if self.expr(expr, None) == Ty::NEVER {
+                        diverges = true;
+                    }
+                }
+
+                StmtKind::ERROR => (),
+            }
+        }
+
+        match &block.tail {
+            Some(tail) => self.expr(tail, expected),
+            None if diverges => Ty::NEVER,
+            None => Ty::unit(),
+        }
+    }
+}
@@ -0,0 +1,508 @@
+use super::defs::FnSig;
+use super::Checker;
+use crate::ast::{ClassDecl, FnDecl, Iden, ItemKind, Member, MemberKind, Module};
+use crate::error::{ErrorId, FeoError};
+use crate::sema::types::Ty;
+use crate::sema::{SymbolId, SymbolKind};
+use crate::span::Span;
+use std::collections::HashMap;
+
+impl<'a> Checker<'a> {
+    /// Checks the class hierarchy of a module: that no class inherits from itself or extends
+    /// a `final` class, that methods override exactly when they are marked `override`, that
+    /// no other member reuses the name of an inherited one, and that every class which can
+    /// be instantiated implements its abstract methods
+    ///
+    pub(super) fn class_hierarchy(&mut self, module: &Module) {
+        // cycles are broken first, so that the other checks can follow superclasses
+        for item in &module.items {
+            if let ItemKind::CLASS(decl) = &item.kind {
+                self.inheritance_cycle(decl);
+            }
+        }
+
+        for item in &module.items {
+            if let ItemKind::CLASS(decl) = &item.kind {
+                self.final_superclass(decl);
+                self.overrides(decl);
+                self.shadowed_members(decl);
+                self.abstract_methods(decl);
+            }
+        }
+    }
+
+    /// Returns the type of `super`, which can only be used within a class that extends
+    /// another
+    ///
+    pub(super) fn super_type(&mut self, span: Span) -> Ty {
+        if let Some(ty) = self.superclass() {
+            return ty;
+        }
+
+        // the superclass of a class that extends one from another module is not known here
+        if let Some(Ty::NAMED { symbol, .. }) = &self.self_ty {
+            if self.extends_import(*symbol) {
+                return Ty::ERROR;
+            }
+        }
+
+        let message = "`super` can only be used in the constructors and methods of a class that \
+                       extends another";
+        self.error(ErrorId::ERR_INVALID_SUPER, span, message);
+
+        Ty::ERROR
+    }
+
+    /// Reports a class that inherits from itself, and removes its superclass
+    ///
+    fn inheritance_cycle(&mut self, decl: &ClassDecl) {
+        let Some(symbol) = self.symbol_of(&decl.name) else {
+            return;
+        };
+
+        let mut chain: Vec<SymbolId> = vec![symbol];
+        let mut current: Option<SymbolId> = self.superclass_of(symbol);
+
+        while let Some(next) = current {
+            // a cycle that does not lead back to this class is reported from a class in it
+            if next != symbol && chain.contains(&next) {
+                return;
+            }
+
+            if next == symbol {
+                let names: Vec<String> = chain
+                    .iter()
+                    .chain([&symbol])
+                    .map(|s| format!("`{}`", self.resolution.symbol(*s).name))
+                    .collect();
+
+                let message = match chain.len() {
+                    1 => format!("The class `{}` extends itself", decl.name.name),
+                    _ => format!(
+                        "The class `{}` inherits from itself ({})",
+                        decl.name.name,
+                        names.join(" -> ")
+                    ),
+                };
+
+                let span: Span = self.supertype_name(decl, chain.get(1).copied().unwrap_or(symbol));
+                self.error(ErrorId::ERR_INHERITANCE_CYCLE, span, message);
+
+                if let Some(def) = self.types.defs.get_mut(&symbol) {
+                    def.superclass = None;
+                }
+
+                return;
+            }
+
+            chain.push(next);
+            current = self.superclass_of(next);
+        }
+    }
+
+    /// Reports a class that extends a `final` class
+    ///
+    fn final_superclass(&mut self, decl: &ClassDecl) {
+        let Some(superclass) = self
+            .symbol_of(&decl.name)
+            .and_then(|s| self.superclass_of(s))
+        else {
+            return;
+        };
+
+        let Some(parent) = self.classes.get(&superclass).copied() else {
+            return;
+        };
+
+        if parent.is_final {
+            let message = format!("Cannot extend `{}`, which is `final`", parent.name.name);
+            let err = FeoError::at(
+                ErrorId::ERR_INVALID_SUPERCLASS,
+                self.file,
+                self.supertype_name(decl, superclass),
+                message,
+            )
+            .with_note(
+                parent.name.span,
+                format!("`{}` is declared here", parent.name.name),
+            );
+
+            self.errors.push(err);
+        }
+    }
+
+    /// Checks each method of a class against the method of the same name it inherits, if
+    /// any. A method overrides an inherited one exactly when it is marked `override`, and
+    /// can only override one that is `virtual`, `abstract` or itself an override, and not
+    /// `final`, with the same signature
+    ///
+    fn overrides(&mut self, decl: &ClassDecl) {
+        let Some(symbol) = self.symbol_of(&decl.name) else {
+            return;
+        };
+
+        for member in &decl.members {
+            let MemberKind::METHOD(func) = &member.kind else {
+                continue;
+            };
+
+            if member.modifiers.is_static {
+                continue;
+            }
+
+            let inherited: Option<(SymbolId, &Member, &FnDecl)> =
+                self.superclasses(symbol).into_iter().find_map(|s| {
+                    let (member, func) =
+                        instance_method(self.classes.get(&s).copied()?, &func.name)?;
+                    Some((s, member, func))
+                });
+
+            let Some((owner, base, base_func)) = inherited else {
+                if member.modifiers.is_override && !self.extends_import(symbol) {
+                    let message = format!(
+                        "The method `{}` is marked `override`, but no superclass of `{}` has a \
+                         method `{}`",
+                        func.name.name, decl.name.name, func.name.name
+                    );
+                    self.error(ErrorId::ERR_INVALID_OVERRIDE, func.name.span, message);
+                }
+
+                continue;
+            };
+
+            let owner_name: String = self.resolution.symbol(owner).name.clone();
+            let name: &str = &func.name.name;
+            let declared = format!("`{}.{}` is declared here", owner_name, name);
+
+            let err: FeoError = if base.modifiers.is_final {
+                let message = format!(
+                    "Cannot override `{}`, which is `final` in `{}`",
+                    name, owner_name
+                );
+                FeoError::at(
+                    ErrorId::ERR_INVALID_OVERRIDE,
+                    self.file,
+                    func.name.span,
+                    message,
+                )
+            } else if !(base.modifiers.is_virtual
+                || base.modifiers.is_abstract
+                || base.modifiers.is_override)
+            {
+                // checked before `override`, which would not make the method valid
+                let message = format!(
+                    "Cannot override `{}`, which is not `virtual` in `{}`",
+                    name, owner_name
+                );
+                FeoError::at(
+                    ErrorId::ERR_INVALID_OVERRIDE,
+                    self.file,
+                    func.name.span,
+                    message,
+                )
+                .with_fix(
+                    start_of(base.span),
+                    "virtual ",
+                    format!("make `{}.{}` `virtual`", owner_name, name),
+                )
+            } else if !member.modifiers.is_override {
+                let message = format!(
+                    "The method `{}` overrides a method of `{}`, and must be marked `override`",
+                    name, owner_name
+                );
+                FeoError::at(
+                    ErrorId::ERR_INVALID_OVERRIDE,
+                    self.file,
+                    func.name.span,
+                    message,
+                )
+                .with_fix(
+                    start_of(member.span),
+                    "override ",
+                    format!("mark `{}` as `override`", name),
+                )
+            } else if let Some((expected, found)) = self.override_mismatch(owner, symbol, name) {
+                let message = format!(
+                    "The method `{}` does not match the method it overrides: expected `{}`, \
+                     found `{}`",
+                    name,
+                    expected.ty(),
+                    found.ty()
+                );
+                FeoError::at(
+                    ErrorId::ERR_INVALID_OVERRIDE,
+                    self.file,
+                    func.name.span,
+                    message,
+                )
+            } else {
+                continue;
+            };
+
+            self.errors
+                .push(err.with_note(base_func.name.span, declared));
+        }
+    }
+
+    /// Reports each field or method of a class with the name of a field or method it
+    /// inherits, other than an instance method, which `overrides` checks, and a static
+    /// member, which hides a static member of the same name
+    ///
+    fn shadowed_members(&mut self, decl: &ClassDecl) {
+        let Some(symbol) = self.symbol_of(&decl.name) else {
+            return;
+        };
+
+        for member in &decl.members {
+            let Some(name) = member_name(member) else {
+                continue;
+            };
+
+            let inherited: Option<(SymbolId, &Member, &Iden)> =
+                self.superclasses(symbol).into_iter().find_map(|s| {
+                    let parent: &ClassDecl = self.classes.get(&s).copied()?;
+
+                    parent.members.iter().find_map(|m| match member_name(m) {
+                        Some(n) if n.name == name.name => Some((s, m, n)),
+                        _ => None,
+                    })
+                });
+
+            let Some((owner, base, base_name)) = inherited else {
+                continue;
+            };
+
+            let is_method = |m: &Member| matches!(m.kind, MemberKind::METHOD(_));
+            let both_methods: bool = is_method(member) && is_method(base);
+
+            if (both_methods && !member.modifiers.is_static && !base.modifiers.is_static)
+                || (member.modifiers.is_static && base.modifiers.is_static)
+            {
+                continue;
+            }
+
+            let owner_name: &str = &self.resolution.symbol(owner).name;
+            let message = format!(
+                "The {} `{}` shadows the {} `{}` inherited from `{}`",
+                member_kind(member),
+                name.name,
+                member_kind(base),
+                name.name,
+                owner_name
+            );
+            let err = FeoError::at(ErrorId::ERR_DUPLICATE_NAME, self.file, name.span, message)
+                .with_note(
+                    base_name.span,
+                    format!("`{}.{}` is declared here", owner_name, name.name),
+                );
+
+            self.errors.push(err);
+        }
+    }
+
+    /// Returns the signatures of an inherited method and the method overriding it, if they
+    /// do not match
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - the superclass declaring the inherited method
+    /// * `class` - the class declaring the overriding method
+    /// * `name` - the name of the method
+    ///
+    fn override_mismatch(
+        &self,
+        owner: SymbolId,
+        class: SymbolId,
+        name: &str,
+    ) -> Option<(FnSig, FnSig)> {
+        let expected: &FnSig = self.types.defs.get(&owner)?.method(name)?;
+        let found: &FnSig = self.types.defs.get(&class)?.method(name)?;
+
+        if expected.generics.len() != found.generics.len() {
+            return Some((expected.clone(), found.clone()));
+        }
+
+        // the generic parameters of the two methods are matched by position
+        let subst: HashMap<SymbolId, Ty> = expected
+            .generics
+            .iter()
+            .zip(&found.generics)
+            .map(|(e, f)| {
+                let param = Ty::PARAM {
+                    name: self.resolution.symbol(*f).name.clone(),
+                    symbol: *f,
+                };
+                (*e, param)
+            })
+            .collect();
+        let expected: FnSig = expected.subst(&subst);
+
+        match expected.params == found.params && expected.ret == found.ret {
+            true => None,
+            false => Some((expected, found.clone())),
+        }
+    }
+
+    /// Checks the abstract methods of a class: only an `abstract` class may declare them,
+    /// and any other class must implement each one it inherits
+    ///
+    fn abstract_methods(&mut self, decl: &ClassDecl) {
+        let Some(symbol) = self.symbol_of(&decl.name) else {
+            return;
+        };
+
+        if decl.is_abstract {
+            return;
+        }
+
+        for member in &decl.members {
+            if let (MemberKind::METHOD(func), true) = (&member.kind, member.modifiers.is_abstract) {
+                let message = format!(
+                    "The method `{}` is `abstract`, so the class `{}` must be too",
+                    func.name.name, decl.name.name
+                );
+                self.error(ErrorId::ERR_INVALID_ABSTRACT, func.name.span, message);
+            }
+        }
+
+        if self.extends_import(symbol) {
+            return;
+        }
+
+        // the method each name refers to in the class, from the furthest superclass down
+        let mut methods: Vec<(SymbolId, &Member, &FnDecl)> = Vec::new();
+        let classes: Vec<SymbolId> = self.superclasses(symbol).into_iter().rev().collect();
+
+        for class in classes {
+            let Some(parent) = self.classes.get(&class).copied() else {
+                continue;
+            };
+
+            for member in &parent.members {
+                let MemberKind::METHOD(func) = &member.kind else {
+                    continue;
+                };
+
+                if member.modifiers.is_static {
+                    continue;
+                }
+
+                methods.retain(|(_, _, f)| f.name.name != func.name.name);
+                methods.push((class, member, func));
+            }
+        }
+
+        for (owner, member, func) in methods {
+            let is_implemented: bool = instance_method(decl, &func.name).is_some();
+
+            if !member.modifiers.is_abstract || is_implemented {
+                continue;
+            }
+
+            let owner_name: String = self.resolution.symbol(owner).name.clone();
+            let message = format!(
+                "The class `{}` must implement the abstract method `{}` of `{}`",
+                decl.name.name, func.name.name, owner_name
+            );
+            let err = FeoError::at(
+                ErrorId::ERR_MISSING_METHOD,
+                self.file,
+                decl.name.span,
+                message,
+            )
+            .with_note(
+                func.name.span,
+                format!("`{}.{}` is declared here", owner_name, func.name.name),
+            );
+
+            self.errors.push(err);
+        }
+    }
+
+    /// Returns the class a class directly extends, if it is declared in the module
+    ///
+    fn superclass_of(&self, class: SymbolId) -> Option<SymbolId> {
+        match self.types.defs.get(&class)?.superclass {
+            Some(Ty::NAMED { symbol, .. }) => Some(symbol),
+            _ => None,
+        }
+    }
+
+    /// Returns the superclasses of a class declared in the module, nearest first
+    ///
+    pub(super) fn superclasses(&self, class: SymbolId) -> Vec<SymbolId> {
+        let mut superclasses: Vec<SymbolId> = Vec::new();
+        let mut current: Option<SymbolId> = self.superclass_of(class);
+
+        while let Some(next) = current {
+            if next == class || superclasses.contains(&next) {
+                break;
+            }
+
+            superclasses.push(next);
+            current = self.superclass_of(next);
+        }
+
+        superclasses
+    }
+
+    /// Returns `true` if a class or any of its superclasses names a supertype imported from
+    /// another module, which might be a class whose members are not known here
+    ///
+    pub(super) fn extends_import(&self, class: SymbolId) -> bool {
+        [class]
+            .into_iter()
+            .chain(self.superclasses(class))
+            .filter_map(|c| self.classes.get(&c))
+            .flat_map(|d| &d.supertypes)
+            .filter_map(|name| self.resolution.use_at(name.span))
+            .any(|s| s.kind == SymbolKind::IMPORT)
+    }
+
+    /// Returns the span of the name of a class's superclass in its list of supertypes
+    ///
+    fn supertype_name(&self, decl: &ClassDecl, superclass: SymbolId) -> Span {
+        decl.supertypes
+            .iter()
+            .find(|n| self.resolution.uses.get(&n.span) == Some(&superclass))
+            .map_or(decl.name.span, |n| n.span)
+    }
+}
+
+/// Returns the non-static method of a class with a name, if it has one
+///
+fn instance_method<'d>(decl: &'d ClassDecl, name: &Iden) -> Option<(&'d Member, &'d FnDecl)> {
+    decl.members.iter().find_map(|member| match &member.kind {
+        MemberKind::METHOD(func) if func.name.name == name.name && !member.modifiers.is_static => {
+            Some((member, func))
+        }
+        _ => None,
+    })
+}
+
+/// Returns the name of a field or method
+///
+fn member_name(member: &Member) -> Option<&Iden> {
+    match &member.kind {
+        MemberKind::FIELD(field) => Some(&field.name),
+        MemberKind::METHOD(func) => Some(&func.name),
+        _ => None,
+    }
+}
+
+/// Returns what kind of member a field or method is, for errors
+///
+fn member_kind(member: &Member) -> &'static str {
+    match (&member.kind, member.modifiers.is_static) {
+        (MemberKind::FIELD(_), false) => "field",
+        (MemberKind::FIELD(_), true) => "static field",
+        (_, false) => "method",
+        (_, true) => "static method",
+    }
+}
+
+/// Returns the empty span at the start of a span, where a fix inserts text
+///
+fn start_of(span: Span) -> Span {
+    Span::new(span.line, span.col, span.line, span.col)
+}
@@ -0,0 +1,1066 @@
+use super::mutability::Bindings;
+use super::{Checker, VariantDef};
+use crate::ast::{
+    BinaryOp, BindingKind, Block, ClassField, Expr, ExprKind, Literal, Pattern, PatternKind,
+    StmtKind, UnaryOp, VarDecl, Visitor,
+};
+use crate::error::{ErrorId, FeoError};
+use crate::sema::types::Ty;
+use crate::sema::value::Value;
+use crate::sema::{SymbolId, SymbolKind};
+use crate::span::Span;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// The most calls evaluated within one another before a function is taken to recurse
+/// without end
+///
+const MAX_CALL_DEPTH: usize = 64;
+
+/// The most expressions evaluated, counting each time a function body is evaluated again,
+/// in working out one value. A call that would take longer is taken to never finish
+///
+const MAX_STEPS: usize = 1 << 20;
+
+/// The most values, counting each element of an array and each byte of a string, that may
+/// be built in working out one value, so that `[[0; N]; N]` or a string doubled in a loop
+/// cannot use up the compiler's memory
+///
+const MAX_SIZE: usize = 1 << 20;
+
+/// What is left of the steps and size one evaluation may use up
+///
+pub(super) struct Budget {
+    steps: usize,
+    size: usize,
+}
+
+/// Why an expression has no value at compile time
+///
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+enum Stop {
+    /// the part of the expression at this span can only be evaluated when the program runs
+    NOT_CONST(Span),
+
+    /// the evaluation failed, and the reason was already reported
+    FAILED,
+
+    /// a `return` from the function being called, with its value
+    RETURN(Value),
+
+    /// the evaluation took more than `MAX_STEPS` steps, which is reported where it started
+    TOO_LONG,
+}
+
+type Eval = Result<Value, Stop>;
+
+/// The values of the parameters and local bindings of the function being called
+///
+type Env = HashMap<SymbolId, Value>;
+
+impl<'a> Checker<'a> {
+    /// Evaluates the value of a `const`, recording the value of each name it binds. A
+    /// module's constants may be used before they are declared, in which case they are
+    /// evaluated when first used, and only converted to their declared types here
+    ///
+    pub(super) fn const_decl(&mut self, decl: &VarDecl) {
+        let Some(value) = &decl.value else {
+            return;
+        };
+
+        let mut bindings = Bindings(Vec::new());
+        bindings.visit_pattern(&decl.pattern);
+        let symbols: Vec<SymbolId> = bindings
+            .0
+            .iter()
+            .filter_map(|name| self.symbol_of(name))
+            .collect();
+
+        if symbols.iter().any(|s| !self.consts.contains_key(s)) {
+            self.evaluating.extend(&symbols);
+            let found: Eval = self.eval_const(value);
+            self.evaluating.retain(|s| !symbols.contains(s));
+
+            let found: Option<Value> = match found {
+                Ok(found) => Some(found),
+
+                Err(Stop::NOT_CONST(span)) => {
+                    self.not_const(span, decl.keyword, "required because this is a `const`");
+                    None
+                }
+
+                Err(_) => None,
+            };
+
+            self.bind_const(&decl.pattern, found.as_ref());
+        }
+
+        for symbol in symbols {
+            let found: Option<Value> = self.consts.get(&symbol).cloned().flatten();
+
+            if let (Some(found), Some(ty)) = (found, self.types.symbols.get(&symbol).cloned()) {
+                let found: Option<Value> = self.coerce(found, &ty, value.span).ok();
+                self.consts.insert(symbol, found);
+            }
+        }
+    }
+
+    /// Evaluates the initial value of a static field, which is set before the program runs
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - the type the field belongs to
+    /// * `field` - the field
+    /// * `ty` - the field's type
+    ///
+    pub(super) fn static_field(&mut self, owner: SymbolId, field: &ClassField, ty: &Ty) {
+        let Some(value) = &field.value else {
+            return;
+        };
+
+        let found: Option<Value> = match self.eval_const(value) {
+            Ok(found) => self.coerce(found, ty, value.span).ok(),
+
+            Err(Stop::NOT_CONST(span)) => {
+                let note = format!("required because `{}` is a static field", field.name.name);
+                self.not_const(span, field.name.span, note);
+
+                None
+            }
+
+            Err(_) => None,
+        };
+
+        if let Some(found) = found {
+            let key: (SymbolId, String) = (owner, field.name.name.clone());
+            self.types.statics.insert(key, found);
+        }
+    }
+
+    /// Returns the length of an array, if it can be evaluated at compile time
+    ///
+    /// # Arguments
+    ///
+    /// * `len` - the length expression
+    /// * `is_type` - whether the length is that of an array type (`[T; N]`), which must be
+    ///   a constant, rather than of an array value (`[value; n]`), which only has a fixed
+    ///   length type if it is one
+    ///
+    pub(super) fn array_len(&mut self, len: &Expr, is_type: bool) -> Option<usize> {
+        // the same type may be lowered more than once, but is only reported once
+        if let Some(found) = self.lengths.get(&len.span) {
+            return *found;
+        }
+
+        let found: Option<usize> = match self.eval_const(len) {
+            Ok(Value::INT(n)) => self.length(n.into(), len.span),
+            Ok(Value::UINT(n)) => self.length(n.into(), len.span),
+
+            Ok(found) => {
+                if is_type {
+                    let message = format!("Expected an integer length, found `{}`", found);
+                    self.error(ErrorId::ERR_TYPE_MISMATCH, len.span, message);
+                }
+
+                None
+            }
+
+            Err(Stop::NOT_CONST(span)) if is_type => {
+                let note = "required because this is the length of an array type";
+                self.not_const(span, len.span, note);
+
+                None
+            }
+
+            Err(_) => None,
+        };
+
+        self.lengths.insert(len.span, found);
+        found
+    }
+
+    /// Evaluates the value of a constant, static field or array length. The evaluation has
+    /// `MAX_STEPS` steps and `MAX_SIZE` values to use up, which it shares with the
+    /// evaluations it starts, such as that of a constant used before its declaration
+    ///
+    fn eval_const(&mut self, expr: &Expr) -> Eval {
+        let is_outermost: bool = self.budget.is_none();
+
+        if is_outermost {
+            self.budget = Some(Budget {
+                steps: MAX_STEPS,
+                size: MAX_SIZE,
+            });
+        }
+
+        let found: Eval = match self.eval(expr, &mut Env::new()) {
+            Err(Stop::TOO_LONG) => {
+                let message = "This expression takes too long to evaluate at compile time";
+                self.error(ErrorId::ERR_NOT_CONST, expr.span, message);
+
+                Err(Stop::FAILED)
+            }
+
+            found => found,
+        };
+
+        if is_outermost {
+            self.budget = None;
+        }
+
+        found
+    }
+
+    /// Uses up one step of the evaluation under way
+    ///
+    fn step(&mut self) -> Result<(), Stop> {
+        let Some(budget) = &mut self.budget else {
+            return Ok(());
+        };
+
+        match budget.steps.checked_sub(1) {
+            Some(steps) => {
+                budget.steps = steps;
+                Ok(())
+            }
+
+            None => Err(Stop::TOO_LONG),
+        }
+    }
+
+    /// Uses up the size of a value about to be built by the evaluation under way, reporting
+    /// it if there is not enough left
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the size of the value, as counted by `Value::size`
+    /// * `span` - the expression building the value
+    ///
+    fn allocate(&mut self, size: usize, span: Span) -> Result<(), Stop> {
+        let Some(budget) = &mut self.budget else {
+            return Ok(());
+        };
+
+        match budget.size.checked_sub(size) {
+            Some(left) => {
+                budget.size = left;
+                Ok(())
+            }
+
+            None => {
+                let message = "This value is too large to build at compile time";
+                self.eval_error(ErrorId::ERR_NOT_CONST, span, message);
+
+                Err(Stop::FAILED)
+            }
+        }
+    }
+
+    /// Returns a copy of a value, using up its size
+    ///
+    fn copied(&mut self, value: &Value, span: Span) -> Eval {
+        self.allocate(value.size(), span)?;
+
+        Ok(value.clone())
+    }
+
+    /// Returns a value just built, using up `size` for it
+    ///
+    fn built(&mut self, size: usize, value: Value, span: Span) -> Eval {
+        self.allocate(size, span)?;
+
+        Ok(value)
+    }
+
+    /// Returns an integer as the length of an array, reporting it if it is negative
+    ///
+    fn length(&mut self, n: i64, span: Span) -> Option<usize> {
+        match usize::try_from(n) {
+            Ok(n) => Some(n),
+
+            Err(_) => {
+                let message = format!("An array cannot have a negative length, found `{}`", n);
+                self.eval_error(ErrorId::ERR_INVALID_TYPE, span, message);
+
+                None
+            }
+        }
+    }
+
+    fn not_const(&mut self, span: Span, because: Span, note: impl Into<String>) {
+        let message = "This expression cannot be evaluated at compile time";
+        let err =
+            FeoError::at(ErrorId::ERR_NOT_CONST, self.file, span, message).with_note(because, note);
+
+        self.errors.push(err);
+    }
+
+    /// Reports an error found while evaluating an expression
+    ///
+    fn eval_error(&mut self, id: ErrorId, span: Span, message: impl Into<String>) {
+        let err: FeoError = FeoError::at(id, self.file, span, message);
+        let err: FeoError = self.with_calls(err);
+
+        self.errors.push(err);
+    }
+
+    /// Returns an error with a note at each call being evaluated, innermost first. A
+    /// function calling itself is only noted once
+    ///
+    fn with_calls(&self, mut err: FeoError) -> FeoError {
+        let mut noted: Vec<Span> = Vec::new();
+
+        for call in self.calls.iter().rev() {
+            if !noted.contains(call) {
+                err = err.with_note(*call, "while evaluating this call");
+                noted.push(*call);
+            }
+        }
+
+        err
+    }
+
+    /// Records the values of the names a constant binds, or that they have none
+    ///
+    fn bind_const(&mut self, pattern: &Pattern, value: Option<&Value>) {
+        match (&pattern.kind, value) {
+            (PatternKind::BINDING(name), _) => {
+                if let Some(symbol) = self.symbol_of(name) {
+                    self.consts.insert(symbol, value.cloned());
+                }
+            }
+
+            (PatternKind::TUPLE(elems), Some(Value::TUPLE(values)))
+                if elems.len() == values.len() =>
+            {
+                for (elem, value) in elems.iter().zip(values) {
+                    self.bind_const(elem, Some(value));
+                }
+            }
+
+            _ => {
+                let mut bindings = Bindings(Vec::new());
+                bindings.visit_pattern(pattern);
+
+                for name in bindings.0 {
+                    if let Some(symbol) = self.symbol_of(&name) {
+                        self.consts.insert(symbol, None);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Evaluates an expression at compile time
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - the expression
+    /// * `env` - the values of the bindings in scope, when evaluating the body of a call
+    ///
+    fn eval(&mut self, expr: &Expr, env: &mut Env) -> Eval {
+        self.step()?;
+
+        match &expr.kind {
+            ExprKind::LITERAL(lit) => {
+                let value: Value = self.literal_value(lit, expr.span);
+                self.built(value.size(), value, expr.span)
+            }
+
+            ExprKind::IDEN(_) => self.name_value(expr.span, env),
+            ExprKind::PAREN(inner) => self.eval(inner, env),
+
+            // the elements were each built already
+            ExprKind::TUPLE(elems) => {
+                let elems: Vec<Value> = self.eval_all(elems, env)?;
+                self.built(1, Value::TUPLE(elems), expr.span)
+            }
+
+            ExprKind::ARRAY(elems) => {
+                let elems: Vec<Value> = self.eval_all(elems, env)?;
+                self.built(1, Value::ARRAY(elems), expr.span)
+            }
+
+            ExprKind::ARRAY_REPEAT { value, len } => self.eval_repeat(value, len, expr.span, env),
+
+            ExprKind::BLOCK(block) => self.eval_block(block, env),
+
+            ExprKind::UNARY { op, expr: inner } => match (op, self.eval(inner, env)?) {
+                (UnaryOp::NEG, Value::INT(n)) => match n.checked_neg() {
+                    Some(n) => Ok(Value::INT(n)),
+                    None => {
+                        let message = format!("The result of `-({})` does not fit in `int`", n);
+                        self.overflow(message, expr.span)
+                    }
+                },
+
+                (UnaryOp::NEG, Value::FLOAT(x)) => Ok(Value::FLOAT(-x)),
+                (UnaryOp::NOT, Value::BOOL(b)) => Ok(Value::BOOL(!b)),
+                _ => Err(Stop::FAILED),
+            },
+
+            // `&&` and `||` only evaluate their right operand if it decides the result
+            ExprKind::BINARY {
+                op: op @ (BinaryOp::AND | BinaryOp::OR),
+                lhs,
+                rhs,
+            } => match (op, self.eval(lhs, env)?) {
+                (BinaryOp::AND, Value::BOOL(false)) => Ok(Value::BOOL(false)),
+                (BinaryOp::OR, Value::BOOL(true)) => Ok(Value::BOOL(true)),
+                (_, Value::BOOL(_)) => self.eval(rhs, env),
+                _ => Err(Stop::FAILED),
+            },
+
+            ExprKind::BINARY { op, lhs, rhs } => {
+                let lhs: Value = self.eval(lhs, env)?;
+                let rhs: Value = self.eval(rhs, env)?;
+                self.binary_value(*op, lhs, rhs, expr.span)
+            }
+
+            ExprKind::TERNARY { cond, then, els } => match self.eval(cond, env)? {
+                Value::BOOL(true) => self.eval(then, env),
+                Value::BOOL(false) => self.eval(els, env),
+                _ => Err(Stop::FAILED),
+            },
+
+            ExprKind::IF { cond, then, els } => match (self.eval(cond, env)?, els) {
+                (Value::BOOL(true), _) => self.eval_block(then, env),
+                (Value::BOOL(false), Some(els)) => self.eval(els, env),
+                (Value::BOOL(false), None) => Ok(Value::unit()),
+                _ => Err(Stop::FAILED),
+            },
+
+            ExprKind::MATCH { scrutinee, arms } => {
+                let value: Value = self.eval(scrutinee, env)?;
+
+                for arm in arms {
+                    if !self.matches(&arm.pattern, &value, env) {
+                        continue;
+                    }
+
+                    let guard: Value = match &arm.guard {
+                        Some(guard) => self.eval(guard, env)?,
+                        None => Value::BOOL(true),
+                    };
+
+                    if guard == Value::BOOL(true) {
+                        return self.eval(&arm.body, env);
+                    }
+                }
+
+                let message = format!("No arm of this `match` matches `{}`", value);
+                self.eval_error(ErrorId::ERR_NOT_CONST, expr.span, message);
+
+                Err(Stop::FAILED)
+            }
+
+            ExprKind::RETURN(value) if !self.calls.is_empty() => {
+                let value: Value = match value {
+                    Some(value) => self.eval(value, env)?,
+                    None => Value::unit(),
+                };
+
+                Err(Stop::RETURN(value))
+            }
+
+            ExprKind::FIELD { expr: base, field } => {
+                // a unit variant, such as `Color.Red`
+                if let Some(symbol) = self.type_name(base) {
+                    let def = &self.types.defs[&symbol];
+
+                    return match def.variant(&field.name) {
+                        Some(VariantDef::UNIT) => Ok(Value::VARIANT {
+                            ty: def.name.clone(),
+                            name: field.name.clone(),
+                            elems: Vec::new(),
+                        }),
+
+                        _ => Err(Stop::NOT_CONST(expr.span)),
+                    };
+                }
+
+                match self.eval(base, env)?.field(&field.name) {
+                    Some(value) => Ok(value.clone()),
+                    None => Err(Stop::NOT_CONST(expr.span)),
+                }
+            }
+
+            ExprKind::INDEX { expr: base, index } => {
+                let base: Value = self.eval(base, env)?;
+                let index: Value = self.eval(index, env)?;
+
+                let (Value::ARRAY(elems), Value::INT(_) | Value::UINT(_)) = (&base, &index) else {
+                    return Err(Stop::FAILED);
+                };
+
+                let found: Option<&Value> = match index {
+                    Value::INT(i) => usize::try_from(i).ok().and_then(|i| elems.get(i)),
+                    Value::UINT(i) => elems.get(i as usize),
+                    _ => None,
+                };
+
+                match found {
+                    Some(found) => Ok(found.clone()),
+
+                    None => {
+                        let message = format!(
+                            "The index `{}` is out of bounds for an array of {} element{}",
+                            index,
+                            elems.len(),
+                            if elems.len() == 1 { "" } else { "s" }
+                        );
+                        self.eval_error(ErrorId::ERR_OUT_OF_BOUNDS, expr.span, message);
+
+                        Err(Stop::FAILED)
+                    }
+                }
+            }
+
+            ExprKind::CALL { callee, args } => self.eval_call(callee, args, expr.span, env),
+
+            ExprKind::STRUCT { path, fields } => {
+                let names: Vec<&str> = path.iter().map(|i| i.name.as_str()).collect();
+                let mut values: Vec<(String, Value)> = Vec::new();
+
+                for field in fields {
+                    values.push((field.name.name.clone(), self.eval(&field.value, env)?));
+                }
+
+                Ok(Value::STRUCT {
+                    name: names.join("."),
+                    fields: values,
+                })
+            }
+
+            _ => Err(Stop::NOT_CONST(expr.span)),
+        }
+    }
+
+    fn eval_all(&mut self, exprs: &[Expr], env: &mut Env) -> Result<Vec<Value>, Stop> {
+        exprs.iter().map(|e| self.eval(e, env)).collect()
+    }
+
+    /// Evaluates `[value; len]`, which copies its value once for each element after the
+    /// first. It is kept out of `eval`, whose frame is on the stack once per level of nesting
+    ///
+    fn eval_repeat(&mut self, value: &Expr, len: &Expr, span: Span, env: &mut Env) -> Eval {
+        let value: Value = self.eval(value, env)?;
+
+        let len: Option<usize> = match self.eval(len, env)? {
+            Value::INT(n) => self.length(n.into(), len.span),
+            Value::UINT(n) => self.length(n.into(), len.span),
+            _ => None,
+        };
+
+        let Some(len) = len else {
+            return Err(Stop::FAILED);
+        };
+
+        let copies: usize = len.saturating_sub(1).saturating_mul(value.size());
+        self.allocate(copies.saturating_add(1), span)?;
+
+        Ok(Value::ARRAY(vec![value; len]))
+    }
+
+    /// Evaluates a block, whose statements may only declare `let` and `const` bindings or
+    /// evaluate expressions
+    ///
+    fn eval_block(&mut self, block: &Block, env: &mut Env) -> Eval {
+        for stmt in &block.stmts {
+            match &stmt.kind {
+                StmtKind::VAR(decl) if decl.binding != BindingKind::VAR => {
+                    let Some(value) = &decl.value else {
+                        return Err(Stop::NOT_CONST(stmt.span));
+                    };
+
+                    let value: Value = self.eval(value, env)?;
+
+                    if !self.matches(&decl.pattern, &value, env) {
+                        return Err(Stop::FAILED);
+                    }
+                }
+
+                StmtKind::VAR(decl) => return Err(Stop::NOT_CONST(decl.keyword)),
+
+                StmtKind::EXPR(expr) => {
+                    self.eval(expr, env)?;
+                }
+
+                StmtKind::ERROR => return Err(Stop::FAILED),
+            }
+        }
+
+        match &block.tail {
+            Some(tail) => self.eval(tail, env),
+            None => Ok(Value::unit()),
+        }
+    }
+
+    /// Returns the value of an integer literal, of the integer type the checker found for
+    /// it. A literal in code not yet checked (e.g. a constant used before its declaration)
+    /// is an `int` until it is converted to the type it is used as
+    ///
+    fn literal_value(&self, lit: &Literal, span: Span) -> Value {
+        match lit {
+            Literal::BOOL(b) => Value::BOOL(*b),
+            Literal::CHAR(c) => Value::CHAR(*c),
+            Literal::FLOAT(x) => Value::FLOAT(*x),
+            Literal::STRING(s) => Value::STRING(s.clone()),
+
+            Literal::INT(n) => match self.types.exprs.get(&span).map(|t| self.shown(t)) {
+                Some(Ty::UINT) => u32::try_from(*n).map_or(Value::INT(*n), Value::UINT),
+                _ => Value::INT(*n),
+            },
+        }
+    }
+
+    /// Returns the value of a name: a parameter or local binding of the function being
+    /// called, or a constant
+    ///
+    fn name_value(&mut self, span: Span, env: &Env) -> Eval {
+        let Some(symbol) = self.resolution.uses.get(&span).copied() else {
+            return Err(Stop::FAILED);
+        };
+
+        if let Some(value) = env.get(&symbol) {
+            return self.copied(value, span);
+        }
+
+        match self.resolution.symbol(symbol).kind {
+            SymbolKind::VAR(BindingKind::CONST) => (),
+
+            // the values of other modules' constants are not known here
+            SymbolKind::IMPORT => return Err(Stop::FAILED),
+            _ => return Err(Stop::NOT_CONST(span)),
+        }
+
+        if let Some(value) = self.consts.get(&symbol).cloned() {
+            let value: Value = value.ok_or(Stop::FAILED)?;
+            self.allocate(value.size(), span)?;
+
+            return Ok(value);
+        }
+
+        if self.evaluating.contains(&symbol) {
+            let name: String = self.resolution.symbol(symbol).name.clone();
+            let message = format!("The value of `{}` depends on itself", name);
+            let err = FeoError::at(ErrorId::ERR_NOT_CONST, self.file, span, message).with_note(
+                self.resolution.symbol(symbol).span,
+                format!("`{}` is declared here", name),
+            );
+            self.errors.push(err);
+
+            return Err(Stop::FAILED);
+        }
+
+        let Some(decl) = self.const_items.get(&symbol).copied() else {
+            return Err(Stop::FAILED);
+        };
+
+        self.const_decl(decl);
+        let value: Value = self
+            .consts
+            .get(&symbol)
+            .cloned()
+            .flatten()
+            .ok_or(Stop::FAILED)?;
+        self.allocate(value.size(), span)?;
+
+        Ok(value)
+    }
+
+    /// Evaluates a call: of a function whose body can itself be evaluated, or one that
+    /// builds a tuple variant or converts a value to a distinct type
+    ///
+    fn eval_call(&mut self, callee: &Expr, args: &[Expr], span: Span, env: &mut Env) -> Eval {
+        // a tuple variant, such as `Shape.Circle(1.0)`
+        if let ExprKind::FIELD { expr: base, field } = &callee.kind {
+            let Some(symbol) = self.type_name(base) else {
+                return Err(Stop::NOT_CONST(span));
+            };
+
+            let def = &self.types.defs[&symbol];
+            let Some(VariantDef::TUPLE(_)) = def.variant(&field.name) else {
+                return Err(Stop::NOT_CONST(span));
+            };
+
+            let ty: String = def.name.clone();
+            let elems: Vec<Value> = self.eval_all(args, env)?;
+
+            return Ok(Value::VARIANT {
+                ty,
+                name: field.name.clone(),
+                elems,
+            });
+        }
+
+        // a conversion to a distinct type keeps the value as it is
+        if let Some(symbol) = self.type_name(callee) {
+            return match (&self.types.defs[&symbol].underlying, args) {
+                (Some(_), [value]) => self.eval(value, env),
+                _ => Err(Stop::NOT_CONST(span)),
+            };
+        }
+
+        let Some(symbol) = self.resolution.uses.get(&callee.span).copied() else {
+            return Err(Stop::NOT_CONST(span));
+        };
+
+        let func = match self.fn_decls.get(&symbol).copied() {
+            Some(func) if func.body.is_some() => func,
+            _ if self.resolution.symbol(symbol).kind == SymbolKind::IMPORT => {
+                return Err(Stop::FAILED)
+            }
+            _ => return Err(Stop::NOT_CONST(span)),
+        };
+
+        if self.calls.len() >= MAX_CALL_DEPTH {
+            let message = format!(
+                "The call to `{}` recurses too deeply to be evaluated at compile time",
+                func.name.name
+            );
+            self.eval_error(ErrorId::ERR_NOT_CONST, span, message);
+
+            return Err(Stop::FAILED);
+        }
+
+        let args: Vec<Value> = self.eval_all(args, env)?;
+        let params: Vec<Ty> = self
+            .types
+            .funcs
+            .get(&symbol)
+            .map_or(Vec::new(), |sig| sig.params.clone());
+
+        self.calls.push(span);
+
+        // each call has its own bindings, starting with its parameters, which take their
+        // default values if no argument is passed
+        let mut locals = Env::new();
+        let mut result: Eval = Ok(Value::unit());
+
+        for (i, param) in func.params.iter().enumerate() {
+            let value: Eval = match (args.get(i), &param.default) {
+                (Some(arg), _) => Ok(arg.clone()),
+                (None, Some(default)) => self.eval(default, &mut locals),
+                (None, None) => Err(Stop::FAILED),
+            };
+
+            let value: Eval = match (value, params.get(i)) {
+                (Ok(value), Some(ty)) => self.coerce(value, ty, span),
+                (value, _) => value,
+            };
+
+            match value {
+                Ok(value) if self.matches(&param.pattern, &value, &mut locals) => (),
+                Ok(_) => result = Err(Stop::FAILED),
+                Err(err) => result = Err(err),
+            }
+
+            if result.is_err() {
+                break;
+            }
+        }
+
+        if result.is_ok() {
+            result = match self.eval_block(func.body.as_ref().unwrap(), &mut locals) {
+                Err(Stop::RETURN(value)) => Ok(value),
+                result => result,
+            };
+        }
+
+        self.calls.pop();
+
+        match result {
+            Ok(value) => match self.types.funcs.get(&symbol).map(|sig| sig.ret.clone()) {
+                Some(ret) => self.coerce(value, &ret, span),
+                None => Ok(value),
+            },
+
+            // what cannot be evaluated is reported at the call, so that it is reported once
+            // however deep in the function it is
+            Err(Stop::NOT_CONST(inner)) => {
+                let message = format!(
+                    "The call to `{}` cannot be evaluated at compile time",
+                    func.name.name
+                );
+                let err = FeoError::at(ErrorId::ERR_NOT_CONST, self.file, span, message)
+                    .with_note(inner, "because this cannot be evaluated at compile time");
+                let err: FeoError = self.with_calls(err);
+
+                self.errors.push(err);
+
+                Err(Stop::FAILED)
+            }
+
+            Err(err) => Err(err),
+        }
+    }
+
+    fn binary_value(&mut self, op: BinaryOp, lhs: Value, rhs: Value, span: Span) -> Eval {
+        // an integer literal not yet checked is an `int`, but may be used with a `uint`
+        let (lhs, rhs): (Value, Value) = match (lhs, rhs) {
+            (Value::INT(n), rhs @ Value::UINT(_)) => {
+                (self.coerce(Value::INT(n), &Ty::UINT, span)?, rhs)
+            }
+            (lhs @ Value::UINT(_), Value::INT(n)) => {
+                (lhs, self.coerce(Value::INT(n), &Ty::UINT, span)?)
+            }
+            operands => operands,
+        };
+
+        let shown = |lhs: &Value, rhs: &Value, ty: Ty| {
+            format!(
+                "The result of `{} {} {}` does not fit in `{}`",
+                lhs, op, rhs, ty
+            )
+        };
+
+        match (op, &lhs, &rhs) {
+            (BinaryOp::DIV | BinaryOp::MOD, Value::INT(_), Value::INT(0))
+            | (BinaryOp::DIV | BinaryOp::MOD, Value::UINT(_), Value::UINT(0)) => {
+                let message = format!("Cannot divide `{}` by zero", lhs);
+                self.eval_error(ErrorId::ERR_DIVISION_BY_ZERO, span, message);
+
+                Err(Stop::FAILED)
+            }
+
+            (_, Value::INT(a), Value::INT(b)) if is_arithmetic(op) => {
+                let found: Option<i32> = match op {
+                    BinaryOp::ADD => a.checked_add(*b),
+                    BinaryOp::SUB => a.checked_sub(*b),
+                    BinaryOp::MULT => a.checked_mul(*b),
+                    BinaryOp::DIV => a.checked_div(*b),
+                    _ => a.checked_rem(*b),
+                };
+
+                match found {
+                    Some(n) => Ok(Value::INT(n)),
+                    None => self.overflow(shown(&lhs, &rhs, Ty::INT), span),
+                }
+            }
+
+            (_, Value::UINT(a), Value::UINT(b)) if is_arithmetic(op) => {
+                let found: Option<u32> = match op {
+                    BinaryOp::ADD => a.checked_add(*b),
+                    BinaryOp::SUB => a.checked_sub(*b),
+                    BinaryOp::MULT => a.checked_mul(*b),
+                    BinaryOp::DIV => a.checked_div(*b),
+                    _ => a.checked_rem(*b),
+                };
+
+                match found {
+                    Some(n) => Ok(Value::UINT(n)),
+                    None => self.overflow(shown(&lhs, &rhs, Ty::UINT), span),
+                }
+            }
+
+            (_, Value::FLOAT(a), Value::FLOAT(b)) if is_arithmetic(op) => {
+                Ok(Value::FLOAT(match op {
+                    BinaryOp::ADD => a + b,
+                    BinaryOp::SUB => a - b,
+                    BinaryOp::MULT => a * b,
+                    BinaryOp::DIV => a / b,
+                    _ => a % b,
+                }))
+            }
+
+            (BinaryOp::ADD, Value::STRING(a), Value::STRING(b)) => {
+                self.allocate(a.len() + b.len(), span)?;
+
+                Ok(Value::STRING(format!("{}{}", a, b)))
+            }
+
+            (BinaryOp::EQ, _, _) => Ok(Value::BOOL(equal(&lhs, &rhs))),
+            (BinaryOp::NOT_EQ, _, _) => Ok(Value::BOOL(!equal(&lhs, &rhs))),
+
+            (BinaryOp::LESS | BinaryOp::LESS_EQ | BinaryOp::GRTR | BinaryOp::GRTR_EQ, _, _) => {
+                let Some(ordering) = compare(&lhs, &rhs) else {
+                    return Err(Stop::FAILED);
+                };
+
+                Ok(Value::BOOL(match op {
+                    BinaryOp::LESS => ordering.is_lt(),
+                    BinaryOp::LESS_EQ => ordering.is_le(),
+                    BinaryOp::GRTR => ordering.is_gt(),
+                    _ => ordering.is_ge(),
+                }))
+            }
+
+            (BinaryOp::BIT_AND, Value::INT(a), Value::INT(b)) => Ok(Value::INT(a & b)),
+            (BinaryOp::BIT_AND, Value::UINT(a), Value::UINT(b)) => Ok(Value::UINT(a & b)),
+            (BinaryOp::BIT_AND | BinaryOp::AND, Value::BOOL(a), Value::BOOL(b)) => {
+                Ok(Value::BOOL(*a && *b))
+            }
+
+            (BinaryOp::BIT_OR, Value::INT(a), Value::INT(b)) => Ok(Value::INT(a | b)),
+            (BinaryOp::BIT_OR, Value::UINT(a), Value::UINT(b)) => Ok(Value::UINT(a | b)),
+            (BinaryOp::BIT_OR | BinaryOp::OR, Value::BOOL(a), Value::BOOL(b)) => {
+                Ok(Value::BOOL(*a || *b))
+            }
+
+            _ => Err(Stop::FAILED),
+        }
+    }
+
+    fn overflow(&mut self, message: String, span: Span) -> Eval {
+        self.eval_error(ErrorId::ERR_OVERFLOW, span, message);
+
+        Err(Stop::FAILED)
+    }
+
+    /// Converts a value to a type: integers to the integer type called for, which may not
+    /// be the one they were evaluated as
+    ///
+    fn coerce(&mut self, value: Value, ty: &Ty, span: Span) -> Eval {
+        match (value, self.shown(ty)) {
+            (Value::INT(n), Ty::UINT) => match u32::try_from(n) {
+                Ok(n) => Ok(Value::UINT(n)),
+                Err(_) => self.overflow(format!("The value `{}` does not fit in `uint`", n), span),
+            },
+
+            (Value::UINT(n), Ty::INT) => match i32::try_from(n) {
+                Ok(n) => Ok(Value::INT(n)),
+                Err(_) => self.overflow(format!("The value `{}` does not fit in `int`", n), span),
+            },
+
+            (Value::TUPLE(values), Ty::TUPLE(types)) if values.len() == types.len() => {
+                let mut elems: Vec<Value> = Vec::new();
+
+                for (value, ty) in values.into_iter().zip(&types) {
+                    elems.push(self.coerce(value, ty, span)?);
+                }
+
+                Ok(Value::TUPLE(elems))
+            }
+
+            (Value::ARRAY(values), Ty::ARRAY(elem) | Ty::FIXED_ARRAY(elem, _)) => {
+                let mut elems: Vec<Value> = Vec::new();
+
+                for value in values {
+                    elems.push(self.coerce(value, &elem, span)?);
+                }
+
+                Ok(Value::ARRAY(elems))
+            }
+
+            (value, Ty::OPTIONAL(elem)) => self.coerce(value, &elem, span),
+            (value, _) => Ok(value),
+        }
+    }
+
+    /// Returns `true` if a pattern matches a value, binding the names it declares
+    ///
+    fn matches(&self, pattern: &Pattern, value: &Value, env: &mut Env) -> bool {
+        match (&pattern.kind, value) {
+            (PatternKind::WILDCARD, _) => true,
+
+            (PatternKind::BINDING(name), _) => {
+                if let Some(symbol) = self.symbol_of(name) {
+                    env.insert(symbol, value.clone());
+                }
+
+                true
+            }
+
+            (PatternKind::LITERAL(lit), _) => equal(&self.literal_value(lit, pattern.span), value),
+
+            (
+                PatternKind::RANGE {
+                    start,
+                    end,
+                    is_inclusive,
+                },
+                _,
+            ) => {
+                let start: Option<Ordering> =
+                    compare(&self.literal_value(start, pattern.span), value);
+                let end: Option<Ordering> = compare(value, &self.literal_value(end, pattern.span));
+
+                match (start, end) {
+                    (Some(start), Some(end)) => {
+                        start.is_le() && (end.is_lt() || *is_inclusive && end.is_eq())
+                    }
+                    _ => false,
+                }
+            }
+
+            (PatternKind::TUPLE(elems), Value::TUPLE(values)) => {
+                elems.len() == values.len()
+                    && elems
+                        .iter()
+                        .zip(values)
+                        .all(|(p, v)| self.matches(p, v, env))
+            }
+
+            (
+                PatternKind::VARIANT { path, elems },
+                Value::VARIANT {
+                    name,
+                    elems: values,
+                    ..
+                },
+            ) => {
+                let elems: &[Pattern] = elems.as_deref().unwrap_or(&[]);
+
+                path[path.len() - 1].name == *name
+                    && elems.len() == values.len()
+                    && elems
+                        .iter()
+                        .zip(values)
+                        .all(|(p, v)| self.matches(p, v, env))
+            }
+
+            (PatternKind::STRUCT { path, fields, .. }, Value::STRUCT { name, .. }) => {
+                let names: Vec<&str> = path.iter().map(|i| i.name.as_str()).collect();
+
+                names.join(".") == *name
+                    && fields.iter().all(|f| match value.field(&f.name.name) {
+                        Some(value) => self.matches(&f.pattern, value, env),
+                        None => false,
+                    })
+            }
+
+            (PatternKind::OR(alts), _) => alts.iter().any(|p| self.matches(p, value, env)),
+            _ => false,
+        }
+    }
+}
+
+fn is_arithmetic(op: BinaryOp) -> bool {
+    matches!(
+        op,
+        BinaryOp::ADD | BinaryOp::SUB | BinaryOp::MULT | BinaryOp::DIV | BinaryOp::MOD
+    )
+}
+
+/// Returns `true` if two values are equal, comparing integers by their value whatever their
+/// type
+///
+fn equal(lhs: &Value, rhs: &Value) -> bool {
+    match compare(lhs, rhs) {
+        Some(ordering) => ordering.is_eq(),
+        None => lhs == rhs,
+    }
+}
+
+/// Returns how two values of a type that can be ordered compare, if they can be
+///
+fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    let int = |value: &Value| match value {
+        Value::INT(n) => Some(i64::from(*n)),
+        Value::UINT(n) => Some(i64::from(*n)),
+        _ => None,
+    };
+
+    if let (Some(a), Some(b)) = (int(lhs), int(rhs)) {
+        return Some(a.cmp(&b));
+    }
+
+    match (lhs, rhs) {
+        (Value::BOOL(a), Value::BOOL(b)) => Some(a.cmp(b)),
+        (Value::CHAR(a), Value::CHAR(b)) => Some(a.cmp(b)),
+        (Value::FLOAT(a), Value::FLOAT(b)) => a.partial_cmp(b),
+        (Value::STRING(a), Value::STRING(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
@@ -0,0 +1,717 @@
+use super::mutability::Bindings;
+use super::Checker;
+use crate::ast::{
+    BindingKind, FnDecl, GenericParam, Iden, Item, ItemKind, MemberKind, Module,
+    ProtocolMemberKind, Type, TypeKind, VariantKind, Visitor,
+};
+use crate::error::{ErrorId, FeoError};
+use crate::sema::types::Ty;
+use crate::sema::{SymbolId, SymbolKind};
+use crate::span::Span;
+use std::collections::{HashMap, HashSet};
+
+/// The most aliases expanded within one another before an alias is taken to refer to itself
+///
+const MAX_ALIAS_DEPTH: usize = 64;
+
+/// The signature of a function, method or constructor
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct FnSig {
+    pub generics: Vec<SymbolId>,
+    pub params: Vec<Ty>,
+
+    /// the number of parameters without a default value, which every call must pass
+    pub required: usize,
+
+    pub ret: Ty,
+    pub is_static: bool,
+}
+
+impl FnSig {
+    /// Returns the type of the function as a value
+    ///
+    pub fn ty(&self) -> Ty {
+        Ty::FUNC {
+            params: self.params.clone(),
+            ret: Box::new(self.ret.clone()),
+        }
+    }
+
+    /// Returns the signature with generic parameters replaced by their arguments
+    ///
+    #[must_use]
+    pub fn subst(&self, subst: &HashMap<SymbolId, Ty>) -> FnSig {
+        FnSig {
+            generics: self.generics.clone(),
+            params: self.params.iter().map(|t| t.subst(subst)).collect(),
+            required: self.required,
+            ret: self.ret.subst(subst),
+            is_static: self.is_static,
+        }
+    }
+}
+
+/// A field of a struct or class
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDef {
+    pub name: String,
+    pub ty: Ty,
+    pub is_static: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum VariantDef {
+    UNIT,
+    TUPLE(Vec<Ty>),
+    STRUCT(Vec<(String, Ty)>),
+}
+
+/// What the checker knows about a struct, enum, class, protocol or distinct type
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDef {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub generics: Vec<SymbolId>,
+    pub fields: Vec<FieldDef>,
+    pub methods: Vec<(String, FnSig)>,
+    pub variants: Vec<(String, VariantDef)>,
+    pub constructor: Option<FnSig>,
+
+    /// the class a class extends, if any
+    pub superclass: Option<Ty>,
+
+    /// the protocols the type declares it conforms to
+    pub protocols: Vec<Ty>,
+
+    /// what a distinct (`type`) type is represented by
+    pub underlying: Option<Ty>,
+}
+
+impl TypeDef {
+    /// Returns the argument of each of the type's generic parameters in an instance of it
+    ///
+    pub fn subst(&self, args: &[Ty]) -> HashMap<SymbolId, Ty> {
+        self.generics
+            .iter()
+            .copied()
+            .zip(args.iter().cloned())
+            .collect()
+    }
+
+    pub fn method(&self, name: &str) -> Option<&FnSig> {
+        self.methods
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, sig)| sig)
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldDef> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    pub fn variant(&self, name: &str) -> Option<&VariantDef> {
+        self.variants
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v)
+    }
+}
+
+/// A member of a value's type
+///
+#[derive(Debug, Clone)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub(super) enum MemberTy {
+    FIELD(Ty),
+    METHOD(FnSig),
+}
+
+impl<'a> Checker<'a> {
+    /// Collects the declarations of a module's types and the signatures of its functions,
+    /// along with its constants and functions, which may be evaluated at compile time
+    ///
+    pub(super) fn collect(&mut self, module: &'a Module) {
+        for item in &module.items {
+            let members = match &item.kind {
+                ItemKind::VAR(decl) if decl.binding == BindingKind::CONST => {
+                    let mut bindings = Bindings(Vec::new());
+                    bindings.visit_pattern(&decl.pattern);
+
+                    for name in bindings.0 {
+                        if let Some(symbol) = self.symbol_of(&name) {
+                            self.const_items.insert(symbol, decl);
+                        }
+                    }
+
+                    continue;
+                }
+
+                ItemKind::FUNC(func) => {
+                    if let Some(symbol) = self.symbol_of(&func.name) {
+                        self.fn_decls.insert(symbol, func);
+                    }
+
+                    continue;
+                }
+
+                ItemKind::ALIAS(alias) => {
+                    if let Some(symbol) = self.symbol_of(&alias.name) {
+                        self.aliases.insert(symbol, alias);
+                    }
+
+                    continue;
+                }
+
+                ItemKind::PROTOCOL(decl) => {
+                    if let Some(symbol) = self.symbol_of(&decl.name) {
+                        self.protocols.insert(symbol, decl);
+                    }
+
+                    continue;
+                }
+
+                ItemKind::STRUCT(decl) => &decl.members,
+                ItemKind::ENUM(decl) => &decl.members,
+                ItemKind::CLASS(decl) => {
+                    if let Some(symbol) = self.symbol_of(&decl.name) {
+                        self.classes.insert(symbol, decl);
+                    }
+
+                    &decl.members
+                }
+
+                _ => continue,
+            };
+
+            for member in members {
+                if let MemberKind::TYPE(alias) = &member.kind {
+                    if let Some(symbol) = self.symbol_of(&alias.name) {
+                        self.aliases.insert(symbol, alias);
+                    }
+                }
+            }
+        }
+
+        // every type is declared before any is filled in, as they may refer to each other
+        for item in &module.items {
+            let (name, generics): (&Iden, &[GenericParam]) = match &item.kind {
+                ItemKind::STRUCT(decl) => (&decl.name, &decl.generics),
+                ItemKind::ENUM(decl) => (&decl.name, &decl.generics),
+                ItemKind::CLASS(decl) => (&decl.name, &decl.generics),
+                ItemKind::PROTOCOL(decl) => (&decl.name, &[]),
+                ItemKind::TYPE(alias) => (&alias.name, &alias.generics),
+                _ => continue,
+            };
+
+            let Some(symbol) = self.symbol_of(name) else {
+                continue;
+            };
+
+            let def = TypeDef {
+                name: name.name.clone(),
+                kind: self.resolution.symbol(symbol).kind,
+                generics: generics
+                    .iter()
+                    .filter_map(|g| self.symbol_of(&g.name))
+                    .collect(),
+                fields: Vec::new(),
+                methods: Vec::new(),
+                variants: Vec::new(),
+                constructor: None,
+                superclass: None,
+                protocols: Vec::new(),
+                underlying: None,
+            };
+
+            self.types.defs.entry(symbol).or_insert(def);
+        }
+
+        for item in &module.items {
+            self.collect_item(item);
+        }
+    }
+
+    fn collect_item(&mut self, item: &Item) {
+        let symbol: Option<SymbolId> = match &item.kind {
+            ItemKind::FUNC(func) => self.symbol_of(&func.name),
+            ItemKind::STRUCT(decl) => self.symbol_of(&decl.name),
+            ItemKind::ENUM(decl) => self.symbol_of(&decl.name),
+            ItemKind::CLASS(decl) => self.symbol_of(&decl.name),
+            ItemKind::PROTOCOL(decl) => self.symbol_of(&decl.name),
+            ItemKind::TYPE(alias) => self.symbol_of(&alias.name),
+            _ => None,
+        };
+
+        let Some(symbol) = symbol else {
+            return;
+        };
+
+        // a duplicate declaration is checked, but does not replace the first
+        if let ItemKind::FUNC(func) = &item.kind {
+            let sig: FnSig = self.fn_sig(func, true);
+            self.types.funcs.entry(symbol).or_insert(sig);
+            return;
+        }
+
+        let Some(mut def) = self.types.defs.get(&symbol).cloned() else {
+            return;
+        };
+
+        if !def.fields.is_empty() || !def.methods.is_empty() || !def.variants.is_empty() {
+            return;
+        }
+
+        let members = match &item.kind {
+            ItemKind::STRUCT(decl) => {
+                self.collect_generics(&decl.generics);
+
+                for field in &decl.fields {
+                    def.fields.push(FieldDef {
+                        name: field.name.name.clone(),
+                        ty: self.lower_type(&field.ty),
+                        is_static: false,
+                    });
+                }
+
+                def.protocols = self.supertypes(&decl.protocols, &mut None);
+                &decl.members[..]
+            }
+
+            ItemKind::ENUM(decl) => {
+                self.collect_generics(&decl.generics);
+
+                for variant in &decl.variants {
+                    let kind: VariantDef = match &variant.kind {
+                        VariantKind::UNIT => VariantDef::UNIT,
+
+                        VariantKind::TUPLE(types) => {
+                            VariantDef::TUPLE(types.iter().map(|t| self.lower_type(t)).collect())
+                        }
+
+                        VariantKind::STRUCT(fields) => VariantDef::STRUCT(
+                            fields
+                                .iter()
+                                .map(|f| (f.name.name.clone(), self.lower_type(&f.ty)))
+                                .collect(),
+                        ),
+                    };
+
+                    def.variants.push((variant.name.name.clone(), kind));
+                }
+
+                def.protocols = self.supertypes(&decl.protocols, &mut None);
+                &decl.members[..]
+            }
+
+            ItemKind::CLASS(decl) => {
+                self.collect_generics(&decl.generics);
+
+                let mut superclass: Option<Ty> = None;
+                def.protocols = self.supertypes(&decl.supertypes, &mut Some(&mut superclass));
+                def.superclass = superclass;
+
+                &decl.members[..]
+            }
+
+            ItemKind::PROTOCOL(decl) => {
+                for member in &decl.members {
+                    if let ProtocolMemberKind::METHOD(func) = &member.kind {
+                        let sig: FnSig = self.fn_sig(func, false);
+                        def.methods.push((func.name.name.clone(), sig));
+                    }
+                }
+
+                &[]
+            }
+
+            ItemKind::TYPE(alias) => {
+                self.collect_generics(&alias.generics);
+                def.underlying = Some(self.lower_type(&alias.ty));
+
+                &[]
+            }
+
+            _ => &[],
+        };
+
+        for member in members {
+            let is_static: bool = member.modifiers.is_static;
+
+            match &member.kind {
+                MemberKind::FIELD(field) => def.fields.push(FieldDef {
+                    name: field.name.name.clone(),
+                    ty: self.lower_type(&field.ty),
+                    is_static,
+                }),
+
+                MemberKind::METHOD(func) => {
+                    let sig: FnSig = self.fn_sig(func, is_static);
+                    def.methods.push((func.name.name.clone(), sig));
+                }
+
+                MemberKind::CONSTRUCTOR(constructor) => {
+                    let params: Vec<Ty> = constructor
+                        .params
+                        .iter()
+                        .map(|p| self.lower_type(&p.ty))
+                        .collect();
+
+                    def.constructor.get_or_insert(FnSig {
+                        generics: Vec::new(),
+                        required: constructor
+                            .params
+                            .iter()
+                            .filter(|p| p.default.is_none())
+                            .count(),
+                        params,
+                        ret: Ty::unit(),
+                        is_static: true,
+                    });
+                }
+
+                MemberKind::TYPE(_) | MemberKind::ERROR => (),
+            }
+        }
+
+        self.types.defs.insert(symbol, def);
+    }
+
+    /// Records the protocols each generic parameter is bound by
+    ///
+    fn collect_generics(&mut self, generics: &[GenericParam]) {
+        for param in generics {
+            if let Some(symbol) = self.symbol_of(&param.name) {
+                let bounds: Vec<Ty> = self.supertypes(&param.bounds, &mut None);
+                self.bounds.insert(symbol, bounds);
+            }
+        }
+    }
+
+    /// Returns the protocols named in a conformance list (or a generic parameter's bounds)
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - the names in the list
+    /// * `superclass` - where to put the class in the list, for a class's list of
+    ///   supertypes. Otherwise only protocols are allowed
+    ///
+    fn supertypes(&mut self, names: &[Iden], superclass: &mut Option<&mut Option<Ty>>) -> Vec<Ty> {
+        let mut protocols: Vec<Ty> = Vec::new();
+        let mut listed: HashMap<SymbolId, Span> = HashMap::new();
+
+        for name in names {
+            let Some(symbol) = self.resolution.uses.get(&name.span).copied() else {
+                continue;
+            };
+
+            if let Some(first) = listed.get(&symbol) {
+                let message = format!("`{}` is listed more than once", name.name);
+                let err = FeoError::at(
+                    ErrorId::ERR_DUPLICATE_CONFORMANCE,
+                    self.file,
+                    name.span,
+                    message,
+                )
+                .with_note(*first, "first listed here");
+                self.errors.push(err);
+
+                continue;
+            }
+
+            listed.insert(symbol, name.span);
+
+            let ty = Ty::NAMED {
+                name: name.name.clone(),
+                symbol,
+                args: Vec::new(),
+            };
+
+            match (
+                self.resolution.symbol(symbol).kind,
+                superclass.as_deref_mut(),
+            ) {
+                (SymbolKind::PROTOCOL, _) => protocols.push(ty),
+                (SymbolKind::IMPORT, _) => (),
+                (SymbolKind::CLASS, Some(slot @ None)) => *slot = Some(ty),
+
+                (SymbolKind::CLASS, Some(Some(_))) => {
+                    let message =
+                        format!("A class can only extend one class, found `{}`", name.name);
+                    self.error(ErrorId::ERR_INVALID_TYPE, name.span, message);
+                }
+
+                (_, Some(_)) => {
+                    let message = format!("Expected a class or protocol, found `{}`", name.name);
+                    self.error(ErrorId::ERR_INVALID_TYPE, name.span, message);
+                }
+
+                (_, None) => {
+                    let message = format!("Expected a protocol, found `{}`", name.name);
+                    self.error(ErrorId::ERR_INVALID_TYPE, name.span, message);
+                }
+            }
+        }
+
+        protocols
+    }
+
+    pub(super) fn fn_sig(&mut self, func: &FnDecl, is_static: bool) -> FnSig {
+        self.collect_generics(&func.generics);
+
+        FnSig {
+            generics: func
+                .generics
+                .iter()
+                .filter_map(|g| self.symbol_of(&g.name))
+                .collect(),
+            params: func.params.iter().map(|p| self.lower_type(&p.ty)).collect(),
+            required: func.params.iter().filter(|p| p.default.is_none()).count(),
+            ret: func.ret.as_ref().map_or(Ty::unit(), |t| self.lower_type(t)),
+            is_static,
+        }
+    }
+
+    /// Returns the type of `self` within a type's methods: the type itself, with its generic
+    /// parameters as arguments
+    ///
+    pub(super) fn self_type(&self, symbol: SymbolId) -> Ty {
+        let generics: &[SymbolId] = self
+            .types
+            .defs
+            .get(&symbol)
+            .map_or(&[], |d| &d.generics[..]);
+
+        Ty::NAMED {
+            name: self.resolution.symbol(symbol).name.clone(),
+            symbol,
+            args: generics
+                .iter()
+                .map(|g| Ty::PARAM {
+                    name: self.resolution.symbol(*g).name.clone(),
+                    symbol: *g,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the type a type annotation refers to
+    ///
+    pub(super) fn lower_type(&mut self, ty: &Type) -> Ty {
+        match &ty.kind {
+            TypeKind::BOOL => Ty::BOOL,
+            TypeKind::CHAR => Ty::CHAR,
+            TypeKind::FLOAT => Ty::FLOAT,
+            TypeKind::INT => Ty::INT,
+            TypeKind::STRING => Ty::STRING,
+            TypeKind::UINT => Ty::UINT,
+            TypeKind::ARRAY(elem) => Ty::ARRAY(Box::new(self.lower_type(elem))),
+
+            TypeKind::FIXED_ARRAY { elem, len } => {
+                let elem: Ty = self.lower_type(elem);
+
+                match self.array_len(len, true) {
+                    Some(len) => Ty::FIXED_ARRAY(Box::new(elem), len),
+                    None => Ty::ARRAY(Box::new(elem)),
+                }
+            }
+
+            TypeKind::TUPLE(elems) => Ty::TUPLE(elems.iter().map(|t| self.lower_type(t)).collect()),
+
+            TypeKind::FUNC { params, ret } => Ty::FUNC {
+                params: params.iter().map(|t| self.lower_type(t)).collect(),
+                ret: Box::new(ret.as_ref().map_or(Ty::unit(), |t| self.lower_type(t))),
+            },
+
+            TypeKind::OPTIONAL(elem) => Ty::OPTIONAL(Box::new(self.lower_type(elem))),
+
+            TypeKind::NAMED { path, args } => {
+                let args: Vec<Ty> = args.iter().map(|t| self.lower_type(t)).collect();
+                self.lower_named(path, args, ty)
+            }
+        }
+    }
+
+    fn lower_named(&mut self, path: &[Iden], args: Vec<Ty>, ty: &Type) -> Ty {
+        // names from other modules are not checked
+        let Some(symbol) = self.resolution.uses.get(&path[0].span).copied() else {
+            return Ty::ERROR;
+        };
+
+        if path.len() > 1 {
+            return Ty::ERROR;
+        }
+
+        let name: &str = &path[0].name;
+
+        match self.resolution.symbol(symbol).kind {
+            SymbolKind::STRUCT
+            | SymbolKind::ENUM
+            | SymbolKind::CLASS
+            | SymbolKind::PROTOCOL
+            | SymbolKind::TYPE => {
+                let expected: usize = self.types.defs.get(&symbol).map_or(0, |d| d.generics.len());
+
+                if args.len() != expected {
+                    let message = format!(
+                        "`{}` takes {} generic argument{}, found {}",
+                        name,
+                        expected,
+                        if expected == 1 { "" } else { "s" },
+                        args.len()
+                    );
+                    self.error(ErrorId::ERR_ARG_COUNT, ty.span, message);
+
+                    return Ty::ERROR;
+                }
+
+                let generics: Vec<SymbolId> = self
+                    .types
+                    .defs
+                    .get(&symbol)
+                    .map_or(Vec::new(), |d| d.generics.clone());
+                self.require_bounds(&generics, &args, ty.span);
+
+                Ty::NAMED {
+                    name: name.to_string(),
+                    symbol,
+                    args,
+                }
+            }
+
+            SymbolKind::ALIAS | SymbolKind::ASSOC_TYPE => {
+                let Some(alias) = self.aliases.get(&symbol).copied() else {
+                    // a protocol's associated type stands for whatever a conforming type binds
+                    return Ty::PARAM {
+                        name: name.to_string(),
+                        symbol,
+                    };
+                };
+
+                if self.alias_depth >= MAX_ALIAS_DEPTH {
+                    let message = format!("The alias `{}` refers to itself", name);
+                    self.error(ErrorId::ERR_INVALID_TYPE, ty.span, message);
+
+                    return Ty::ERROR;
+                }
+
+                let generics: Vec<SymbolId> = alias
+                    .generics
+                    .iter()
+                    .filter_map(|g| self.symbol_of(&g.name))
+                    .collect();
+
+                if args.len() != generics.len() {
+                    let message = format!(
+                        "`{}` takes {} generic argument{}, found {}",
+                        name,
+                        generics.len(),
+                        if generics.len() == 1 { "" } else { "s" },
+                        args.len()
+                    );
+                    self.error(ErrorId::ERR_ARG_COUNT, ty.span, message);
+
+                    return Ty::ERROR;
+                }
+
+                self.alias_depth += 1;
+                let aliased: Ty = self.lower_type(&alias.ty);
+                self.alias_depth -= 1;
+
+                let subst: HashMap<SymbolId, Ty> = generics.into_iter().zip(args).collect();
+                aliased.subst(&subst)
+            }
+
+            SymbolKind::GENERIC => Ty::PARAM {
+                name: name.to_string(),
+                symbol,
+            },
+
+            SymbolKind::IMPORT => Ty::ERROR,
+
+            SymbolKind::VAR(_) | SymbolKind::PARAM | SymbolKind::FUNC => {
+                let message = format!("Expected a type, found the value `{}`", name);
+                self.error(ErrorId::ERR_INVALID_TYPE, path[0].span, message);
+
+                Ty::ERROR
+            }
+        }
+    }
+
+    /// Returns every type a type is a subtype of: the superclasses of a class, and the
+    /// protocols it or any of them conform to, nearest first
+    ///
+    pub(super) fn ancestors(&self, ty: &Ty) -> Vec<Ty> {
+        let mut ancestors: Vec<Ty> = Vec::new();
+        let mut seen: HashSet<SymbolId> = HashSet::new();
+        let mut queue: Vec<Ty> = vec![ty.clone()];
+
+        while !queue.is_empty() {
+            let ty: Ty = queue.remove(0);
+
+            let Ty::NAMED { symbol, args, .. } = &ty else {
+                continue;
+            };
+
+            if !seen.insert(*symbol) {
+                continue;
+            }
+
+            if let Some(def) = self.types.defs.get(symbol) {
+                let subst: HashMap<SymbolId, Ty> = def.subst(args);
+
+                for parent in def.superclass.iter().chain(&def.protocols) {
+                    let parent: Ty = parent.subst(&subst);
+                    queue.push(parent.clone());
+                    ancestors.push(parent);
+                }
+            }
+        }
+
+        ancestors
+    }
+
+    /// Returns an instance member of a type, looking through its superclasses and the
+    /// protocols it conforms to (or, for a generic parameter, its bounds)
+    ///
+    pub(super) fn member(&self, ty: &Ty, name: &str) -> Option<MemberTy> {
+        let candidates: Vec<Ty> = match ty {
+            Ty::NAMED { .. } => [vec![ty.clone()], self.ancestors(ty)].concat(),
+
+            Ty::PARAM { symbol, .. } => {
+                let bounds: &[Ty] = self.bounds.get(symbol).map_or(&[], |b| &b[..]);
+                bounds
+                    .iter()
+                    .flat_map(|b| [vec![b.clone()], self.ancestors(b)].concat())
+                    .collect()
+            }
+
+            _ => Vec::new(),
+        };
+
+        for candidate in candidates {
+            let Ty::NAMED { symbol, args, .. } = &candidate else {
+                continue;
+            };
+
+            let Some(def) = self.types.defs.get(symbol) else {
+                continue;
+            };
+
+            let subst: HashMap<SymbolId, Ty> = def.subst(args);
+
+            if let Some(field) = def.field(name).filter(|f| !f.is_static) {
+                return Some(MemberTy::FIELD(field.ty.subst(&subst)));
+            }
+
+            if let Some(sig) = def.method(name).filter(|m| !m.is_static) {
+                return Some(MemberTy::METHOD(sig.subst(&subst)));
+            }
+        }
+
+        None
+    }
+}
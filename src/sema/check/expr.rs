@@ -0,0 +1,1137 @@
+use super::defs::{FnSig, MemberTy, VariantDef};
+use super::{Checker, Expected, Loop};
+use crate::ast::{BinaryOp, Block, Expr, ExprKind, FieldInit, Iden, Literal, MatchArm, UnaryOp};
+use crate::error::{ErrorId, FeoError};
+use crate::sema::types::Ty;
+use crate::sema::{SymbolId, SymbolKind};
+use crate::span::Span;
+use std::collections::HashMap;
+
+impl<'a> Checker<'a> {
+    /// Returns the type of an expression, reporting any type errors within it
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - the expression
+    /// * `expected` - the type the expression should have, if it is known. This only guides
+    ///   the types of literals and generic arguments; it is up to the caller to report a
+    ///   value that does not fit
+    ///
+    pub(super) fn expr(&mut self, expr: &Expr, expected: Option<&Ty>) -> Ty {
+        let expected: Option<Ty> = expected.map(|t| self.resolved(t));
+
+        let ty: Ty = self.expr_kind(expr, expected.as_ref());
+        let ty: Ty = self.resolved(&ty);
+        self.types.exprs.insert(expr.span, ty.clone());
+
+        ty
+    }
+
+    fn expr_kind(&mut self, expr: &Expr, expected: Option<&Ty>) -> Ty {
+        match &expr.kind {
+            ExprKind::LITERAL(lit) => self.literal(lit, expected),
+            ExprKind::IDEN(name) => self.value(name, expr.span),
+            ExprKind::SELF => self.self_ty.clone().unwrap_or(Ty::ERROR),
+            ExprKind::SUPER => self.super_type(expr.span),
+            ExprKind::PAREN(inner) => self.expr(inner, expected),
+
+            ExprKind::TUPLE(elems) => {
+                let hints: &[Ty] = match expected {
+                    Some(Ty::TUPLE(hints)) => hints,
+                    _ => &[],
+                };
+
+                let elems: Vec<Ty> = elems
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| self.expr(e, hints.get(i)))
+                    .collect();
+
+                Ty::TUPLE(elems)
+            }
+
+            ExprKind::ARRAY(elems) => {
+                let mut elem: Option<Ty> = match expected {
+                    Some(Ty::ARRAY(elem) | Ty::FIXED_ARRAY(elem, _)) => Some((**elem).clone()),
+                    _ => None,
+                };
+
+                for value in elems {
+                    let found: Ty = self.expr(value, elem.as_ref());
+
+                    match &elem {
+                        Some(ty) => {
+                            let expected = Expected { ty, because: None };
+                            self.expect(&found, expected, value.span);
+                        }
+
+                        None => elem = Some(found),
+                    }
+                }
+
+                let elem: Ty = elem.unwrap_or_else(|| self.fresh());
+                Ty::FIXED_ARRAY(Box::new(elem), elems.len())
+            }
+
+            ExprKind::ARRAY_REPEAT { value, len } => {
+                let elem: Option<Ty> = match expected {
+                    Some(Ty::ARRAY(elem) | Ty::FIXED_ARRAY(elem, _)) => Some((**elem).clone()),
+                    _ => None,
+                };
+
+                let elem: Ty = self.expr(value, elem.as_ref());
+                self.integer(len);
+
+                match self.array_len(len, false) {
+                    Some(len) => Ty::FIXED_ARRAY(Box::new(elem), len),
+                    None => Ty::ARRAY(Box::new(elem)),
+                }
+            }
+
+            ExprKind::BLOCK(block) => self.block(block, expected),
+
+            ExprKind::UNARY { op, expr: inner } => {
+                let found: Ty = self.expr(inner, expected);
+
+                match (op, &found) {
+                    (_, Ty::ERROR | Ty::NEVER) => Ty::ERROR,
+
+                    // negating an integer literal whose type is not yet known rules out `uint`
+                    (UnaryOp::NEG, ty) if self.is_int_var(ty) => {
+                        let negated: Ty = self.fresh_int(true);
+                        self.unify(&found, &negated);
+
+                        negated
+                    }
+
+                    (UnaryOp::NEG, ty) if self.is_numeric(ty) && *ty != Ty::UINT => found,
+                    (UnaryOp::NOT, _) if self.fits(&found, &Ty::BOOL) => Ty::BOOL,
+
+                    _ => {
+                        let message = format!("Cannot apply `{}` to `{}`", op, self.shown(&found));
+                        self.error(ErrorId::ERR_INVALID_OPERANDS, expr.span, message);
+
+                        Ty::ERROR
+                    }
+                }
+            }
+
+            ExprKind::BINARY { op, lhs, rhs } => {
+                let lhs_ty: Ty = self.expr(lhs, None);
+                let rhs_ty: Ty = self.expr(rhs, Some(&lhs_ty));
+
+                self.binary(*op, &lhs_ty, &rhs_ty, expr.span)
+            }
+
+            ExprKind::TERNARY { cond, then, els } => {
+                self.condition(cond);
+
+                let then_ty: Ty = self.expr(then, expected);
+                let els_ty: Ty = self.expr(els, expected.or(Some(&then_ty)));
+
+                let because: (Span, &'static str) =
+                    (then.span, "expected because of the first branch");
+                self.join(then_ty, els_ty, els.span, because)
+            }
+
+            ExprKind::RANGE { start, end, .. } => {
+                let elem: Option<Ty> = match expected {
+                    Some(Ty::RANGE(elem)) => Some((**elem).clone()),
+                    _ => None,
+                };
+
+                let start_ty: Ty = self.expr(start, elem.as_ref());
+                let end_ty: Ty = self.expr(end, Some(&start_ty));
+
+                let because: (Span, &'static str) =
+                    (start.span, "expected because of the start of the range");
+                let elem: Ty = self.join(start_ty, end_ty, end.span, because);
+                let elem: Ty = self.resolved(&elem);
+
+                match elem {
+                    Ty::ERROR | Ty::NEVER => Ty::RANGE(Box::new(Ty::ERROR)),
+                    _ if self.is_integer(&elem) || elem == Ty::CHAR => Ty::RANGE(Box::new(elem)),
+
+                    _ => {
+                        let message = format!(
+                            "Expected a range of integers or characters, found a range of `{}`",
+                            self.shown(&elem)
+                        );
+                        self.error(ErrorId::ERR_INVALID_OPERANDS, expr.span, message);
+
+                        Ty::RANGE(Box::new(Ty::ERROR))
+                    }
+                }
+            }
+
+            ExprKind::ASSIGN { op, target, value } => {
+                let target_ty: Ty = self.expr(target, None);
+                let value_ty: Ty = self.expr(value, Some(&target_ty));
+
+                let found: Ty = match op.binary_op() {
+                    Some(op) => self.binary(op, &target_ty, &value_ty, expr.span),
+                    None => value_ty,
+                };
+
+                let expected = Expected {
+                    ty: &target_ty,
+                    because: None,
+                };
+                self.expect(&found, expected, value.span);
+                self.assign_target(target, *op);
+
+                Ty::unit()
+            }
+
+            ExprKind::FIELD { expr: base, field } => self.field(base, field, expected),
+
+            ExprKind::INDEX { expr: base, index } => {
+                let base_ty: Ty = self.expr(base, None);
+                let base_ty: Ty = self.known(base_ty, base.span);
+                self.integer(index);
+
+                match base_ty {
+                    Ty::ARRAY(elem) | Ty::FIXED_ARRAY(elem, _) => *elem,
+                    Ty::ERROR | Ty::NEVER => Ty::ERROR,
+
+                    _ => {
+                        let message = format!(
+                            "Cannot index into a value of type `{}`",
+                            self.shown(&base_ty)
+                        );
+                        self.error(ErrorId::ERR_INVALID_OPERANDS, base.span, message);
+
+                        Ty::ERROR
+                    }
+                }
+            }
+
+            ExprKind::CALL { callee, args } => self.call(callee, args, expr.span, expected),
+
+            ExprKind::IF { cond, then, els } => {
+                self.condition(cond);
+
+                let then_ty: Ty = self.block(then, expected);
+
+                // without an `else`, the value of the `if` is never used
+                let Some(els) = els else {
+                    return Ty::unit();
+                };
+
+                let els_ty: Ty = self.expr(els, expected.or(Some(&then_ty)));
+                let because: (Span, &'static str) = (
+                    Self::block_value_span(then),
+                    "expected because of the first branch",
+                );
+                let span: Span = match &els.kind {
+                    ExprKind::BLOCK(block) => Self::block_value_span(block),
+                    _ => els.span,
+                };
+
+                self.join(then_ty, els_ty, span, because)
+            }
+
+            ExprKind::MATCH { scrutinee, arms } => self.match_expr(scrutinee, arms, expected),
+
+            ExprKind::WHILE { label, cond, body } => {
+                self.condition(cond);
+                self.loop_body(label, false, body);
+
+                Ty::unit()
+            }
+
+            ExprKind::LOOP { label, body } => {
+                let state: Loop = self.loop_body(label, true, body);
+
+                match (state.breaks, state.value) {
+                    (false, _) => Ty::NEVER,
+                    (true, value) => value.unwrap_or(Ty::unit()),
+                }
+            }
+
+            ExprKind::FOR {
+                label,
+                pattern,
+                iter,
+                body,
+            } => {
+                let iter_ty: Ty = self.expr(iter, None);
+                let iter_ty: Ty = self.known(iter_ty, iter.span);
+
+                let elem: Ty = match iter_ty {
+                    Ty::ARRAY(elem) | Ty::FIXED_ARRAY(elem, _) | Ty::RANGE(elem) => *elem,
+                    Ty::ERROR | Ty::NEVER => Ty::ERROR,
+
+                    _ => {
+                        let message = format!(
+                            "Expected an array or a range, found `{}`",
+                            self.shown(&iter_ty)
+                        );
+                        self.error(ErrorId::ERR_TYPE_MISMATCH, iter.span, message);
+
+                        Ty::ERROR
+                    }
+                };
+
+                self.pattern(pattern, &elem);
+                self.loop_body(label, false, body);
+
+                Ty::unit()
+            }
+
+            ExprKind::BREAK { label, value } => {
+                self.break_expr(label.as_ref(), value.as_deref(), expr.span);
+                Ty::NEVER
+            }
+
+            ExprKind::CONTINUE { .. } => Ty::NEVER,
+
+            ExprKind::RETURN(value) => {
+                let ret: Option<(Ty, Option<Span>)> = self.ret.clone();
+                let found: Ty = match value {
+                    Some(value) => self.expr(value, ret.as_ref().map(|(t, _)| t)),
+                    None => Ty::unit(),
+                };
+
+                if let Some((ty, span)) = &ret {
+                    let expected = Expected {
+                        ty,
+                        because: span.map(|s| (s, "expected because of the return type")),
+                    };
+                    self.expect(
+                        &found,
+                        expected,
+                        value.as_ref().map_or(expr.span, |v| v.span),
+                    );
+                }
+
+                Ty::NEVER
+            }
+
+            ExprKind::NEW { class, args } => self.new_expr(class, args, expr.span, expected),
+
+            ExprKind::STRUCT { path, fields } => {
+                self.struct_expr(path, fields, expr.span, expected)
+            }
+        }
+    }
+
+    pub(super) fn literal(&mut self, lit: &Literal, expected: Option<&Ty>) -> Ty {
+        match lit {
+            Literal::BOOL(_) => Ty::BOOL,
+            Literal::CHAR(_) => Ty::CHAR,
+            Literal::FLOAT(_) => Ty::FLOAT,
+            Literal::STRING(_) => Ty::STRING,
+
+            // an integer literal is whichever integer type is called for, which may only be
+            // worked out later, and otherwise an `int`. A negative one is never a `uint`
+            Literal::INT(n) => match expected {
+                Some(Ty::INT) => Ty::INT,
+                Some(Ty::UINT) if *n >= 0 => Ty::UINT,
+                Some(Ty::OPTIONAL(elem)) if **elem == Ty::INT => Ty::INT,
+                Some(Ty::OPTIONAL(elem)) if **elem == Ty::UINT && *n >= 0 => Ty::UINT,
+                _ => self.fresh_int(*n < 0),
+            },
+        }
+    }
+
+    /// Returns the type of a name used as a value
+    ///
+    fn value(&mut self, name: &str, span: Span) -> Ty {
+        let Some(symbol) = self.resolution.uses.get(&span).copied() else {
+            return Ty::ERROR;
+        };
+
+        match self.resolution.symbol(symbol).kind {
+            SymbolKind::VAR(_) | SymbolKind::PARAM => self
+                .types
+                .symbols
+                .get(&symbol)
+                .cloned()
+                .unwrap_or(Ty::ERROR),
+
+            SymbolKind::FUNC => match self.types.funcs.get(&symbol) {
+                Some(sig) => {
+                    let sig: FnSig = sig.clone();
+                    self.instantiate(&sig, span).ty()
+                }
+                None => Ty::ERROR,
+            },
+
+            SymbolKind::IMPORT => Ty::ERROR,
+
+            _ => {
+                let message = format!("Expected a value, found the type `{}`", name);
+                self.error(ErrorId::ERR_INVALID_TYPE, span, message);
+
+                Ty::ERROR
+            }
+        }
+    }
+
+    /// Returns the type an expression names, if it is the name of a user-defined type (as
+    /// in `Shape.Dot` or `Point.origin()`)
+    ///
+    pub(super) fn type_name(&self, expr: &Expr) -> Option<SymbolId> {
+        let ExprKind::IDEN(_) = &expr.kind else {
+            return None;
+        };
+
+        self.resolution
+            .uses
+            .get(&expr.span)
+            .copied()
+            .filter(|s| self.types.defs.contains_key(s))
+    }
+
+    /// Returns the superclass of the class whose method is being checked
+    ///
+    pub(super) fn superclass(&self) -> Option<Ty> {
+        let Some(Ty::NAMED { symbol, args, .. }) = &self.self_ty else {
+            return None;
+        };
+
+        let def = self.types.defs.get(symbol)?;
+        def.superclass.as_ref().map(|s| s.subst(&def.subst(args)))
+    }
+
+    /// Returns an instance of a generic type, taking its arguments from the expected type if
+    /// that is an instance of the same type, and otherwise inferring them
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - the type
+    /// * `expected` - the type called for, if any
+    /// * `span` - where the type is named, for inferred arguments that do not meet their
+    ///   bounds
+    ///
+    pub(super) fn instance(&mut self, symbol: SymbolId, expected: Option<&Ty>, span: Span) -> Ty {
+        let name: String = self.resolution.symbol(symbol).name.clone();
+
+        if let Some(Ty::NAMED {
+            symbol: expected_symbol,
+            args,
+            ..
+        }) = expected
+        {
+            if *expected_symbol == symbol {
+                return Ty::NAMED {
+                    name,
+                    symbol,
+                    args: args.clone(),
+                };
+            }
+        }
+
+        let generics: Vec<SymbolId> = self
+            .types
+            .defs
+            .get(&symbol)
+            .map_or(Vec::new(), |d| d.generics.clone());
+        let args: Vec<Ty> = generics.iter().map(|_| self.fresh()).collect();
+        self.require_bounds(&generics, &args, span);
+
+        Ty::NAMED { name, symbol, args }
+    }
+
+    /// Checks that an expression is a `bool`, as the condition of an `if` or loop
+    ///
+    fn condition(&mut self, cond: &Expr) {
+        let found: Ty = self.expr(cond, Some(&Ty::BOOL));
+        let expected = Expected {
+            ty: &Ty::BOOL,
+            because: None,
+        };
+
+        self.expect(&found, expected, cond.span);
+    }
+
+    /// Checks that an expression is an integer, as an index or an array length
+    ///
+    fn integer(&mut self, expr: &Expr) {
+        let found: Ty = self.expr(expr, None);
+
+        if !self.is_integer(&found) && !self.fits(&found, &Ty::INT) {
+            let message = format!("Expected an integer, found `{}`", self.shown(&found));
+            self.error(ErrorId::ERR_TYPE_MISMATCH, expr.span, message);
+        }
+    }
+
+    /// Returns the type of a binary operation, reporting operands it cannot be applied to
+    ///
+    pub(super) fn binary(&mut self, op: BinaryOp, lhs: &Ty, rhs: &Ty, span: Span) -> Ty {
+        let is_comparison: bool = matches!(
+            op,
+            BinaryOp::EQ
+                | BinaryOp::NOT_EQ
+                | BinaryOp::LESS
+                | BinaryOp::LESS_EQ
+                | BinaryOp::GRTR
+                | BinaryOp::GRTR_EQ
+        );
+
+        if matches!(lhs, Ty::ERROR | Ty::NEVER) || matches!(rhs, Ty::ERROR | Ty::NEVER) {
+            return match is_comparison {
+                true => Ty::BOOL,
+                false => Ty::ERROR,
+            };
+        }
+
+        // each operand's type may be worked out from the other's
+        let same: bool = self.fits(rhs, lhs);
+        let lhs: Ty = self.resolved(lhs);
+        let rhs: Ty = self.resolved(rhs);
+
+        let ty: Option<Ty> = match op {
+            BinaryOp::ADD if lhs == Ty::STRING && same => Some(Ty::STRING),
+
+            BinaryOp::ADD | BinaryOp::SUB | BinaryOp::MULT | BinaryOp::DIV | BinaryOp::MOD => {
+                (same && self.is_numeric(&lhs)).then(|| lhs.clone())
+            }
+
+            BinaryOp::EQ | BinaryOp::NOT_EQ => (same || self.fits(&lhs, &rhs)).then_some(Ty::BOOL),
+
+            BinaryOp::LESS | BinaryOp::LESS_EQ | BinaryOp::GRTR | BinaryOp::GRTR_EQ => {
+                let is_ordered: bool =
+                    self.is_numeric(&lhs) || matches!(lhs, Ty::CHAR | Ty::STRING);
+                (same && is_ordered).then_some(Ty::BOOL)
+            }
+
+            BinaryOp::AND | BinaryOp::OR => {
+                (self.fits(&lhs, &Ty::BOOL) && self.fits(&rhs, &Ty::BOOL)).then_some(Ty::BOOL)
+            }
+
+            BinaryOp::BIT_AND | BinaryOp::BIT_OR => {
+                let is_bits: bool = self.is_integer(&lhs) || lhs == Ty::BOOL;
+                (same && is_bits).then(|| lhs.clone())
+            }
+        };
+
+        match ty {
+            Some(ty) => ty,
+
+            None => {
+                let message = format!(
+                    "Cannot apply `{}` to `{}` and `{}`",
+                    op,
+                    self.shown(&lhs),
+                    self.shown(&rhs)
+                );
+                self.error(ErrorId::ERR_INVALID_OPERANDS, span, message);
+
+                match is_comparison {
+                    true => Ty::BOOL,
+                    false => Ty::ERROR,
+                }
+            }
+        }
+    }
+
+    /// Returns the type of a value that is one of two branches (e.g. of an `if`), reporting
+    /// the second branch if it does not fit the first
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - the type of the branches so far
+    /// * `next` - the type of the next branch
+    /// * `span` - the span of the next branch's value
+    /// * `because` - the span of the value the branches so far are expected to fit, and a note
+    ///   on it
+    ///
+    fn join(&mut self, first: Ty, next: Ty, span: Span, because: (Span, &'static str)) -> Ty {
+        if first == Ty::NEVER {
+            return next;
+        }
+
+        if self.fits(&next, &first) {
+            return first;
+        }
+
+        if self.fits(&first, &next) {
+            return next;
+        }
+
+        let expected = Expected {
+            ty: &first,
+            because: Some(because),
+        };
+        self.expect(&next, expected, span);
+
+        // the value has no one type, so it is not reported again where it is used
+        Ty::ERROR
+    }
+
+    fn match_expr(&mut self, scrutinee: &Expr, arms: &[MatchArm], expected: Option<&Ty>) -> Ty {
+        let scrutinee_ty: Ty = self.expr(scrutinee, None);
+        let mut ty: Option<(Ty, Span)> = None;
+
+        for arm in arms {
+            self.pattern(&arm.pattern, &scrutinee_ty);
+
+            if let Some(guard) = &arm.guard {
+                self.condition(guard);
+            }
+
+            let hint: Option<&Ty> = expected.or(ty.as_ref().map(|(t, _)| t));
+            let found: Ty = self.expr(&arm.body, hint);
+
+            ty = Some(match ty {
+                None => (found, arm.body.span),
+
+                Some((first, span)) => {
+                    let because: (Span, &'static str) = (span, "expected because of the first arm");
+
+                    let joined: Ty = self.join(first.clone(), found, arm.body.span, because);
+                    let span: Span = if first == Ty::NEVER {
+                        arm.body.span
+                    } else {
+                        span
+                    };
+
+                    (joined, span)
+                }
+            });
+        }
+
+        ty.map_or(Ty::NEVER, |(t, _)| t)
+    }
+
+    /// Checks the body of a loop, returning what was found about the values it breaks with
+    ///
+    fn loop_body(&mut self, label: &Option<Iden>, has_value: bool, body: &Block) -> Loop {
+        self.loops.push(Loop {
+            label: label.as_ref().map(|l| l.name.clone()),
+            has_value,
+            value: None,
+            breaks: false,
+        });
+
+        let found: Ty = self.block(body, Some(&Ty::unit()));
+        let expected = Expected {
+            ty: &Ty::unit(),
+            because: None,
+        };
+        self.expect(&found, expected, Self::block_value_span(body));
+
+        self.loops.pop().unwrap()
+    }
+
+    fn break_expr(&mut self, label: Option<&Iden>, value: Option<&Expr>, span: Span) {
+        let index: Option<usize> = match label {
+            Some(label) => self
+                .loops
+                .iter()
+                .rposition(|l| l.label.as_deref() == Some(label.name.as_str())),
+            None => self.loops.len().checked_sub(1),
+        };
+
+        // the resolver has already reported a `break` outside any loop, or with an unknown
+        // label
+        let Some(index) = index else {
+            if let Some(value) = value {
+                self.expr(value, None);
+            }
+
+            return;
+        };
+
+        let (has_value, prev): (bool, Option<Ty>) = {
+            let state: &mut Loop = &mut self.loops[index];
+            state.breaks = true;
+
+            (state.has_value, state.value.clone())
+        };
+
+        let found: Ty = match value {
+            Some(value) => self.expr(value, prev.as_ref()),
+            None => Ty::unit(),
+        };
+        let value_span: Span = value.map_or(span, |v| v.span);
+
+        // only a `loop` has a value; the value of a `while` or `for` is always `()`
+        let expected: Option<Ty> = match has_value {
+            true => prev,
+            false => Some(Ty::unit()),
+        };
+
+        match expected {
+            Some(ty) => {
+                let expected = Expected {
+                    ty: &ty,
+                    because: None,
+                };
+                self.expect(&found, expected, value_span);
+            }
+
+            None => self.loops[index].value = Some(found),
+        }
+    }
+
+    /// Returns the type of a field, method, enum variant or static member
+    ///
+    fn field(&mut self, base: &Expr, field: &Iden, expected: Option<&Ty>) -> Ty {
+        if let Some(symbol) = self.type_name(base) {
+            return self.static_member(symbol, field, expected);
+        }
+
+        let base_ty: Ty = self.expr(base, None);
+        let base_ty: Ty = self.known(base_ty, base.span);
+
+        match self.member(&base_ty, &field.name) {
+            Some(MemberTy::FIELD(ty)) => ty,
+            Some(MemberTy::METHOD(sig)) => {
+                self.dispatch(&base_ty, field);
+                self.instantiate(&sig, field.span).ty()
+            }
+
+            None if matches!(base_ty, Ty::ERROR | Ty::NEVER) => Ty::ERROR,
+
+            None => {
+                self.undefined_member(&base_ty, field);
+                Ty::ERROR
+            }
+        }
+    }
+
+    fn undefined_member(&mut self, ty: &Ty, field: &Iden) {
+        let message = format!(
+            "No field or method `{}` on type `{}`",
+            field.name,
+            self.shown(ty)
+        );
+        self.error(ErrorId::ERR_UNDEFINED_MEMBER, field.span, message);
+    }
+
+    /// Returns the type of a member accessed through a type's name: an enum variant, or a
+    /// static field or method
+    ///
+    fn static_member(&mut self, symbol: SymbolId, field: &Iden, expected: Option<&Ty>) -> Ty {
+        let def = &self.types.defs[&symbol];
+
+        if let Some(variant) = def.variant(&field.name).cloned() {
+            let ty: Ty = self.instance(symbol, expected, field.span);
+            let Ty::NAMED { args, .. } = &ty else {
+                unreachable!()
+            };
+            let subst: HashMap<SymbolId, Ty> = self.types.defs[&symbol].subst(args);
+
+            return match variant {
+                VariantDef::UNIT => ty,
+
+                // a tuple variant is a function that builds it
+                VariantDef::TUPLE(params) => Ty::FUNC {
+                    params: params.iter().map(|t| t.subst(&subst)).collect(),
+                    ret: Box::new(ty),
+                },
+
+                VariantDef::STRUCT(_) => {
+                    let message = format!(
+                        "The variant `{}` has fields, and must be built with `{}.{} {{ .. }}`",
+                        field.name,
+                        def_name(&ty),
+                        field.name
+                    );
+                    self.error(ErrorId::ERR_INVALID_TYPE, field.span, message);
+
+                    Ty::ERROR
+                }
+            };
+        }
+
+        // the arguments of a generic type are inferred from how its static member is used
+        let generics: Vec<SymbolId> = self.types.defs[&symbol].generics.clone();
+        let args: Vec<Ty> = generics.iter().map(|_| self.fresh()).collect();
+        self.require_bounds(&generics, &args, field.span);
+        let subst: HashMap<SymbolId, Ty> = generics.into_iter().zip(args).collect();
+        let def = &self.types.defs[&symbol];
+
+        if let Some(field) = def.field(&field.name).filter(|f| f.is_static) {
+            return field.ty.subst(&subst);
+        }
+
+        if let Some(sig) = def.method(&field.name).filter(|m| m.is_static) {
+            let sig: FnSig = sig.subst(&subst);
+            return self.instantiate(&sig, field.span).ty();
+        }
+
+        let message = format!(
+            "No static member or variant `{}` on type `{}`",
+            field.name, def.name
+        );
+        self.error(ErrorId::ERR_UNDEFINED_MEMBER, field.span, message);
+
+        Ty::ERROR
+    }
+
+    fn call(&mut self, callee: &Expr, args: &[Expr], span: Span, expected: Option<&Ty>) -> Ty {
+        // `super(..)` calls the superclass's constructor
+        if let ExprKind::SUPER = callee.kind {
+            let superclass: Ty = self.super_type(callee.span);
+            self.types.exprs.insert(callee.span, superclass.clone());
+
+            let sig: Option<FnSig> = match &superclass {
+                Ty::NAMED { symbol, args, .. } => {
+                    let def = &self.types.defs[symbol];
+                    Some(def.constructor.clone().unwrap_or(FnSig {
+                        generics: Vec::new(),
+                        params: Vec::new(),
+                        required: 0,
+                        ret: Ty::unit(),
+                        is_static: true,
+                    }))
+                    .map(|sig| sig.subst(&def.subst(args)))
+                }
+
+                _ => None,
+            };
+
+            return self.apply(sig.as_ref(), args, span, None);
+        }
+
+        // calling a distinct type converts a value of its underlying type to it
+        if let Some(symbol) = self.type_name(callee) {
+            if let Some(underlying) = self.types.defs[&symbol].underlying.clone() {
+                let ty: Ty = self.instance(symbol, expected, callee.span);
+                self.types.exprs.insert(callee.span, ty.clone());
+
+                let Ty::NAMED { args: ty_args, .. } = &ty else {
+                    unreachable!()
+                };
+                let underlying: Ty = underlying.subst(&self.types.defs[&symbol].subst(ty_args));
+
+                let sig = FnSig {
+                    generics: Vec::new(),
+                    params: vec![underlying],
+                    required: 1,
+                    ret: ty,
+                    is_static: true,
+                };
+
+                return self.apply(Some(&sig), args, span, None);
+            }
+        }
+
+        let callee_ty: Ty = self.expr(callee, None);
+        let callee_ty: Ty = self.known(callee_ty, callee.span);
+
+        match callee_ty {
+            Ty::FUNC { params, ret } => {
+                let sig = FnSig {
+                    generics: Vec::new(),
+                    required: self.required_args(callee).unwrap_or(params.len()),
+                    params,
+                    ret: *ret,
+                    is_static: true,
+                };
+
+                self.apply(Some(&sig), args, span, expected)
+            }
+
+            Ty::ERROR | Ty::NEVER => self.apply(None, args, span, None),
+
+            _ => {
+                let message = format!("Expected a function, found `{}`", self.shown(&callee_ty));
+                self.error(ErrorId::ERR_TYPE_MISMATCH, callee.span, message);
+
+                self.apply(None, args, span, None)
+            }
+        }
+    }
+
+    /// Returns the number of arguments a call to a function or method must pass, which is
+    /// fewer than its parameters if some have defaults
+    ///
+    fn required_args(&self, callee: &Expr) -> Option<usize> {
+        match &callee.kind {
+            ExprKind::IDEN(_) => {
+                let symbol: SymbolId = self.resolution.uses.get(&callee.span).copied()?;
+                self.types.funcs.get(&symbol).map(|sig| sig.required)
+            }
+
+            ExprKind::FIELD { expr: base, field } => {
+                if let Some(symbol) = self.type_name(base) {
+                    let def = &self.types.defs[&symbol];
+                    return def.method(&field.name).map(|sig| sig.required);
+                }
+
+                let base_ty: &Ty = self.types.exprs.get(&base.span)?;
+
+                match self.member(base_ty, &field.name)? {
+                    MemberTy::METHOD(sig) => Some(sig.required),
+                    MemberTy::FIELD(_) => None,
+                }
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Checks the arguments of a call against a signature, returning the call's type
+    ///
+    /// # Arguments
+    ///
+    /// * `sig` - the signature, or `None` if it is not known, in which case the arguments
+    ///   are checked on their own
+    /// * `args` - the arguments
+    /// * `span` - the span of the call
+    /// * `expected` - the type the call should have, if known, from which the signature's
+    ///   generic arguments are first inferred
+    ///
+    fn apply(
+        &mut self,
+        sig: Option<&FnSig>,
+        args: &[Expr],
+        span: Span,
+        expected: Option<&Ty>,
+    ) -> Ty {
+        let Some(sig) = sig else {
+            for arg in args {
+                self.expr(arg, None);
+            }
+
+            return Ty::ERROR;
+        };
+
+        // if the call does not fit, it is reported where its value is used
+        if let Some(expected) = expected {
+            self.fits(&sig.ret, expected);
+        }
+
+        if args.len() < sig.required || args.len() > sig.params.len() {
+            let count: String = match sig.required == sig.params.len() {
+                true => sig.params.len().to_string(),
+                false => format!("{} to {}", sig.required, sig.params.len()),
+            };
+
+            let message = format!(
+                "Expected {} argument{}, found {}",
+                count,
+                if sig.params.len() == 1 { "" } else { "s" },
+                args.len()
+            );
+            self.error(ErrorId::ERR_ARG_COUNT, span, message);
+        }
+
+        for (i, arg) in args.iter().enumerate() {
+            let param: Option<&Ty> = sig.params.get(i);
+            let found: Ty = self.expr(arg, param);
+
+            if let Some(param) = param {
+                let expected = Expected {
+                    ty: param,
+                    because: None,
+                };
+                self.expect(&found, expected, arg.span);
+            }
+        }
+
+        sig.ret.clone()
+    }
+
+    fn new_expr(&mut self, class: &[Iden], args: &[Expr], span: Span, expected: Option<&Ty>) -> Ty {
+        let symbol: Option<SymbolId> = self.resolution.uses.get(&class[0].span).copied();
+
+        let symbol: SymbolId = match symbol {
+            Some(symbol) if class.len() == 1 => symbol,
+
+            _ => {
+                self.apply(None, args, span, None);
+                return Ty::ERROR;
+            }
+        };
+
+        if self.resolution.symbol(symbol).kind != SymbolKind::CLASS {
+            if self.resolution.symbol(symbol).kind != SymbolKind::IMPORT {
+                let message = format!("Expected a class, found `{}`", class[0].name);
+                self.error(ErrorId::ERR_INVALID_TYPE, class[0].span, message);
+            }
+
+            self.apply(None, args, span, None);
+            return Ty::ERROR;
+        }
+
+        if let Some(decl) = self.classes.get(&symbol).filter(|d| d.is_abstract) {
+            let message = format!(
+                "Cannot create an instance of `{}`, which is `abstract`",
+                class[0].name
+            );
+            let err = FeoError::at(
+                ErrorId::ERR_INVALID_ABSTRACT,
+                self.file,
+                class[0].span,
+                message,
+            )
+            .with_note(
+                decl.name.span,
+                format!("`{}` is declared here", decl.name.name),
+            );
+
+            self.errors.push(err);
+        }
+
+        let ty: Ty = self.instance(symbol, expected, class[0].span);
+        let Ty::NAMED { args: ty_args, .. } = &ty else {
+            unreachable!()
+        };
+
+        let def = &self.types.defs[&symbol];
+        let sig: FnSig = def
+            .constructor
+            .clone()
+            .unwrap_or(FnSig {
+                generics: Vec::new(),
+                params: Vec::new(),
+                required: 0,
+                ret: Ty::unit(),
+                is_static: true,
+            })
+            .subst(&def.subst(ty_args));
+
+        self.apply(Some(&sig), args, span, None);
+
+        ty
+    }
+
+    /// Returns the type of a struct literal, or of an enum variant with fields
+    ///
+    fn struct_expr(
+        &mut self,
+        path: &[Iden],
+        inits: &[FieldInit],
+        span: Span,
+        expected: Option<&Ty>,
+    ) -> Ty {
+        let Some((ty, fields)) = self.struct_fields(path, expected) else {
+            for init in inits {
+                self.expr(&init.value, None);
+            }
+
+            return Ty::ERROR;
+        };
+
+        for init in inits {
+            let Some((_, field_ty)) = fields.iter().find(|(n, _)| *n == init.name.name) else {
+                self.expr(&init.value, None);
+
+                let message = format!("No field `{}` on `{}`", init.name.name, path_str(path));
+                self.error(ErrorId::ERR_UNDEFINED_MEMBER, init.name.span, message);
+
+                continue;
+            };
+
+            let found: Ty = self.expr(&init.value, Some(field_ty));
+            let expected = Expected {
+                ty: field_ty,
+                because: None,
+            };
+            self.expect(&found, expected, init.value.span);
+        }
+
+        for (name, _) in &fields {
+            if !inits.iter().any(|i| i.name.name == *name) {
+                let message = format!("Missing field `{}` in `{}`", name, path_str(path));
+                self.error(ErrorId::ERR_MISSING_FIELD, span, message);
+            }
+        }
+
+        ty
+    }
+
+    /// Returns the type a struct literal or pattern builds, and the fields it has, for a path
+    /// to a struct or to an enum variant with fields. Anything else is reported
+    ///
+    pub(super) fn struct_fields(
+        &mut self,
+        path: &[Iden],
+        expected: Option<&Ty>,
+    ) -> Option<(Ty, Vec<(String, Ty)>)> {
+        let symbol: SymbolId = self.resolution.uses.get(&path[0].span).copied()?;
+        let kind: SymbolKind = self.resolution.symbol(symbol).kind;
+
+        match (kind, path.len()) {
+            (SymbolKind::STRUCT, 1) => {
+                let ty: Ty = self.instance(symbol, expected, path[0].span);
+                let Ty::NAMED { args, .. } = &ty else {
+                    unreachable!()
+                };
+
+                let def = &self.types.defs[&symbol];
+                let subst: HashMap<SymbolId, Ty> = def.subst(args);
+                let fields: Vec<(String, Ty)> = def
+                    .fields
+                    .iter()
+                    .filter(|f| !f.is_static)
+                    .map(|f| (f.name.clone(), f.ty.subst(&subst)))
+                    .collect();
+
+                Some((ty, fields))
+            }
+
+            (SymbolKind::ENUM, 2) => {
+                let ty: Ty = self.instance(symbol, expected, path[0].span);
+                let Ty::NAMED { args, .. } = &ty else {
+                    unreachable!()
+                };
+
+                let def = &self.types.defs[&symbol];
+                let subst: HashMap<SymbolId, Ty> = def.subst(args);
+
+                match def.variant(&path[1].name) {
+                    Some(VariantDef::STRUCT(fields)) => {
+                        let fields: Vec<(String, Ty)> = fields
+                            .iter()
+                            .map(|(n, t)| (n.clone(), t.subst(&subst)))
+                            .collect();
+
+                        Some((ty, fields))
+                    }
+
+                    Some(_) => {
+                        let message = format!("The variant `{}` has no fields", path_str(path));
+                        self.error(ErrorId::ERR_INVALID_TYPE, path[1].span, message);
+
+                        None
+                    }
+
+                    None => {
+                        let message =
+                            format!("No variant `{}` on type `{}`", path[1].name, path[0].name);
+                        self.error(ErrorId::ERR_UNDEFINED_MEMBER, path[1].span, message);
+
+                        None
+                    }
+                }
+            }
+
+            (SymbolKind::IMPORT, _) => None,
+
+            _ => {
+                let message = format!(
+                    "Expected a struct or enum variant, found `{}`",
+                    path_str(path)
+                );
+                let span: Span = path[0].span.to(path[path.len() - 1].span);
+                self.errors.push(FeoError::at(
+                    ErrorId::ERR_INVALID_TYPE,
+                    self.file,
+                    span,
+                    message,
+                ));
+
+                None
+            }
+        }
+    }
+}
+
+fn path_str(path: &[Iden]) -> String {
+    let names: Vec<&str> = path.iter().map(|i| i.name.as_str()).collect();
+    names.join(".")
+}
+
+fn def_name(ty: &Ty) -> String {
+    match ty {
+        Ty::NAMED { name, .. } => name.clone(),
+        _ => ty.to_string(),
+    }
+}
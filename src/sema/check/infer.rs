@@ -0,0 +1,288 @@
+use super::defs::FnSig;
+use super::Checker;
+use crate::error::ErrorId;
+use crate::sema::types::Ty;
+use crate::sema::SymbolId;
+use crate::span::Span;
+use std::collections::HashMap;
+
+/// An inference variable: a type that is worked out from how a value is used
+///
+#[derive(Debug, Clone)]
+pub(super) struct Var {
+    /// the type the variable stands for, once it is known
+    ty: Option<Ty>,
+
+    /// whether the variable is the type of an integer literal, which can only be an integer
+    /// type, and is an `int` if nothing else is called for
+    is_int: bool,
+
+    /// whether the variable is the type of a negative integer literal, which cannot be a
+    /// `uint`. A variable worked out to be another keeps this on whichever is left unbound
+    is_negative: bool,
+}
+
+impl<'a> Checker<'a> {
+    /// Returns a new inference variable
+    ///
+    pub(super) fn fresh(&mut self) -> Ty {
+        self.vars.push(Var {
+            ty: None,
+            is_int: false,
+            is_negative: false,
+        });
+
+        Ty::INFER(self.vars.len() - 1)
+    }
+
+    /// Returns a new inference variable for the type of an integer literal
+    ///
+    /// # Arguments
+    ///
+    /// * `is_negative` - whether the literal is negative, so that it cannot be a `uint`
+    ///
+    pub(super) fn fresh_int(&mut self, is_negative: bool) -> Ty {
+        self.vars.push(Var {
+            ty: None,
+            is_int: true,
+            is_negative,
+        });
+
+        Ty::INFER(self.vars.len() - 1)
+    }
+
+    /// Returns a signature with new inference variables for its generic parameters, whose
+    /// arguments are then worked out from a call's arguments and expected type
+    ///
+    /// # Arguments
+    ///
+    /// * `sig` - the signature
+    /// * `span` - where the function is named, for arguments that do not meet their bounds
+    ///
+    pub(super) fn instantiate(&mut self, sig: &FnSig, span: Span) -> FnSig {
+        let args: Vec<Ty> = sig.generics.iter().map(|_| self.fresh()).collect();
+        self.require_bounds(&sig.generics, &args, span);
+
+        let subst: HashMap<SymbolId, Ty> = sig.generics.iter().copied().zip(args).collect();
+        sig.subst(&subst)
+    }
+
+    /// Returns a type with each inference variable that has been worked out replaced by the
+    /// type it stands for
+    ///
+    pub(super) fn resolved(&self, ty: &Ty) -> Ty {
+        match ty {
+            Ty::INFER(id) => match &self.vars[*id].ty {
+                Some(ty) => self.resolved(ty),
+                None => ty.clone(),
+            },
+
+            Ty::ARRAY(elem) => Ty::ARRAY(Box::new(self.resolved(elem))),
+            Ty::FIXED_ARRAY(elem, len) => Ty::FIXED_ARRAY(Box::new(self.resolved(elem)), *len),
+            Ty::RANGE(elem) => Ty::RANGE(Box::new(self.resolved(elem))),
+            Ty::OPTIONAL(elem) => Ty::OPTIONAL(Box::new(self.resolved(elem))),
+            Ty::TUPLE(elems) => Ty::TUPLE(elems.iter().map(|t| self.resolved(t)).collect()),
+
+            Ty::FUNC { params, ret } => Ty::FUNC {
+                params: params.iter().map(|t| self.resolved(t)).collect(),
+                ret: Box::new(self.resolved(ret)),
+            },
+
+            Ty::NAMED { name, symbol, args } => Ty::NAMED {
+                name: name.clone(),
+                symbol: *symbol,
+                args: args.iter().map(|t| self.resolved(t)).collect(),
+            },
+
+            _ => ty.clone(),
+        }
+    }
+
+    /// Returns a type as it is shown in errors, with integer literals not yet worked out
+    /// taken to be an `int`
+    ///
+    pub(super) fn shown(&self, ty: &Ty) -> Ty {
+        let defaults: HashMap<usize, Ty> = self
+            .resolved(ty)
+            .vars()
+            .into_iter()
+            .filter(|v| self.vars[*v].is_int)
+            .map(|v| (v, Ty::INT))
+            .collect();
+
+        self.fill(&self.resolved(ty), &defaults)
+    }
+
+    /// Returns a type with the given inference variables replaced
+    ///
+    fn fill(&self, ty: &Ty, types: &HashMap<usize, Ty>) -> Ty {
+        match ty {
+            Ty::INFER(id) => types.get(id).cloned().unwrap_or(Ty::INFER(*id)),
+            Ty::ARRAY(elem) => Ty::ARRAY(Box::new(self.fill(elem, types))),
+            Ty::FIXED_ARRAY(elem, len) => Ty::FIXED_ARRAY(Box::new(self.fill(elem, types)), *len),
+            Ty::RANGE(elem) => Ty::RANGE(Box::new(self.fill(elem, types))),
+            Ty::OPTIONAL(elem) => Ty::OPTIONAL(Box::new(self.fill(elem, types))),
+            Ty::TUPLE(elems) => Ty::TUPLE(elems.iter().map(|t| self.fill(t, types)).collect()),
+
+            Ty::FUNC { params, ret } => Ty::FUNC {
+                params: params.iter().map(|t| self.fill(t, types)).collect(),
+                ret: Box::new(self.fill(ret, types)),
+            },
+
+            Ty::NAMED { name, symbol, args } => Ty::NAMED {
+                name: name.clone(),
+                symbol: *symbol,
+                args: args.iter().map(|t| self.fill(t, types)).collect(),
+            },
+
+            _ => ty.clone(),
+        }
+    }
+
+    /// Returns `true` if a (resolved) type is numeric, or is an integer literal's type
+    ///
+    pub(super) fn is_numeric(&self, ty: &Ty) -> bool {
+        ty.is_numeric() || self.is_int_var(ty)
+    }
+
+    /// Returns `true` if a (resolved) type is an integer type, or is an integer literal's type
+    ///
+    pub(super) fn is_integer(&self, ty: &Ty) -> bool {
+        ty.is_integer() || self.is_int_var(ty)
+    }
+
+    /// Returns `true` if a (resolved) type is an integer literal's type not yet worked out
+    ///
+    pub(super) fn is_int_var(&self, ty: &Ty) -> bool {
+        matches!(ty, Ty::INFER(id) if self.vars[*id].is_int)
+    }
+
+    /// Returns a (resolved) type that must be known where it is used, such as the type of a
+    /// value whose field is accessed. If it is not yet known, it is reported
+    ///
+    pub(super) fn known(&mut self, ty: Ty, span: Span) -> Ty {
+        match ty {
+            Ty::INFER(_) if !self.is_int_var(&ty) => {
+                let message = "The type of this value must be known here; add a type annotation";
+                self.error(ErrorId::ERR_INVALID_TYPE, span, message);
+
+                Ty::ERROR
+            }
+
+            _ => ty,
+        }
+    }
+
+    /// Makes an inference variable stand for a type, returning `false` if it cannot
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the variable, which must not yet be worked out
+    /// * `ty` - the (resolved) type
+    ///
+    pub(super) fn bind(&mut self, id: usize, ty: &Ty) -> bool {
+        if *ty == Ty::INFER(id) {
+            return true;
+        }
+
+        // an integer literal's type can only be an integer type, and a negative one's cannot
+        // be a `uint`, so a variable is bound to another that is at least as constrained
+        let Var {
+            is_int,
+            is_negative,
+            ..
+        } = self.vars[id];
+
+        match ty {
+            Ty::INFER(other) if is_int && !self.vars[*other].is_int => {
+                return self.bind(*other, &Ty::INFER(id));
+            }
+
+            Ty::INFER(other) if is_negative && !self.vars[*other].is_negative => {
+                return self.bind(*other, &Ty::INFER(id));
+            }
+
+            Ty::INFER(_) | Ty::ERROR => (),
+            _ if is_int && !ty.is_integer() => return false,
+            Ty::UINT if is_negative => return false,
+
+            // a variable cannot stand for a type that contains it
+            _ if ty.vars().contains(&id) => return false,
+            _ => (),
+        }
+
+        self.vars[id].ty = Some(ty.clone());
+        self.trail.push(id);
+
+        true
+    }
+
+    /// Undoes the inference variables worked out since the trail had a given length, after
+    /// a value is found not to fit a type
+    ///
+    pub(super) fn undo(&mut self, mark: usize) {
+        while self.trail.len() > mark {
+            let id: usize = self.trail.pop().unwrap();
+            self.vars[id].ty = None;
+        }
+    }
+
+    /// Completes the inference of a function or variable: integer literals of no other type
+    /// are made `int`s, generic arguments that do not meet their bounds are reported, and
+    /// each binding without an annotation whose type is still not known is reported
+    ///
+    pub(super) fn finish_inference(&mut self) {
+        for var in &mut self.vars[self.finished..] {
+            if var.is_int && var.ty.is_none() {
+                var.ty = Some(Ty::INT);
+            }
+        }
+
+        for (span, generic, arg) in std::mem::take(&mut self.bound_checks) {
+            let arg: Ty = self.resolved(&arg);
+
+            // an argument that was not worked out is reported as such
+            if arg.vars().is_empty() {
+                self.check_bounds(span, generic, &arg);
+            }
+        }
+
+        for (span, ty) in std::mem::take(&mut self.inferred) {
+            let ty: Ty = self.resolved(&ty);
+            let vars: Vec<usize> = ty.vars();
+
+            if vars.is_empty() {
+                continue;
+            }
+
+            let message = format!("Cannot infer the type `{}`; add a type annotation", ty);
+            self.error(ErrorId::ERR_INVALID_TYPE, span, message);
+
+            for id in vars {
+                self.vars[id].ty = Some(Ty::ERROR);
+            }
+        }
+
+        self.trail.clear();
+        self.finished = self.vars.len();
+    }
+
+    /// Replaces the inference variables in every type the checker found by the types they
+    /// stand for, or by `{unknown}` if they were never worked out
+    ///
+    pub(super) fn finish_types(&mut self) {
+        let unknown: HashMap<usize, Ty> = (0..self.vars.len()).map(|id| (id, Ty::ERROR)).collect();
+
+        let mut exprs = std::mem::take(&mut self.types.exprs);
+        for ty in exprs.values_mut() {
+            *ty = self.fill(&self.resolved(ty), &unknown);
+        }
+        self.types.exprs = exprs;
+
+        let mut symbols = std::mem::take(&mut self.types.symbols);
+        for ty in symbols.values_mut() {
+            *ty = self.fill(&self.resolved(ty), &unknown);
+        }
+        self.types.symbols = symbols;
+    }
+}
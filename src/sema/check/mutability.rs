@@ -0,0 +1,148 @@
+use super::Checker;
+use crate::ast::{
+    visit, AssignOp, BindingKind, Expr, ExprKind, Iden, Pattern, PatternKind, VarDecl, Visitor,
+};
+use crate::error::{ErrorId, FeoError};
+use crate::sema::types::Ty;
+use crate::sema::SymbolKind;
+use crate::span::Span;
+
+/// Collects the names a pattern binds
+///
+pub(super) struct Bindings(pub(super) Vec<Iden>);
+
+impl Visitor for Bindings {
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        if let PatternKind::BINDING(name) = &pattern.kind {
+            self.0.push(name.clone());
+        }
+
+        visit::walk_pattern(self, pattern);
+    }
+}
+
+impl<'a> Checker<'a> {
+    /// Checks the rules a declaration's keyword sets: a `const` must have a value, which is
+    /// evaluated at compile time, and the keyword of a `let` is remembered, so that an
+    /// assignment to one of its bindings can suggest changing it to `var`
+    ///
+    pub(super) fn binding_rules(&mut self, decl: &VarDecl) {
+        match decl.binding {
+            BindingKind::LET => {
+                let mut bindings = Bindings(Vec::new());
+                bindings.visit_pattern(&decl.pattern);
+
+                for name in bindings.0 {
+                    if let Some(symbol) = self.symbol_of(&name) {
+                        self.keywords.insert(symbol, decl.keyword);
+                    }
+                }
+            }
+
+            BindingKind::CONST => match &decl.value {
+                Some(_) => self.const_decl(decl),
+
+                None => {
+                    let message = "A constant needs a value";
+                    self.error(ErrorId::ERR_NOT_CONST, decl.pattern.span, message);
+                }
+            },
+
+            BindingKind::VAR => (),
+        }
+    }
+
+    /// Checks that the target of an assignment can be assigned to: it must be a `var`, or a
+    /// field or element of one. The fields of a class instance can be assigned through any
+    /// binding, as the binding only refers to the instance
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - the place assigned to
+    /// * `op` - the assignment operator, e.g. `+=`
+    ///
+    pub(super) fn assign_target(&mut self, target: &Expr, op: AssignOp) {
+        let mut root: &Expr = target;
+        let mut is_part: bool = false;
+
+        loop {
+            match &root.kind {
+                ExprKind::FIELD { expr: base, .. } | ExprKind::INDEX { expr: base, .. } => {
+                    if self.is_reference(base) {
+                        return;
+                    }
+
+                    root = base;
+                    is_part = true;
+                }
+
+                ExprKind::PAREN(inner) => root = inner,
+                _ => break,
+            }
+        }
+
+        let ExprKind::IDEN(name) = &root.kind else {
+            return;
+        };
+
+        let Some(symbol) = self.resolution.uses.get(&root.span).copied() else {
+            return;
+        };
+
+        let what: &str = match self.resolution.symbol(symbol).kind {
+            SymbolKind::VAR(BindingKind::VAR) | SymbolKind::IMPORT => return,
+            SymbolKind::VAR(BindingKind::LET) => "immutable",
+            SymbolKind::VAR(BindingKind::CONST) => "a constant",
+            SymbolKind::PARAM => "a parameter",
+            SymbolKind::FUNC => "a function",
+            _ => "a type",
+        };
+
+        let action: String = match op {
+            AssignOp::ASSIGN => "assign to".to_string(),
+            _ => format!("apply `{}` to", op),
+        };
+
+        let message: String = match is_part {
+            false => format!("Cannot {} `{}`, which is {}", action, name, what),
+            true => format!("Cannot {} a part of `{}`, which is {}", action, name, what),
+        };
+
+        let decl: Span = self.resolution.symbol(symbol).span;
+        let mut err = FeoError::at(
+            ErrorId::ERR_ASSIGN_IMMUTABLE,
+            self.file,
+            target.span,
+            message,
+        )
+        .with_note(decl, format!("`{}` is declared here", name));
+
+        if let Some(keyword) = self.keywords.get(&symbol) {
+            let fix = format!("declare `{}` with `var` to make it mutable", name);
+            err = err.with_fix(*keyword, "var", fix);
+        }
+
+        self.errors.push(err);
+    }
+
+    /// Returns `true` if the fields of a value can be assigned whatever it is bound by: it is
+    /// `self`, an instance of a class or the name of a type with static fields
+    ///
+    fn is_reference(&self, expr: &Expr) -> bool {
+        if matches!(expr.kind, ExprKind::SELF) || self.type_name(expr).is_some() {
+            return true;
+        }
+
+        let ty: Option<Ty> = self.types.exprs.get(&expr.span).map(|t| self.resolved(t));
+
+        match ty {
+            Some(Ty::NAMED { symbol, .. }) => self
+                .types
+                .defs
+                .get(&symbol)
+                .is_some_and(|d| d.kind == SymbolKind::CLASS),
+
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use super::defs::VariantDef;
+use super::{Checker, Expected};
+use crate::ast::{Iden, Pattern, PatternKind};
+use crate::error::ErrorId;
+use crate::sema::types::Ty;
+use crate::sema::{SymbolId, SymbolKind};
+use std::collections::HashMap;
+
+impl<'a> Checker<'a> {
+    /// Checks that a pattern can match a value of a type, and records the type of each
+    /// name it binds
+    ///
+    pub(super) fn pattern(&mut self, pattern: &Pattern, ty: &Ty) {
+        let ty: &Ty = &self.resolved(ty);
+
+        match &pattern.kind {
+            PatternKind::WILDCARD => (),
+
+            PatternKind::BINDING(name) => {
+                if let Some(symbol) = self.symbol_of(name) {
+                    self.types.symbols.insert(symbol, ty.clone());
+                }
+            }
+
+            PatternKind::LITERAL(lit) => {
+                let found: Ty = self.literal(lit, Some(ty));
+                let expected = Expected { ty, because: None };
+                self.expect(&found, expected, pattern.span);
+            }
+
+            PatternKind::RANGE { start, end, .. } => {
+                let start: Ty = self.literal(start, Some(ty));
+                let end: Ty = self.literal(end, Some(ty));
+                let start: Ty = self.resolved(&start);
+
+                if !self.fits(&end, &start) || !(self.is_numeric(&start) || start == Ty::CHAR) {
+                    let message = format!(
+                        "Cannot match a range of `{}` to `{}`",
+                        self.shown(&start),
+                        self.shown(&end)
+                    );
+                    self.error(ErrorId::ERR_INVALID_OPERANDS, pattern.span, message);
+
+                    return;
+                }
+
+                let expected = Expected { ty, because: None };
+                self.expect(&start, expected, pattern.span);
+            }
+
+            PatternKind::TUPLE(elems) => {
+                let types: Vec<Ty> = match ty {
+                    Ty::TUPLE(types) if types.len() == elems.len() => types.clone(),
+                    Ty::ERROR | Ty::NEVER => vec![Ty::ERROR; elems.len()],
+
+                    // a value of a type not yet known is a tuple of types yet to be inferred
+                    Ty::INFER(_) if !self.is_integer(ty) => {
+                        let types: Vec<Ty> = elems.iter().map(|_| self.fresh()).collect();
+                        self.fits(&Ty::TUPLE(types.clone()), ty);
+
+                        types
+                    }
+
+                    _ => {
+                        let message = format!(
+                            "Expected `{}`, found a tuple of {} element{}",
+                            self.shown(ty),
+                            elems.len(),
+                            if elems.len() == 1 { "" } else { "s" }
+                        );
+                        self.error(ErrorId::ERR_TYPE_MISMATCH, pattern.span, message);
+
+                        vec![Ty::ERROR; elems.len()]
+                    }
+                };
+
+                for (elem, ty) in elems.iter().zip(&types) {
+                    self.pattern(elem, ty);
+                }
+            }
+
+            PatternKind::VARIANT { path, elems } => {
+                let types: Option<Vec<Ty>> = self.variant_pattern(path, elems.as_ref(), ty);
+                let elems: &[Pattern] = elems.as_deref().unwrap_or(&[]);
+                let types: Vec<Ty> = types.unwrap_or(vec![Ty::ERROR; elems.len()]);
+
+                for (elem, ty) in elems.iter().zip(&types) {
+                    self.pattern(elem, ty);
+                }
+            }
+
+            PatternKind::STRUCT {
+                path,
+                fields,
+                has_rest,
+            } => {
+                let found: Option<(Ty, Vec<(String, Ty)>)> = self.struct_fields(path, Some(ty));
+
+                let Some((found, types)) = found else {
+                    for field in fields {
+                        self.pattern(&field.pattern, &Ty::ERROR);
+                    }
+
+                    return;
+                };
+
+                let expected = Expected { ty, because: None };
+                self.expect(&found, expected, pattern.span);
+
+                for field in fields {
+                    match types.iter().find(|(n, _)| *n == field.name.name) {
+                        Some((_, field_ty)) => self.pattern(&field.pattern, field_ty),
+
+                        None => {
+                            let message = format!("No field `{}` on `{}`", field.name.name, found);
+                            self.error(ErrorId::ERR_UNDEFINED_MEMBER, field.name.span, message);
+
+                            self.pattern(&field.pattern, &Ty::ERROR);
+                        }
+                    }
+                }
+
+                if *has_rest {
+                    return;
+                }
+
+                for (name, _) in &types {
+                    if !fields.iter().any(|f| f.name.name == *name) {
+                        let message = format!("Missing field `{}` in `{}`", name, found);
+                        self.error(ErrorId::ERR_MISSING_FIELD, pattern.span, message);
+                    }
+                }
+            }
+
+            PatternKind::OR(alts) => {
+                for alt in alts {
+                    self.pattern(alt, ty);
+                }
+            }
+        }
+    }
+
+    /// Checks a variant pattern (e.g. `Shape.Circle(center, r)`), returning the types of the
+    /// variant's elements if it is found
+    ///
+    fn variant_pattern(
+        &mut self,
+        path: &[Iden],
+        elems: Option<&Vec<Pattern>>,
+        ty: &Ty,
+    ) -> Option<Vec<Ty>> {
+        let symbol: SymbolId = self.resolution.uses.get(&path[0].span).copied()?;
+        let kind: SymbolKind = self.resolution.symbol(symbol).kind;
+        let span = path[0].span.to(path[path.len() - 1].span);
+
+        if kind == SymbolKind::IMPORT {
+            return None;
+        }
+
+        if kind != SymbolKind::ENUM || path.len() != 2 {
+            let names: Vec<&str> = path.iter().map(|i| i.name.as_str()).collect();
+            let message = format!("Expected an enum variant, found `{}`", names.join("."));
+            self.error(ErrorId::ERR_INVALID_TYPE, span, message);
+
+            return None;
+        }
+
+        let name: &Iden = &path[1];
+        let found: Ty = self.instance(symbol, Some(ty), span);
+
+        let expected = Expected { ty, because: None };
+        self.expect(&found, expected, span);
+
+        let Ty::NAMED { args, .. } = self.resolved(&found) else {
+            unreachable!()
+        };
+        let subst: HashMap<SymbolId, Ty> = self.types.defs[&symbol].subst(&args);
+
+        let Some(variant) = self.types.defs[&symbol].variant(&name.name).cloned() else {
+            let message = format!("No variant `{}` on type `{}`", name.name, path[0].name);
+            self.error(ErrorId::ERR_UNDEFINED_MEMBER, name.span, message);
+
+            return None;
+        };
+
+        match (variant, elems) {
+            (VariantDef::UNIT, None) => Some(Vec::new()),
+
+            (VariantDef::TUPLE(types), Some(elems)) if types.len() == elems.len() => {
+                Some(types.iter().map(|t| t.subst(&subst)).collect())
+            }
+
+            (VariantDef::TUPLE(types), Some(elems)) => {
+                let message = format!(
+                    "The variant `{}` has {} element{}, found {}",
+                    name.name,
+                    types.len(),
+                    if types.len() == 1 { "" } else { "s" },
+                    elems.len()
+                );
+                self.error(ErrorId::ERR_ARG_COUNT, span, message);
+
+                None
+            }
+
+            (VariantDef::STRUCT(_), _) => {
+                let message = format!(
+                    "The variant `{}` has fields, and must be matched with `{}.{} {{ .. }}`",
+                    name.name, path[0].name, name.name
+                );
+                self.error(ErrorId::ERR_INVALID_TYPE, span, message);
+
+                None
+            }
+
+            (VariantDef::UNIT, Some(_)) | (VariantDef::TUPLE(_), None) => {
+                let message = match elems {
+                    Some(_) => format!("The variant `{}` has no elements", name.name),
+                    None => format!(
+                        "The variant `{}` has elements, which must be matched",
+                        name.name
+                    ),
+                };
+                self.error(ErrorId::ERR_INVALID_TYPE, span, message);
+
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,509 @@
+use super::defs::FnSig;
+use super::Checker;
+use crate::ast::{FnDecl, Iden, ItemKind, Member, MemberKind, Module, ProtocolMemberKind};
+use crate::error::{ErrorId, FeoError};
+use crate::sema::types::Ty;
+use crate::sema::{SymbolId, SymbolKind};
+use crate::span::Span;
+use std::collections::HashMap;
+
+/// A method a protocol calls for, in a type that declares it conforms to the protocol
+///
+struct Requirement<'d> {
+    protocol: SymbolId,
+    func: &'d FnDecl,
+
+    /// the method's signature, with the protocol's associated types replaced by the type's
+    /// bindings of them
+    sig: FnSig,
+}
+
+impl<'a> Checker<'a> {
+    /// Checks that each struct, enum and class of a module conforms to the protocols it
+    /// declares: that its bindings of their associated types meet their bounds, and that it
+    /// implements each method they call for with a compatible signature. Default methods
+    /// the type does not implement are added to it, unless more than one protocol provides
+    /// one, and how the type implements each protocol is recorded for dynamic dispatch
+    ///
+    pub(super) fn conformances(&mut self, module: &Module) {
+        let mut types: Vec<(SymbolId, &Iden, &[Member])> = Vec::new();
+
+        for item in &module.items {
+            let (name, members): (&Iden, &[Member]) = match &item.kind {
+                ItemKind::STRUCT(decl) => (&decl.name, &decl.members),
+                ItemKind::ENUM(decl) => (&decl.name, &decl.members),
+                ItemKind::CLASS(decl) => (&decl.name, &decl.members),
+                _ => continue,
+            };
+
+            if let Some(symbol) = self.symbol_of(name) {
+                types.push((symbol, name, members));
+            }
+        }
+
+        // a class is checked after its superclasses, so that the default methods they take
+        // from their protocols are there to be inherited
+        types.sort_by_cached_key(|(symbol, _, _)| self.superclasses(*symbol).len());
+
+        for (symbol, name, members) in types {
+            self.conformance(symbol, name, members);
+        }
+    }
+
+    /// Records generic arguments to be checked against the bounds of their parameters once
+    /// inference has worked them out
+    ///
+    /// # Arguments
+    ///
+    /// * `generics` - the generic parameters
+    /// * `args` - the argument for each parameter
+    /// * `span` - where the arguments are given (or inferred), for errors
+    ///
+    pub(super) fn require_bounds(&mut self, generics: &[SymbolId], args: &[Ty], span: Span) {
+        for (generic, arg) in generics.iter().zip(args) {
+            if self.bounds.get(generic).is_some_and(|b| !b.is_empty())
+                && self.checked_bounds.insert((span, *generic))
+            {
+                self.bound_checks.push((span, *generic, arg.clone()));
+            }
+        }
+    }
+
+    /// Reports each protocol a generic parameter is bound by that its (resolved) argument
+    /// does not conform to
+    ///
+    pub(super) fn check_bounds(&mut self, span: Span, generic: SymbolId, arg: &Ty) {
+        let bounds: Vec<Ty> = self.bounds.get(&generic).cloned().unwrap_or_default();
+
+        for bound in bounds {
+            if self.satisfies(arg, &bound) {
+                continue;
+            }
+
+            let param: &str = &self.resolution.symbol(generic).name;
+            let message = format!(
+                "The type `{}` does not conform to `{}`, which `{}` requires",
+                self.shown(arg),
+                bound,
+                param
+            );
+            let err = FeoError::at(ErrorId::ERR_UNSATISFIED_BOUND, self.file, span, message)
+                .with_note(
+                    self.resolution.symbol(generic).span,
+                    format!("`{}` is declared here", param),
+                );
+
+            self.errors.push(err);
+        }
+    }
+
+    /// Returns `true` if a type meets a bound: if it is the protocol, if it (or, for a class,
+    /// a superclass) has a witness table for it, so it both declares and implements it, or,
+    /// for a generic parameter, if it is bound by it
+    ///
+    fn satisfies(&self, ty: &Ty, bound: &Ty) -> bool {
+        let Ty::NAMED {
+            symbol: protocol, ..
+        } = bound
+        else {
+            return true;
+        };
+
+        match ty {
+            Ty::NAMED { symbol, .. } => {
+                symbol == protocol
+                    || [*symbol]
+                        .into_iter()
+                        .chain(self.superclasses(*symbol))
+                        .any(|s| self.types.conformances.contains_key(&(s, *protocol)))
+            }
+
+            Ty::PARAM { symbol, .. } => self.bounds.get(symbol).is_some_and(|bounds| {
+                bounds
+                    .iter()
+                    .any(|b| matches!(b, Ty::NAMED { symbol, .. } if symbol == protocol))
+            }),
+
+            Ty::ERROR | Ty::NEVER => true,
+            _ => false,
+        }
+    }
+
+    /// Records a method called through a value whose type is a protocol, or a generic
+    /// parameter bound by one, which is looked up in the value's witness table when the
+    /// program runs
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - the type of the value
+    /// * `name` - the name of the method
+    ///
+    pub(super) fn dispatch(&mut self, ty: &Ty, name: &Iden) {
+        let protocols: Vec<Ty> = match self.resolved(ty) {
+            ty @ Ty::NAMED { .. } => vec![ty],
+            Ty::PARAM { symbol, .. } => self.bounds.get(&symbol).cloned().unwrap_or_default(),
+            _ => return,
+        };
+
+        for protocol in protocols {
+            let Ty::NAMED { symbol, .. } = protocol else {
+                continue;
+            };
+
+            let Some(def) = self.types.defs.get(&symbol) else {
+                continue;
+            };
+
+            if def.kind != SymbolKind::PROTOCOL {
+                continue;
+            }
+
+            if let Some(index) = def.methods.iter().position(|(n, _)| *n == name.name) {
+                self.types.dispatch.insert(name.span, (symbol, index));
+                return;
+            }
+        }
+    }
+
+    /// Checks a type's conformance to each protocol it declares
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - the type
+    /// * `name` - the type's name in its declaration
+    /// * `members` - the type's members
+    ///
+    fn conformance(&mut self, symbol: SymbolId, name: &Iden, members: &[Member]) {
+        let mut protocols: Vec<SymbolId> = Vec::new();
+
+        for protocol in &self.types.defs[&symbol].protocols {
+            if let Ty::NAMED { symbol, .. } = protocol {
+                if !protocols.contains(symbol) {
+                    protocols.push(*symbol);
+                }
+            }
+        }
+
+        // the methods called for by each name, in the order the protocols declare them
+        let mut required: Vec<(String, Vec<Requirement>)> = Vec::new();
+
+        for protocol in &protocols {
+            let Some(decl) = self.protocols.get(protocol).copied() else {
+                continue;
+            };
+
+            let subst: HashMap<SymbolId, Ty> = self.assoc_types(name, members, *protocol);
+            let sigs: Vec<FnSig> = self.types.defs[protocol]
+                .methods
+                .iter()
+                .map(|(_, sig)| sig.subst(&subst))
+                .collect();
+
+            let funcs = decl.members.iter().filter_map(|m| match &m.kind {
+                ProtocolMemberKind::METHOD(func) => Some(func),
+                ProtocolMemberKind::TYPE(_) => None,
+            });
+
+            for (func, sig) in funcs.zip(sigs) {
+                let requirement = Requirement {
+                    protocol: *protocol,
+                    func,
+                    sig,
+                };
+
+                match required.iter_mut().find(|(n, _)| *n == func.name.name) {
+                    Some((_, requirements)) => requirements.push(requirement),
+                    None => required.push((func.name.name.clone(), vec![requirement])),
+                }
+            }
+        }
+
+        // the type providing each method: the type itself, a superclass, or a protocol
+        let mut witnesses: HashMap<String, SymbolId> = HashMap::new();
+
+        for (method, requirements) in required {
+            match self.implementation(symbol, &method) {
+                Some((owner, found)) => {
+                    let span: Span = members
+                        .iter()
+                        .find_map(|m| match &m.kind {
+                            MemberKind::METHOD(func) if func.name.name == method => {
+                                Some(func.name.span)
+                            }
+                            _ => None,
+                        })
+                        .unwrap_or(name.span);
+
+                    for requirement in &requirements {
+                        self.requirement_mismatch(name, span, requirement, &found);
+                    }
+
+                    witnesses.insert(method, owner);
+                }
+
+                None => {
+                    let mut defaults: Vec<&Requirement> = requirements
+                        .iter()
+                        .filter(|r| r.func.body.is_some())
+                        .collect();
+                    defaults.dedup_by_key(|r| r.protocol);
+
+                    match defaults[..] {
+                        [] => {
+                            if !self.extends_import(symbol) {
+                                for requirement in &requirements {
+                                    self.missing_method(name, requirement);
+                                }
+                            }
+                        }
+
+                        [default] => {
+                            let sig: FnSig = default.sig.clone();
+                            let protocol: SymbolId = default.protocol;
+
+                            if let Some(def) = self.types.defs.get_mut(&symbol) {
+                                def.methods.push((method.clone(), sig));
+                            }
+
+                            witnesses.insert(method, protocol);
+                        }
+
+                        _ => self.ambiguous_default(name, &method, &defaults),
+                    }
+                }
+            }
+        }
+
+        // a witness table is only recorded for a protocol the type fully implements
+        for protocol in protocols {
+            let table: Option<Vec<(String, SymbolId)>> = self.types.defs[&protocol]
+                .methods
+                .iter()
+                .map(|(n, _)| witnesses.get(n).map(|w| (n.clone(), *w)))
+                .collect();
+
+            if let Some(table) = table {
+                self.types.conformances.insert((symbol, protocol), table);
+            }
+        }
+    }
+
+    /// Returns the type's binding of each associated type of a protocol, reporting any that
+    /// does not conform to the protocols its associated type is bound by. An associated type
+    /// the type does not bind is taken to be anything
+    ///
+    fn assoc_types(
+        &mut self,
+        name: &Iden,
+        members: &[Member],
+        protocol: SymbolId,
+    ) -> HashMap<SymbolId, Ty> {
+        let mut subst: HashMap<SymbolId, Ty> = HashMap::new();
+
+        let Some(decl) = self.protocols.get(&protocol).copied() else {
+            return subst;
+        };
+
+        for member in &decl.members {
+            let ProtocolMemberKind::TYPE(assoc) = &member.kind else {
+                continue;
+            };
+
+            let Some(symbol) = self.symbol_of(&assoc.name) else {
+                continue;
+            };
+
+            let binding = members.iter().find_map(|m| match &m.kind {
+                MemberKind::TYPE(alias) if alias.name.name == assoc.name.name => Some(alias),
+                _ => None,
+            });
+
+            let Some(binding) = binding else {
+                subst.insert(symbol, Ty::ERROR);
+                continue;
+            };
+
+            // errors in the binding are reported where it is checked as a member
+            let errors: usize = self.errors.len();
+            let ty: Ty = self.lower_type(&binding.ty);
+            self.errors.truncate(errors);
+
+            for bound in &assoc.bounds {
+                let Some(bound_symbol) = self.resolution.uses.get(&bound.span).copied() else {
+                    continue;
+                };
+
+                let bound_ty = Ty::NAMED {
+                    name: bound.name.clone(),
+                    symbol: bound_symbol,
+                    args: Vec::new(),
+                };
+
+                if self.fits(&ty, &bound_ty) {
+                    continue;
+                }
+
+                let protocol_name: String = self.resolution.symbol(protocol).name.clone();
+                let message = format!(
+                    "The type `{}` of `{}` must conform to `{}`, found `{}`",
+                    assoc.name.name,
+                    name.name,
+                    bound.name,
+                    self.shown(&ty)
+                );
+                let err = FeoError::at(
+                    ErrorId::ERR_TYPE_MISMATCH,
+                    self.file,
+                    binding.ty.span,
+                    message,
+                )
+                .with_note(
+                    assoc.name.span,
+                    format!("`{}.{}` is declared here", protocol_name, assoc.name.name),
+                );
+
+                self.errors.push(err);
+            }
+
+            subst.insert(symbol, ty);
+        }
+
+        subst
+    }
+
+    /// Returns the instance method of a type with a name, along with the type declaring it:
+    /// the type itself or, for a class, its nearest superclass with one
+    ///
+    fn implementation(&self, symbol: SymbolId, name: &str) -> Option<(SymbolId, FnSig)> {
+        [symbol]
+            .into_iter()
+            .chain(self.superclasses(symbol))
+            .find_map(|owner| {
+                let sig: &FnSig = self
+                    .types
+                    .defs
+                    .get(&owner)?
+                    .method(name)
+                    .filter(|m| !m.is_static)?;
+                Some((owner, sig.clone()))
+            })
+    }
+
+    /// Reports a method whose signature is not compatible with that of a protocol method it
+    /// implements: it must take the same number of parameters, accept the protocol method's
+    /// parameter types, and return a type that fits the protocol method's return type
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the conforming type
+    /// * `span` - where to report a mismatch: the name of the method, or of the type if the
+    ///   method is inherited
+    /// * `requirement` - the protocol method
+    /// * `found` - the signature of the method
+    ///
+    fn requirement_mismatch(
+        &mut self,
+        name: &Iden,
+        span: Span,
+        requirement: &Requirement,
+        found: &FnSig,
+    ) {
+        let expected: &FnSig = &requirement.sig;
+
+        let matches: bool = expected.generics.len() == found.generics.len()
+            && expected.params.len() == found.params.len()
+            && {
+                // the generic parameters of the two methods are matched by position
+                let subst: HashMap<SymbolId, Ty> = expected
+                    .generics
+                    .iter()
+                    .zip(&found.generics)
+                    .map(|(e, f)| {
+                        let param = Ty::PARAM {
+                            name: self.resolution.symbol(*f).name.clone(),
+                            symbol: *f,
+                        };
+                        (*e, param)
+                    })
+                    .collect();
+                let expected: FnSig = expected.subst(&subst);
+
+                expected
+                    .params
+                    .iter()
+                    .zip(&found.params)
+                    .all(|(e, f)| self.fits(e, f))
+                    && self.fits(&found.ret, &expected.ret)
+            };
+
+        if matches {
+            return;
+        }
+
+        let protocol_name: String = self.resolution.symbol(requirement.protocol).name.clone();
+        let method: &str = &requirement.func.name.name;
+        let message = format!(
+            "The method `{}` of `{}` does not match the one `{}` requires: expected `{}`, \
+             found `{}`",
+            method,
+            name.name,
+            protocol_name,
+            expected.ty(),
+            found.ty()
+        );
+        let err = FeoError::at(ErrorId::ERR_TYPE_MISMATCH, self.file, span, message).with_note(
+            requirement.func.name.span,
+            format!("`{}.{}` is declared here", protocol_name, method),
+        );
+
+        self.errors.push(err);
+    }
+
+    fn missing_method(&mut self, name: &Iden, requirement: &Requirement) {
+        let protocol_name: String = self.resolution.symbol(requirement.protocol).name.clone();
+        let method: &str = &requirement.func.name.name;
+        let message = format!(
+            "The type `{}` must implement the method `{}` required by `{}`",
+            name.name, method, protocol_name
+        );
+        let err = FeoError::at(ErrorId::ERR_MISSING_METHOD, self.file, name.span, message)
+            .with_note(
+                requirement.func.name.span,
+                format!("`{}.{}` is declared here", protocol_name, method),
+            );
+
+        self.errors.push(err);
+    }
+
+    /// Reports a method the type does not implement, for which more than one of its
+    /// protocols provides a default
+    ///
+    fn ambiguous_default(&mut self, name: &Iden, method: &str, defaults: &[&Requirement]) {
+        let protocols: Vec<String> = defaults
+            .iter()
+            .map(|r| format!("`{}`", self.resolution.symbol(r.protocol).name))
+            .collect();
+        let (last, rest) = protocols.split_last().unwrap();
+
+        let message = format!(
+            "The method `{}` has a default implementation in {} {} and {}, so `{}` must \
+             implement it",
+            method,
+            if rest.len() == 1 { "both" } else { "each of" },
+            rest.join(", "),
+            last,
+            name.name
+        );
+        let mut err = FeoError::at(ErrorId::ERR_AMBIGUOUS_METHOD, self.file, name.span, message);
+
+        for default in defaults {
+            let protocol_name: &str = &self.resolution.symbol(default.protocol).name;
+            err = err.with_note(
+                default.func.name.span,
+                format!("`{}.{}` is declared here", protocol_name, method),
+            );
+        }
+
+        self.errors.push(err);
+    }
+}
@@ -0,0 +1,820 @@
+//! Name resolution
+//!
+//! Binds each name used in a module to its declaration. Scopes are opened for the module,
+//! each type (its generic parameters and associated types), each function (its generic
+//! parameters and parameters), each block, each `for` loop and each match arm. Items are
+//! visible throughout their module, while local variables are only visible after their
+//! declaration
+//!
+//! Shadowing policy:
+//!
+//! * Items, the generic parameters of a declaration, the parameters of a function, the
+//!   bindings of a single pattern, and the fields, variants and methods of a type must each
+//!   have distinct names
+//! * A local variable (including one bound by a `for` loop or a match arm) may shadow any
+//!   name from an enclosing scope, items and parameters included
+//! * Within a block, `let`, `var` and `const` may redeclare a name, which shadows the earlier
+//!   declaration for the rest of the block
+//!
+//! Members are not in scope by name: fields and methods are reached through `self` (or the
+//! type's name, for static members). Only the first name of a path (e.g. `geometry` in
+//! `geometry.Point`) is resolved, as the rest depends on what it refers to, except that the
+//! second name must be a member of a renamed group of imports (e.g. `geo` in
+//! `import geometry.{Point, Circle} as geo;`) if the first is one
+
+use crate::ast::visit::{self, Visitor};
+use crate::ast::{
+    AssocType, BindingKind, Block, ClassDecl, Constructor, EnumDecl, Expr, ExprKind, FnDecl,
+    GenericParam, Iden, ImportKind, ImportTree, Item, ItemKind, MatchArm, Member, MemberKind,
+    Module, Param, Pattern, PatternKind, ProtocolDecl, ProtocolMemberKind, StructDecl, Type,
+    TypeAlias, TypeKind, VarDecl, VariantKind,
+};
+use crate::error::{ErrorId, FeoError};
+use crate::span::Span;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Identifies a symbol by its position in `Resolution::symbols`
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SymbolId(pub usize);
+
+/// A declared name
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+
+    /// the span of the name in its declaration
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum SymbolKind {
+    /// a variable declared with `let`, `var` or `const`, or bound by a `for` loop or match
+    /// arm (as if by `let`)
+    VAR(BindingKind),
+
+    PARAM,
+    FUNC,
+    STRUCT,
+    ENUM,
+    CLASS,
+    PROTOCOL,
+    ALIAS,
+    TYPE,
+    GENERIC,
+
+    /// an associated type of a protocol, or a type's binding of one
+    ASSOC_TYPE,
+
+    /// a module or item brought in by `import`, or a group of them renamed as a namespace
+    IMPORT,
+}
+
+/// The result of resolving a module
+///
+#[derive(Debug, Default)]
+pub struct Resolution {
+    pub symbols: Vec<Symbol>,
+
+    /// the symbol each name refers to, by the span of the name where it is used
+    pub uses: HashMap<Span, SymbolId>,
+
+    /// the symbol each name declares, by the span of the name in its declaration
+    pub defs: HashMap<Span, SymbolId>,
+}
+
+impl Resolution {
+    pub fn symbol(&self, id: SymbolId) -> &Symbol {
+        &self.symbols[id.0]
+    }
+
+    /// Returns the symbol a name refers to, if it was resolved
+    ///
+    /// # Arguments
+    ///
+    /// * `span` - the span of the name where it is used
+    ///
+    pub fn use_at(&self, span: Span) -> Option<&Symbol> {
+        self.uses.get(&span).map(|id| self.symbol(*id))
+    }
+}
+
+/// Resolves the names of a module, returning what each refers to along with any undefined
+/// or duplicate names
+///
+/// # Arguments
+///
+/// * `module` - the syntax tree of a file
+/// * `file` - the file's path, for errors
+///
+pub fn resolve(module: &Module, file: &'static Path) -> (Resolution, Vec<FeoError>) {
+    let mut resolver = Resolver {
+        file,
+        resolution: Resolution::default(),
+        errors: Vec::new(),
+        scopes: Vec::new(),
+        labels: Vec::new(),
+        namespaces: HashMap::new(),
+        has_self: false,
+    };
+
+    resolver.scopes.push(Scope::default());
+
+    for item in &module.items {
+        resolver.declare_item(item);
+    }
+
+    resolver.visit_module(module);
+
+    (resolver.resolution, resolver.errors)
+}
+
+#[derive(Default)]
+struct Scope {
+    names: HashMap<String, SymbolId>,
+}
+
+struct Resolver {
+    file: &'static Path,
+    resolution: Resolution,
+    errors: Vec<FeoError>,
+
+    /// the scopes enclosing the node being visited, innermost last
+    scopes: Vec<Scope>,
+
+    /// the labels of the loops enclosing the node being visited, within its function
+    labels: Vec<Option<String>>,
+
+    /// the names exposed by each group of imports renamed as a namespace (e.g. `x` and `y`
+    /// for `z` in `import a.b.{x, y} as z`)
+    namespaces: HashMap<SymbolId, Vec<String>>,
+
+    /// whether `self` can be used (i.e. in a constructor or non-static method)
+    has_self: bool,
+}
+
+impl Resolver {
+    fn in_scope<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.scopes.push(Scope::default());
+        let result: T = f(self);
+        self.scopes.pop();
+
+        result
+    }
+
+    /// Declares a name in the innermost scope
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name, as declared
+    /// * `kind` - what the name is
+    /// * `is_unique` - whether the name must not already be declared in the scope. If it is,
+    ///   the earlier declaration is kept
+    ///
+    fn declare(&mut self, name: &Iden, kind: SymbolKind, is_unique: bool) {
+        let id = SymbolId(self.resolution.symbols.len());
+
+        self.resolution.symbols.push(Symbol {
+            name: name.name.clone(),
+            kind,
+            span: name.span,
+        });
+        self.resolution.defs.insert(name.span, id);
+
+        let scope: &mut Scope = self.scopes.last_mut().unwrap();
+
+        match scope.names.get(&name.name) {
+            Some(prev) if is_unique => {
+                let prev: Span = self.resolution.symbol(*prev).span;
+                self.duplicate(name, prev);
+            }
+
+            _ => {
+                scope.names.insert(name.name.clone(), id);
+            }
+        }
+    }
+
+    fn duplicate(&mut self, name: &Iden, prev: Span) {
+        let message = format!("The name `{}` is defined more than once", name.name);
+        let err = FeoError::at(ErrorId::ERR_DUPLICATE_NAME, self.file, name.span, message)
+            .with_note(prev, format!("`{}` is first defined here", name.name));
+
+        self.errors.push(err);
+    }
+
+    /// Reports any names in a list that are not distinct, e.g. the fields of a struct
+    ///
+    fn check_unique<'a>(&mut self, names: impl IntoIterator<Item = &'a Iden>) {
+        let mut seen: HashMap<&str, Span> = HashMap::new();
+
+        for name in names {
+            match seen.get(name.name.as_str()) {
+                Some(prev) => self.duplicate(name, *prev),
+                None => {
+                    seen.insert(&name.name, name.span);
+                }
+            }
+        }
+    }
+
+    /// Records the symbol a name refers to, reporting it if there is none
+    ///
+    fn resolve_name(&mut self, name: &str, span: Span) {
+        let found: Option<SymbolId> = self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|s| s.names.get(name).copied());
+
+        match found {
+            Some(id) => {
+                self.resolution.uses.insert(span, id);
+            }
+
+            None => {
+                let message = format!("Cannot find `{}` in this scope", name);
+                let err = FeoError::at(ErrorId::ERR_UNDEFINED_NAME, self.file, span, message);
+                self.errors.push(err);
+            }
+        }
+    }
+
+    fn resolve_names(&mut self, names: &[Iden]) {
+        for name in names {
+            self.resolve_name(&name.name, name.span);
+        }
+    }
+
+    /// Resolves the first name of a path, and the second if the first is a namespace
+    ///
+    fn resolve_path(&mut self, path: &[Iden]) {
+        if let Some(first) = path.first() {
+            self.resolve_name(&first.name, first.span);
+        }
+
+        if let [first, member, ..] = path {
+            self.resolve_member(first, member);
+        }
+    }
+
+    /// Reports a member that is not exposed by a namespace (e.g. `w` in `z.w`, for
+    /// `import a.b.{x, y} as z`)
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - the name before the member, which has been resolved
+    /// * `member` - the member's name
+    ///
+    fn resolve_member(&mut self, namespace: &Iden, member: &Iden) {
+        let Some(names) = self
+            .resolution
+            .uses
+            .get(&namespace.span)
+            .and_then(|id| self.namespaces.get(id))
+        else {
+            return;
+        };
+
+        if !names.contains(&member.name) {
+            let message = format!("Cannot find `{}` in `{}`", member.name, namespace.name);
+            let err = FeoError::at(ErrorId::ERR_UNDEFINED_NAME, self.file, member.span, message);
+            self.errors.push(err);
+        }
+    }
+
+    /// Declares the names an item adds to its module
+    ///
+    fn declare_item(&mut self, item: &Item) {
+        let (name, kind): (&Iden, SymbolKind) = match &item.kind {
+            ItemKind::VAR(decl) => {
+                for name in self.bindings(&decl.pattern) {
+                    self.declare(&name, SymbolKind::VAR(decl.binding), true);
+                }
+
+                return;
+            }
+
+            ItemKind::IMPORT(tree) => {
+                self.declare_import(tree);
+                return;
+            }
+
+            ItemKind::FUNC(func) => (&func.name, SymbolKind::FUNC),
+            ItemKind::STRUCT(decl) => (&decl.name, SymbolKind::STRUCT),
+            ItemKind::ENUM(decl) => (&decl.name, SymbolKind::ENUM),
+            ItemKind::CLASS(decl) => (&decl.name, SymbolKind::CLASS),
+            ItemKind::PROTOCOL(decl) => (&decl.name, SymbolKind::PROTOCOL),
+            ItemKind::ALIAS(alias) => (&alias.name, SymbolKind::ALIAS),
+            ItemKind::TYPE(alias) => (&alias.name, SymbolKind::TYPE),
+            ItemKind::LIB(_) | ItemKind::ERROR => return,
+        };
+
+        self.declare(name, kind, true);
+    }
+
+    /// Declares the names an import brings in: the last name of each imported path, or its
+    /// alias. A renamed group declares only its alias, as a namespace exposing its members
+    ///
+    fn declare_import(&mut self, tree: &ImportTree) {
+        match &tree.kind {
+            ImportKind::GROUP {
+                trees,
+                alias: Some(alias),
+            } => {
+                let mut names: Vec<&Iden> = Vec::new();
+
+                for tree in trees {
+                    Self::import_names(tree, &mut names);
+                }
+                self.check_unique(names.iter().copied());
+
+                let id = SymbolId(self.resolution.symbols.len());
+                let names: Vec<String> = names.into_iter().map(|n| n.name.clone()).collect();
+
+                self.declare(alias, SymbolKind::IMPORT, true);
+                self.namespaces.insert(id, names);
+            }
+
+            ImportKind::GROUP { trees, alias: None } => {
+                for tree in trees {
+                    self.declare_import(tree);
+                }
+            }
+
+            ImportKind::SINGLE(alias) => {
+                if let Some(name) = alias.as_ref().or(tree.path.last()) {
+                    self.declare(name, SymbolKind::IMPORT, true);
+                }
+            }
+        }
+    }
+
+    /// Collects the names an import would declare if it were not in a renamed group
+    ///
+    fn import_names<'a>(tree: &'a ImportTree, names: &mut Vec<&'a Iden>) {
+        match &tree.kind {
+            ImportKind::GROUP {
+                alias: Some(alias), ..
+            }
+            | ImportKind::SINGLE(Some(alias)) => names.push(alias),
+
+            ImportKind::GROUP { trees, alias: None } => {
+                for tree in trees {
+                    Self::import_names(tree, names);
+                }
+            }
+
+            ImportKind::SINGLE(None) => names.extend(tree.path.last()),
+        }
+    }
+
+    /// Returns the names bound by a pattern, reporting any bound twice and any not bound by
+    /// every alternative of an or-pattern
+    ///
+    fn bindings(&mut self, pattern: &Pattern) -> Vec<Iden> {
+        let mut names: Vec<Iden> = Vec::new();
+        self.collect_bindings(pattern, &mut names);
+        self.check_unique(&names.clone());
+
+        names
+    }
+
+    fn collect_bindings(&mut self, pattern: &Pattern, names: &mut Vec<Iden>) {
+        match &pattern.kind {
+            PatternKind::BINDING(name) => names.push(name.clone()),
+
+            PatternKind::WILDCARD | PatternKind::LITERAL(_) | PatternKind::RANGE { .. } => (),
+
+            PatternKind::TUPLE(elems) => {
+                for elem in elems {
+                    self.collect_bindings(elem, names);
+                }
+            }
+
+            PatternKind::VARIANT { elems, .. } => {
+                for elem in elems.iter().flatten() {
+                    self.collect_bindings(elem, names);
+                }
+            }
+
+            PatternKind::STRUCT { fields, .. } => {
+                for field in fields {
+                    self.collect_bindings(&field.pattern, names);
+                }
+            }
+
+            // every alternative must bind the same names, so those of the first stand in for
+            // the rest
+            PatternKind::OR(alts) => {
+                let mut first: Vec<Iden> = Vec::new();
+                self.collect_bindings(&alts[0], &mut first);
+
+                for alt in &alts[1..] {
+                    let mut other: Vec<Iden> = Vec::new();
+                    self.collect_bindings(alt, &mut other);
+
+                    let missing = first
+                        .iter()
+                        .chain(&other)
+                        .find(|n| !(has(&first, n) && has(&other, n)));
+
+                    if let Some(name) = missing {
+                        let message = format!(
+                            "`{}` is not bound in every alternative of the pattern",
+                            name.name
+                        );
+                        let err =
+                            FeoError::at(ErrorId::ERR_UNDEFINED_NAME, self.file, alt.span, message);
+                        self.errors.push(err);
+                    }
+                }
+
+                names.extend(first);
+            }
+        }
+    }
+
+    /// Resolves the paths in a pattern, then declares the names it binds
+    ///
+    fn declare_pattern(&mut self, pattern: &Pattern, kind: SymbolKind, is_unique: bool) {
+        self.visit_pattern(pattern);
+
+        for name in self.bindings(pattern) {
+            self.declare(&name, kind, is_unique);
+        }
+    }
+
+    fn declare_generics(&mut self, generics: &[GenericParam]) {
+        for param in generics {
+            self.visit_generic_param(param);
+            self.declare(&param.name, SymbolKind::GENERIC, true);
+        }
+    }
+
+    /// Declares the associated type bindings of a type (e.g. `type Output = Point;`)
+    ///
+    fn declare_type_members(&mut self, members: &[Member]) {
+        for member in members {
+            if let MemberKind::TYPE(alias) = &member.kind {
+                self.declare(&alias.name, SymbolKind::ASSOC_TYPE, true);
+            }
+        }
+    }
+
+    /// Returns the names of a type's members, which must be distinct (constructors have no
+    /// name, and are left out)
+    ///
+    fn member_names(members: &[Member]) -> impl Iterator<Item = &Iden> {
+        members.iter().filter_map(|m| match &m.kind {
+            MemberKind::FIELD(field) => Some(&field.name),
+            MemberKind::METHOD(func) => Some(&func.name),
+            MemberKind::TYPE(alias) => Some(&alias.name),
+            MemberKind::CONSTRUCTOR(_) | MemberKind::ERROR => None,
+        })
+    }
+
+    /// Visits the body of a loop, within which `break` and `continue` may use its label
+    ///
+    fn in_loop(&mut self, label: &Option<Iden>, f: impl FnOnce(&mut Self)) {
+        self.labels.push(label.as_ref().map(|l| l.name.clone()));
+        f(self);
+        self.labels.pop();
+    }
+
+    /// Reports a `break` or `continue` outside any loop, or naming a label no enclosing loop
+    /// has
+    ///
+    /// # Arguments
+    ///
+    /// * `keyword` - `break` or `continue`
+    /// * `label` - the label it names, if any
+    /// * `span` - the `break` or `continue` expression
+    ///
+    fn check_label(&mut self, keyword: &str, label: &Option<Iden>, span: Span) {
+        if self.labels.is_empty() {
+            let message = format!("`{}` can only be used inside a loop", keyword);
+            let err = FeoError::at(ErrorId::ERR_OUTSIDE_LOOP, self.file, span, message);
+            self.errors.push(err);
+
+            return;
+        }
+
+        let Some(label) = label else {
+            return;
+        };
+
+        if !self.labels.iter().any(|l| l.as_ref() == Some(&label.name)) {
+            let message = format!("Cannot find a loop labeled `{}`", label.name);
+            let err = FeoError::at(ErrorId::ERR_UNDEFINED_LABEL, self.file, label.span, message);
+            self.errors.push(err);
+        }
+    }
+
+    /// Visits a function with `self` available (or not)
+    ///
+    fn visit_fn_with_self(&mut self, func: &FnDecl, has_self: bool) {
+        let outer: bool = std::mem::replace(&mut self.has_self, has_self);
+        self.visit_fn_decl(func);
+        self.has_self = outer;
+    }
+}
+
+fn has(names: &[Iden], name: &Iden) -> bool {
+    names.iter().any(|n| n.name == name.name)
+}
+
+impl Visitor for Resolver {
+    fn visit_item(&mut self, item: &Item) {
+        match &item.kind {
+            // declared along with the other items
+            ItemKind::VAR(decl) => {
+                if let Some(ty) = &decl.ty {
+                    self.visit_type(ty);
+                }
+
+                if let Some(value) = &decl.value {
+                    self.visit_expr(value);
+                }
+
+                self.visit_pattern(&decl.pattern);
+            }
+
+            _ => visit::walk_item(self, item),
+        }
+    }
+
+    /// A local declaration, whose names are declared after its value is resolved, so that
+    /// e.g. `let x = x + 1;` refers to an earlier `x`
+    ///
+    fn visit_var_decl(&mut self, decl: &VarDecl) {
+        if let Some(ty) = &decl.ty {
+            self.visit_type(ty);
+        }
+
+        if let Some(value) = &decl.value {
+            self.visit_expr(value);
+        }
+
+        self.declare_pattern(&decl.pattern, SymbolKind::VAR(decl.binding), false);
+    }
+
+    fn visit_fn_decl(&mut self, func: &FnDecl) {
+        let labels: Vec<Option<String>> = std::mem::take(&mut self.labels);
+
+        self.in_scope(|r| {
+            r.declare_generics(&func.generics);
+
+            for param in &func.params {
+                r.visit_param(param);
+            }
+
+            if let Some(ret) = &func.ret {
+                r.visit_type(ret);
+            }
+
+            if let Some(body) = &func.body {
+                r.visit_block(body);
+            }
+        });
+
+        self.labels = labels;
+    }
+
+    fn visit_param(&mut self, param: &Param) {
+        self.visit_type(&param.ty);
+
+        if let Some(default) = &param.default {
+            self.visit_expr(default);
+        }
+
+        self.declare_pattern(&param.pattern, SymbolKind::PARAM, true);
+    }
+
+    fn visit_generic_param(&mut self, param: &GenericParam) {
+        for bound in &param.bounds {
+            self.resolve_name(&bound.name, bound.span);
+        }
+    }
+
+    fn visit_struct_decl(&mut self, decl: &StructDecl) {
+        self.resolve_names(&decl.protocols);
+
+        let fields = decl.fields.iter().map(|f| &f.name);
+        self.check_unique(fields.chain(Self::member_names(&decl.members)));
+
+        self.in_scope(|r| {
+            r.declare_generics(&decl.generics);
+            r.declare_type_members(&decl.members);
+
+            for field in &decl.fields {
+                r.visit_field(field);
+            }
+
+            for member in &decl.members {
+                r.visit_member(member);
+            }
+        });
+    }
+
+    fn visit_enum_decl(&mut self, decl: &EnumDecl) {
+        self.resolve_names(&decl.protocols);
+
+        let variants = decl.variants.iter().map(|v| &v.name);
+        self.check_unique(variants.chain(Self::member_names(&decl.members)));
+
+        for variant in &decl.variants {
+            if let VariantKind::STRUCT(fields) = &variant.kind {
+                self.check_unique(fields.iter().map(|f| &f.name));
+            }
+        }
+
+        self.in_scope(|r| {
+            r.declare_generics(&decl.generics);
+            r.declare_type_members(&decl.members);
+
+            for variant in &decl.variants {
+                r.visit_variant(variant);
+            }
+
+            for member in &decl.members {
+                r.visit_member(member);
+            }
+        });
+    }
+
+    fn visit_class_decl(&mut self, decl: &ClassDecl) {
+        self.resolve_names(&decl.supertypes);
+        self.check_unique(Self::member_names(&decl.members));
+
+        self.in_scope(|r| {
+            r.declare_generics(&decl.generics);
+            r.declare_type_members(&decl.members);
+
+            for member in &decl.members {
+                r.visit_member(member);
+            }
+        });
+    }
+
+    fn visit_member(&mut self, member: &Member) {
+        match &member.kind {
+            MemberKind::METHOD(func) => self.visit_fn_with_self(func, !member.modifiers.is_static),
+            _ => visit::walk_member(self, member),
+        }
+    }
+
+    fn visit_constructor(&mut self, constructor: &Constructor) {
+        let outer: bool = std::mem::replace(&mut self.has_self, true);
+        let labels: Vec<Option<String>> = std::mem::take(&mut self.labels);
+
+        self.in_scope(|r| {
+            for param in &constructor.params {
+                r.visit_param(param);
+            }
+
+            r.visit_block(&constructor.body);
+        });
+
+        self.has_self = outer;
+        self.labels = labels;
+    }
+
+    fn visit_protocol_decl(&mut self, decl: &ProtocolDecl) {
+        let names = decl.members.iter().map(|m| match &m.kind {
+            ProtocolMemberKind::TYPE(assoc) => &assoc.name,
+            ProtocolMemberKind::METHOD(func) => &func.name,
+        });
+        self.check_unique(names);
+
+        self.in_scope(|r| {
+            for member in &decl.members {
+                if let ProtocolMemberKind::TYPE(assoc) = &member.kind {
+                    r.declare(&assoc.name, SymbolKind::ASSOC_TYPE, true);
+                }
+            }
+
+            for member in &decl.members {
+                match &member.kind {
+                    ProtocolMemberKind::TYPE(assoc) => r.visit_assoc_type(assoc),
+                    ProtocolMemberKind::METHOD(func) => r.visit_fn_with_self(func, true),
+                }
+            }
+        });
+    }
+
+    fn visit_assoc_type(&mut self, assoc: &AssocType) {
+        self.resolve_names(&assoc.bounds);
+    }
+
+    fn visit_type_alias(&mut self, alias: &TypeAlias) {
+        self.in_scope(|r| {
+            r.declare_generics(&alias.generics);
+            r.visit_type(&alias.ty);
+        });
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.in_scope(|r| visit::walk_block(r, block));
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::IDEN(name) => self.resolve_name(name, expr.span),
+
+            ExprKind::SELF if !self.has_self => {
+                let message = "`self` can only be used in constructors and non-static methods";
+                let err = FeoError::at(ErrorId::ERR_INVALID_SELF, self.file, expr.span, message);
+                self.errors.push(err);
+            }
+
+            ExprKind::FIELD { expr: base, field } => {
+                self.visit_expr(base);
+
+                if let ExprKind::IDEN(name) = &base.kind {
+                    let namespace = Iden {
+                        name: name.clone(),
+                        span: base.span,
+                    };
+                    self.resolve_member(&namespace, field);
+                }
+            }
+
+            ExprKind::NEW { class: path, .. } | ExprKind::STRUCT { path, .. } => {
+                self.resolve_path(path);
+                visit::walk_expr(self, expr);
+            }
+
+            ExprKind::WHILE { label, cond, body } => {
+                self.visit_expr(cond);
+                self.in_loop(label, |r| r.visit_block(body));
+            }
+
+            ExprKind::LOOP { label, body } => self.in_loop(label, |r| r.visit_block(body)),
+
+            ExprKind::FOR {
+                label,
+                pattern,
+                iter,
+                body,
+            } => {
+                self.visit_expr(iter);
+
+                self.in_scope(|r| {
+                    r.declare_pattern(pattern, SymbolKind::VAR(BindingKind::LET), false);
+                    r.in_loop(label, |r| r.visit_block(body));
+                });
+            }
+
+            ExprKind::BREAK { label, .. } => {
+                self.check_label("break", label, expr.span);
+                visit::walk_expr(self, expr);
+            }
+
+            ExprKind::CONTINUE { label } => {
+                self.check_label("continue", label, expr.span);
+                visit::walk_expr(self, expr);
+            }
+
+            _ => visit::walk_expr(self, expr),
+        }
+    }
+
+    fn visit_match_arm(&mut self, arm: &MatchArm) {
+        self.in_scope(|r| {
+            r.declare_pattern(&arm.pattern, SymbolKind::VAR(BindingKind::LET), false);
+
+            if let Some(guard) = &arm.guard {
+                r.visit_expr(guard);
+            }
+
+            r.visit_expr(&arm.body);
+        });
+    }
+
+    /// Resolves the paths in a pattern. The names it binds are declared by `declare_pattern`
+    ///
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        match &pattern.kind {
+            PatternKind::VARIANT { path, .. } | PatternKind::STRUCT { path, .. } => {
+                self.resolve_path(path);
+            }
+
+            _ => (),
+        }
+
+        visit::walk_pattern(self, pattern);
+    }
+
+    fn visit_type(&mut self, ty: &Type) {
+        if let TypeKind::NAMED { path, .. } = &ty.kind {
+            self.resolve_path(path);
+        }
+
+        visit::walk_type(self, ty);
+    }
+}
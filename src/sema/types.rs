@@ -0,0 +1,183 @@
+use super::SymbolId;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A type, as the checker sees it
+///
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum Ty {
+    BOOL,
+    CHAR,
+    FLOAT,
+    INT,
+    STRING,
+    UINT,
+
+    /// `[T]`, an array of any length
+    ARRAY(Box<Ty>),
+
+    /// `[T; N]`, an array of a known length
+    FIXED_ARRAY(Box<Ty>, usize),
+
+    /// the type of a range such as `0..10`, over integers or characters
+    RANGE(Box<Ty>),
+
+    /// `(A, B)`, or `()` for the unit type
+    TUPLE(Vec<Ty>),
+
+    FUNC {
+        params: Vec<Ty>,
+        ret: Box<Ty>,
+    },
+
+    OPTIONAL(Box<Ty>),
+
+    /// a struct, enum, class, protocol or distinct (`type`) type, with any generic arguments
+    NAMED {
+        name: String,
+        symbol: SymbolId,
+        args: Vec<Ty>,
+    },
+
+    /// a generic parameter, within the declaration that has it
+    PARAM {
+        name: String,
+        symbol: SymbolId,
+    },
+
+    /// a type being inferred, numbered by the checker. Once worked out it is replaced by
+    /// the type it stands for
+    INFER(usize),
+
+    /// the type of an expression that never produces a value (e.g. `return`), which fits
+    /// wherever a value is expected
+    NEVER,
+
+    /// a type that could not be worked out, because of an earlier error or a name from
+    /// another module. It fits anywhere, so that one mistake is only reported once
+    ERROR,
+}
+
+impl Ty {
+    pub fn unit() -> Ty {
+        Ty::TUPLE(Vec::new())
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Ty::INT | Ty::UINT | Ty::FLOAT)
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Ty::INT | Ty::UINT)
+    }
+
+    /// Returns `true` if the type is, or contains, a type that could not be worked out
+    ///
+    pub fn has_error(&self) -> bool {
+        match self {
+            Ty::ERROR => true,
+            Ty::ARRAY(elem) | Ty::FIXED_ARRAY(elem, _) | Ty::RANGE(elem) | Ty::OPTIONAL(elem) => {
+                elem.has_error()
+            }
+
+            Ty::TUPLE(elems) => elems.iter().any(Ty::has_error),
+            Ty::FUNC { params, ret } => params.iter().any(Ty::has_error) || ret.has_error(),
+            Ty::NAMED { args, .. } => args.iter().any(Ty::has_error),
+            _ => false,
+        }
+    }
+
+    /// Returns the inference variables in the type
+    ///
+    pub fn vars(&self) -> Vec<usize> {
+        match self {
+            Ty::INFER(id) => vec![*id],
+            Ty::ARRAY(elem) | Ty::FIXED_ARRAY(elem, _) | Ty::RANGE(elem) | Ty::OPTIONAL(elem) => {
+                elem.vars()
+            }
+
+            Ty::TUPLE(elems) | Ty::NAMED { args: elems, .. } => {
+                elems.iter().flat_map(Ty::vars).collect()
+            }
+
+            Ty::FUNC { params, ret } => params.iter().chain([&**ret]).flat_map(Ty::vars).collect(),
+
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the type with generic parameters replaced by their arguments
+    ///
+    /// # Arguments
+    ///
+    /// * `subst` - the argument of each generic parameter. Any other parameters are kept
+    ///
+    #[must_use]
+    pub fn subst(&self, subst: &HashMap<SymbolId, Ty>) -> Ty {
+        if subst.is_empty() {
+            return self.clone();
+        }
+
+        match self {
+            Ty::PARAM { symbol, .. } => subst.get(symbol).cloned().unwrap_or(self.clone()),
+            Ty::ARRAY(elem) => Ty::ARRAY(Box::new(elem.subst(subst))),
+            Ty::FIXED_ARRAY(elem, len) => Ty::FIXED_ARRAY(Box::new(elem.subst(subst)), *len),
+            Ty::RANGE(elem) => Ty::RANGE(Box::new(elem.subst(subst))),
+            Ty::OPTIONAL(elem) => Ty::OPTIONAL(Box::new(elem.subst(subst))),
+            Ty::TUPLE(elems) => Ty::TUPLE(elems.iter().map(|t| t.subst(subst)).collect()),
+
+            Ty::FUNC { params, ret } => Ty::FUNC {
+                params: params.iter().map(|t| t.subst(subst)).collect(),
+                ret: Box::new(ret.subst(subst)),
+            },
+
+            Ty::NAMED { name, symbol, args } => Ty::NAMED {
+                name: name.clone(),
+                symbol: *symbol,
+                args: args.iter().map(|t| t.subst(subst)).collect(),
+            },
+
+            _ => self.clone(),
+        }
+    }
+}
+
+/// Writes a type as it would be written in source (e.g. `[int; 3]` or `Pair<int, String>`)
+///
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |types: &[Ty]| -> String {
+            let types: Vec<String> = types.iter().map(Ty::to_string).collect();
+            types.join(", ")
+        };
+
+        match self {
+            Ty::BOOL => write!(f, "bool"),
+            Ty::CHAR => write!(f, "char"),
+            Ty::FLOAT => write!(f, "float"),
+            Ty::INT => write!(f, "int"),
+            Ty::STRING => write!(f, "String"),
+            Ty::UINT => write!(f, "uint"),
+            Ty::ARRAY(elem) => write!(f, "[{}]", elem),
+            Ty::FIXED_ARRAY(elem, len) => write!(f, "[{}; {}]", elem, len),
+            Ty::RANGE(elem) => write!(f, "Range<{}>", elem),
+            Ty::TUPLE(elems) if elems.len() == 1 => write!(f, "({},)", elems[0]),
+            Ty::TUPLE(elems) => write!(f, "({})", list(elems)),
+
+            Ty::FUNC { params, ret } => match **ret == Ty::unit() {
+                true => write!(f, "func({})", list(params)),
+                false => write!(f, "func({}) -> {}", list(params), ret),
+            },
+
+            Ty::OPTIONAL(elem) if matches!(**elem, Ty::FUNC { .. }) => write!(f, "({})?", elem),
+            Ty::OPTIONAL(elem) => write!(f, "{}?", elem),
+            Ty::NAMED { name, args, .. } if args.is_empty() => write!(f, "{}", name),
+            Ty::NAMED { name, args, .. } => write!(f, "{}<{}>", name, list(args)),
+            Ty::PARAM { name, .. } => write!(f, "{}", name),
+            Ty::INFER(_) => write!(f, "_"),
+            Ty::NEVER => write!(f, "never"),
+            Ty::ERROR => write!(f, "{{unknown}}"),
+        }
+    }
+}
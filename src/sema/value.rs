@@ -0,0 +1,95 @@
+use std::fmt;
+
+/// A value worked out at compile time, such as that of a `const`
+///
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum Value {
+    BOOL(bool),
+    CHAR(char),
+    FLOAT(f64),
+    INT(i32),
+    STRING(String),
+    UINT(u32),
+
+    /// `(a, b)`, or `()` for the unit value
+    TUPLE(Vec<Value>),
+    ARRAY(Vec<Value>),
+
+    /// an instance of a struct, or a variant of an enum with fields, by its (qualified) name
+    STRUCT {
+        name: String,
+        fields: Vec<(String, Value)>,
+    },
+
+    /// a unit or tuple variant of an enum, e.g. `Color.Red` or `Shape.Circle(1.0)`
+    VARIANT {
+        ty: String,
+        name: String,
+        elems: Vec<Value>,
+    },
+}
+
+impl Value {
+    pub fn unit() -> Value {
+        Value::TUPLE(Vec::new())
+    }
+
+    /// Returns how much memory the value takes up, counting one for each value within it
+    /// and each byte of a string
+    ///
+    #[must_use]
+    pub fn size(&self) -> usize {
+        let sum = |values: &[Value]| -> usize { values.iter().map(Value::size).sum() };
+
+        match self {
+            Value::STRING(s) => 1 + s.len(),
+            Value::TUPLE(elems) | Value::ARRAY(elems) => 1 + sum(elems),
+            Value::STRUCT { fields, .. } => 1 + fields.iter().map(|(_, v)| v.size()).sum::<usize>(),
+            Value::VARIANT { elems, .. } => 1 + sum(elems),
+            _ => 1,
+        }
+    }
+
+    /// Returns the value of a field of a struct, if it has one
+    ///
+    #[must_use]
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::STRUCT { fields, .. } => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |values: &[Value]| -> String {
+            let values: Vec<String> = values.iter().map(Value::to_string).collect();
+            values.join(", ")
+        };
+
+        match self {
+            Value::BOOL(b) => write!(f, "{}", b),
+            Value::CHAR(c) => write!(f, "{:?}", c),
+            Value::FLOAT(x) => write!(f, "{:?}", x),
+            Value::INT(n) => write!(f, "{}", n),
+            Value::STRING(s) => write!(f, "{:?}", s),
+            Value::UINT(n) => write!(f, "{}", n),
+            Value::TUPLE(elems) if elems.len() == 1 => write!(f, "({},)", elems[0]),
+            Value::TUPLE(elems) => write!(f, "({})", list(elems)),
+            Value::ARRAY(elems) => write!(f, "[{}]", list(elems)),
+
+            Value::STRUCT { name, fields } => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(n, v)| format!("{}: {}", n, v))
+                    .collect();
+                write!(f, "{} {{ {} }}", name, fields.join(", "))
+            }
+
+            Value::VARIANT { ty, name, elems } if elems.is_empty() => write!(f, "{}.{}", ty, name),
+            Value::VARIANT { ty, name, elems } => write!(f, "{}.{}({})", ty, name, list(elems)),
+        }
+    }
+}
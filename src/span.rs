@@ -0,0 +1,49 @@
+use std::fmt;
+
+/// A region of a source file, from the start of one token to the end of another
+///
+/// Lines and columns are 1-based, and columns count `char` (as with `Token`). The end
+/// column is exclusive
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub line: usize,
+    pub col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, col: usize, end_line: usize, end_col: usize) -> Self {
+        Self {
+            line,
+            col,
+            end_line,
+            end_col,
+        }
+    }
+
+    /// Returns a span covering both this span and a later one
+    ///
+    #[must_use]
+    pub fn to(&self, end: Span) -> Span {
+        Span::new(self.line, self.col, end.end_line, end.end_col)
+    }
+
+    /// Returns an empty span at the end of this span
+    ///
+    #[must_use]
+    pub fn end(&self) -> Span {
+        Span::new(self.end_line, self.end_col, self.end_line, self.end_col)
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}-{}:{}",
+            self.line, self.col, self.end_line, self.end_col
+        )
+    }
+}
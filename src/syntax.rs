@@ -0,0 +1,100 @@
+//! A lossless concrete syntax tree (CST) of a source file, for tools such as formatters and
+//! refactorings that must print a file back out exactly as it was, apart from their edits
+//!
+//! The tree comes in two layers:
+//!
+//! * the *green* tree holds the text of every token, trivia (whitespace and comments)
+//!   included, grouped into nodes. Green nodes are immutable and shared through `Rc`, so an
+//!   edit only rebuilds the nodes between the change and the root, sharing the rest
+//! * the *red* tree (`SyntaxNode`) wraps green nodes with their parents and offsets, and is
+//!   created on demand while the tree is walked
+//!
+//! Typed views (see `view`) sit on top of the red tree, giving access to the parts of a
+//! declaration, statement or expression by name
+
+use crate::ast::Module;
+use crate::lexer::token::Token;
+
+mod build;
+pub mod green;
+pub mod red;
+pub mod view;
+
+pub use green::{GreenElement, GreenNode, GreenToken};
+pub use red::{SyntaxElement, SyntaxNode, SyntaxToken};
+
+/// The kind of a node or token of the concrete syntax tree
+///
+/// Node kinds mirror the syntax tree types of `ast`. Significant tokens all have the kind
+/// `TOKEN`, with their `TokType` kept alongside
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum SyntaxKind {
+    MODULE,
+
+    ALIAS_DECL,
+    CLASS_DECL,
+    ENUM_DECL,
+    FUNC_DECL,
+    IMPORT_DECL,
+    LIB_DECL,
+    PROTOCOL_DECL,
+    STRUCT_DECL,
+    TYPE_DECL,
+    VAR_DECL,
+
+    ASSOC_TYPE,
+    CONSTRUCTOR,
+    FIELD,
+    GENERIC_PARAM,
+    PARAM,
+    VARIANT,
+
+    BLOCK,
+    STMT,
+    EXPR,
+    FIELD_INIT,
+    MATCH_ARM,
+    PATTERN,
+    FIELD_PATTERN,
+    TYPE,
+
+    /// an item, member or statement that failed to parse
+    ERROR,
+
+    /// a token of the language, as produced by the lexer
+    TOKEN,
+
+    /// spaces, tabs and line breaks
+    WHITESPACE,
+
+    /// a `//` or `/* */` comment
+    COMMENT,
+}
+
+impl SyntaxKind {
+    /// Returns `true` for whitespace and comments
+    ///
+    #[must_use]
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, SyntaxKind::WHITESPACE | SyntaxKind::COMMENT)
+    }
+}
+
+/// Returns the concrete syntax tree of a source file
+///
+/// Every character of the source ends up in exactly one token of the tree, so printing the
+/// tree gives back the source. Whitespace and comments belong to the innermost node
+/// containing the tokens on either side of them
+///
+/// # Arguments
+///
+/// * `source` - the contents of the file
+/// * `tokens` - the file's tokens, as returned by `lex`
+/// * `module` - the file's syntax tree, as returned by `parse` (or `parse_with_recovery`),
+///   whose spans determine the nodes of the concrete syntax tree
+///
+pub fn build(source: &str, tokens: &[Vec<Token>], module: &Module) -> SyntaxNode {
+    build::build(source, tokens, module)
+}
@@ -0,0 +1,469 @@
+use super::{GreenElement, GreenNode, GreenToken, SyntaxKind, SyntaxNode};
+use crate::ast::{
+    Block, Expr, ExprKind, FnDecl, GenericParam, Item, ItemKind, Member, MemberKind, Module, Param,
+    Pattern, PatternKind, ProtocolMemberKind, Stmt, StmtKind, Type, TypeKind, VarDecl, VariantKind,
+};
+use crate::lexer::token::Token;
+use crate::span::Span;
+use std::rc::Rc;
+
+/// The kind and extent of a node of the concrete syntax tree, as given by the abstract one,
+/// before any tokens are attached to it
+///
+struct Layout {
+    kind: SyntaxKind,
+    span: Span,
+    children: Vec<Layout>,
+}
+
+impl Layout {
+    fn new(kind: SyntaxKind, span: Span, children: Vec<Layout>) -> Self {
+        Self {
+            kind,
+            span,
+            children,
+        }
+    }
+
+    fn leaf(kind: SyntaxKind, span: Span) -> Self {
+        Self::new(kind, span, Vec::new())
+    }
+}
+
+/// A token or run of trivia, with the position of its first character (tokens only)
+///
+struct Piece {
+    token: GreenToken,
+    start: Option<(usize, usize)>,
+}
+
+pub(super) fn build(source: &str, tokens: &[Vec<Token>], module: &Module) -> SyntaxNode {
+    let layout = Layout::new(
+        SyntaxKind::MODULE,
+        module.span,
+        module.items.iter().map(item).collect(),
+    );
+
+    let mut builder = Builder {
+        pieces: pieces(source, tokens),
+        pos: 0,
+    };
+
+    let mut children: Vec<GreenElement> = builder.children(&layout);
+
+    // trailing trivia, and anything the syntax tree does not account for, goes in the root
+    while builder.pos < builder.pieces.len() {
+        children.push(builder.bump());
+    }
+
+    SyntaxNode::new_root(Rc::new(GreenNode::new(SyntaxKind::MODULE, children)))
+}
+
+/// Splits a source file into its tokens and the trivia between them
+///
+fn pieces(source: &str, tokens: &[Vec<Token>]) -> Vec<Piece> {
+    let mut line_starts: Vec<usize> = vec![0];
+    line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+
+    // the byte offset of a (1-based, `char`-counting) line and column
+    let offset = |line: usize, col: usize| -> usize {
+        let start: usize = line_starts.get(line - 1).copied().unwrap_or(source.len());
+        let text: &str = source[start..].split('\n').next().unwrap_or("");
+
+        match text.char_indices().nth(col - 1) {
+            Some((i, _)) => start + i,
+            None => start + text.len(),
+        }
+    };
+
+    let mut pieces: Vec<Piece> = Vec::new();
+    let mut cursor: usize = 0;
+
+    for token in tokens.iter().flatten() {
+        let span: Span = token.span();
+        let start: usize = offset(span.line, span.col).max(cursor);
+        let end: usize = offset(span.end_line, span.end_col).max(start);
+
+        push_trivia(&mut pieces, &source[cursor..start]);
+
+        pieces.push(Piece {
+            token: GreenToken::new(
+                SyntaxKind::TOKEN,
+                Some(token.tok_type().clone()),
+                &source[start..end],
+            ),
+            start: Some((span.line, span.col)),
+        });
+
+        cursor = end;
+    }
+
+    push_trivia(&mut pieces, &source[cursor..]);
+
+    pieces
+}
+
+/// Splits the text between two tokens into whitespace and comments
+///
+fn push_trivia(pieces: &mut Vec<Piece>, mut text: &str) {
+    while !text.is_empty() {
+        let (kind, len): (SyntaxKind, usize) = if text.starts_with("//") {
+            (SyntaxKind::COMMENT, text.find('\n').unwrap_or(text.len()))
+        } else if text.starts_with("/*") {
+            (
+                SyntaxKind::COMMENT,
+                text.find("*/").map_or(text.len(), |i| i + 2),
+            )
+        } else {
+            let len: usize = text
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(text.len());
+
+            // the lexer only skips whitespace and comments, so nothing else is expected here,
+            // but it is kept all the same
+            match len {
+                0 => (
+                    SyntaxKind::COMMENT,
+                    text.find(char::is_whitespace).unwrap_or(text.len()),
+                ),
+                _ => (SyntaxKind::WHITESPACE, len),
+            }
+        };
+
+        pieces.push(Piece {
+            token: GreenToken::new(kind, None, &text[..len]),
+            start: None,
+        });
+
+        text = &text[len..];
+    }
+}
+
+struct Builder {
+    pieces: Vec<Piece>,
+    pos: usize,
+}
+
+impl Builder {
+    /// Returns the green nodes and tokens making up a node, taking the tokens inside the
+    /// node's span, along with the trivia between them
+    ///
+    fn children(&mut self, layout: &Layout) -> Vec<GreenElement> {
+        let mut children: Vec<GreenElement> = Vec::new();
+        let mut nested: Vec<&Layout> = layout.children.iter().collect();
+        nested.sort_by_key(|l| (l.span.line, l.span.col));
+
+        for child in nested {
+            self.bump_until((child.span.line, child.span.col), &mut children);
+
+            // trivia before the child's first token stays outside of it
+            while self.pieces.get(self.pos).is_some_and(|p| p.start.is_none()) {
+                children.push(self.bump());
+            }
+
+            let node = GreenNode::new(child.kind, self.children(child));
+            children.push(GreenElement::NODE(Rc::new(node)));
+        }
+
+        self.bump_until((layout.span.end_line, layout.span.end_col), &mut children);
+
+        children
+    }
+
+    /// Takes every token starting before a position, and the trivia leading up to each
+    ///
+    fn bump_until(&mut self, limit: (usize, usize), children: &mut Vec<GreenElement>) {
+        while let Some(start) = self.pieces[self.pos..].iter().find_map(|p| p.start) {
+            if start >= limit {
+                break;
+            }
+
+            loop {
+                let is_token: bool = self.pieces[self.pos].start.is_some();
+                children.push(self.bump());
+
+                if is_token {
+                    break;
+                }
+            }
+        }
+    }
+
+    fn bump(&mut self) -> GreenElement {
+        let piece: &Piece = &self.pieces[self.pos];
+        self.pos += 1;
+
+        GreenElement::TOKEN(Rc::new(piece.token.clone()))
+    }
+}
+
+fn item(item: &Item) -> Layout {
+    let span: Span = item.span;
+
+    match &item.kind {
+        ItemKind::VAR(decl) => var_decl(decl, span),
+        ItemKind::FUNC(func) => fn_decl(func, span),
+
+        ItemKind::STRUCT(decl) => {
+            let mut children: Vec<Layout> = generics(&decl.generics);
+            children.extend(decl.fields.iter().map(|f| field(f.span, &f.ty, None)));
+            children.extend(decl.members.iter().map(member));
+
+            Layout::new(SyntaxKind::STRUCT_DECL, span, children)
+        }
+
+        ItemKind::ENUM(decl) => {
+            let mut children: Vec<Layout> = generics(&decl.generics);
+
+            for variant in &decl.variants {
+                let mut elems: Vec<Layout> = match &variant.kind {
+                    VariantKind::UNIT => Vec::new(),
+                    VariantKind::TUPLE(types) => types.iter().map(ty).collect(),
+                    VariantKind::STRUCT(fields) => {
+                        fields.iter().map(|f| field(f.span, &f.ty, None)).collect()
+                    }
+                };
+
+                elems.extend(variant.discriminant.iter().map(expr));
+                children.push(Layout::new(SyntaxKind::VARIANT, variant.span, elems));
+            }
+
+            children.extend(decl.members.iter().map(member));
+
+            Layout::new(SyntaxKind::ENUM_DECL, span, children)
+        }
+
+        ItemKind::CLASS(decl) => {
+            let mut children: Vec<Layout> = generics(&decl.generics);
+            children.extend(decl.members.iter().map(member));
+
+            Layout::new(SyntaxKind::CLASS_DECL, span, children)
+        }
+
+        ItemKind::PROTOCOL(decl) => {
+            let children: Vec<Layout> = decl
+                .members
+                .iter()
+                .map(|m| match &m.kind {
+                    ProtocolMemberKind::TYPE(_) => Layout::leaf(SyntaxKind::ASSOC_TYPE, m.span),
+                    ProtocolMemberKind::METHOD(func) => fn_decl(func, m.span),
+                })
+                .collect();
+
+            Layout::new(SyntaxKind::PROTOCOL_DECL, span, children)
+        }
+
+        ItemKind::LIB(_) => Layout::leaf(SyntaxKind::LIB_DECL, span),
+        ItemKind::IMPORT(_) => Layout::leaf(SyntaxKind::IMPORT_DECL, span),
+
+        ItemKind::ALIAS(alias) => {
+            let mut children: Vec<Layout> = generics(&alias.generics);
+            children.push(ty(&alias.ty));
+
+            Layout::new(SyntaxKind::ALIAS_DECL, span, children)
+        }
+
+        ItemKind::TYPE(alias) => {
+            let mut children: Vec<Layout> = generics(&alias.generics);
+            children.push(ty(&alias.ty));
+
+            Layout::new(SyntaxKind::TYPE_DECL, span, children)
+        }
+
+        ItemKind::ERROR => Layout::leaf(SyntaxKind::ERROR, span),
+    }
+}
+
+fn var_decl(decl: &VarDecl, span: Span) -> Layout {
+    let mut children: Vec<Layout> = vec![pattern(&decl.pattern)];
+    children.extend(decl.ty.iter().map(ty));
+    children.extend(decl.value.iter().map(expr));
+
+    Layout::new(SyntaxKind::VAR_DECL, span, children)
+}
+
+fn fn_decl(func: &FnDecl, span: Span) -> Layout {
+    let mut children: Vec<Layout> = generics(&func.generics);
+    children.extend(func.params.iter().map(param));
+    children.extend(func.ret.iter().map(ty));
+    children.extend(func.body.iter().map(block));
+
+    Layout::new(SyntaxKind::FUNC_DECL, span, children)
+}
+
+fn generics(generics: &[GenericParam]) -> Vec<Layout> {
+    generics
+        .iter()
+        .map(|g| Layout::leaf(SyntaxKind::GENERIC_PARAM, g.span))
+        .collect()
+}
+
+fn param(param: &Param) -> Layout {
+    let mut children: Vec<Layout> = vec![pattern(&param.pattern), ty(&param.ty)];
+    children.extend(param.default.iter().map(expr));
+
+    Layout::new(SyntaxKind::PARAM, param.span, children)
+}
+
+fn field(span: Span, field_ty: &Type, value: Option<&Expr>) -> Layout {
+    let mut children: Vec<Layout> = vec![ty(field_ty)];
+    children.extend(value.map(expr));
+
+    Layout::new(SyntaxKind::FIELD, span, children)
+}
+
+fn member(member: &Member) -> Layout {
+    let span: Span = member.span;
+
+    match &member.kind {
+        MemberKind::FIELD(f) => field(span, &f.ty, f.value.as_ref()),
+
+        MemberKind::CONSTRUCTOR(constructor) => {
+            let mut children: Vec<Layout> = constructor.params.iter().map(param).collect();
+            children.push(block(&constructor.body));
+
+            Layout::new(SyntaxKind::CONSTRUCTOR, span, children)
+        }
+
+        MemberKind::METHOD(func) => fn_decl(func, span),
+
+        MemberKind::TYPE(alias) => Layout::new(SyntaxKind::TYPE_DECL, span, vec![ty(&alias.ty)]),
+
+        MemberKind::ERROR => Layout::leaf(SyntaxKind::ERROR, span),
+    }
+}
+
+fn block(block: &Block) -> Layout {
+    let mut children: Vec<Layout> = block.stmts.iter().map(stmt).collect();
+    children.extend(block.tail.iter().map(|e| expr(e)));
+
+    Layout::new(SyntaxKind::BLOCK, block.span, children)
+}
+
+fn stmt(stmt: &Stmt) -> Layout {
+    match &stmt.kind {
+        StmtKind::VAR(decl) => var_decl(decl, stmt.span),
+        StmtKind::EXPR(e) => Layout::new(SyntaxKind::STMT, stmt.span, vec![expr(e)]),
+        StmtKind::ERROR => Layout::leaf(SyntaxKind::ERROR, stmt.span),
+    }
+}
+
+fn expr(e: &Expr) -> Layout {
+    let exprs = |exprs: &[Expr]| -> Vec<Layout> { exprs.iter().map(expr).collect() };
+
+    let children: Vec<Layout> = match &e.kind {
+        ExprKind::LITERAL(_)
+        | ExprKind::IDEN(_)
+        | ExprKind::SELF
+        | ExprKind::SUPER
+        | ExprKind::CONTINUE { .. } => Vec::new(),
+
+        ExprKind::PAREN(inner) | ExprKind::UNARY { expr: inner, .. } => vec![expr(inner)],
+        ExprKind::FIELD { expr: inner, .. } => vec![expr(inner)],
+
+        ExprKind::TUPLE(elems) | ExprKind::ARRAY(elems) => exprs(elems),
+        ExprKind::ARRAY_REPEAT { value, len } => vec![expr(value), expr(len)],
+
+        ExprKind::BLOCK(b) => vec![block(b)],
+
+        ExprKind::BINARY { lhs, rhs, .. } => vec![expr(lhs), expr(rhs)],
+        ExprKind::TERNARY { cond, then, els } => vec![expr(cond), expr(then), expr(els)],
+        ExprKind::RANGE { start, end, .. } => vec![expr(start), expr(end)],
+        ExprKind::ASSIGN { target, value, .. } => vec![expr(target), expr(value)],
+        ExprKind::INDEX { expr: inner, index } => vec![expr(inner), expr(index)],
+
+        ExprKind::CALL { callee, args } => {
+            let mut children: Vec<Layout> = vec![expr(callee)];
+            children.extend(exprs(args));
+            children
+        }
+
+        ExprKind::IF { cond, then, els } => {
+            let mut children: Vec<Layout> = vec![expr(cond), block(then)];
+            children.extend(els.iter().map(|e| expr(e)));
+            children
+        }
+
+        ExprKind::MATCH { scrutinee, arms } => {
+            let mut children: Vec<Layout> = vec![expr(scrutinee)];
+
+            for arm in arms {
+                let mut parts: Vec<Layout> = vec![pattern(&arm.pattern)];
+                parts.extend(arm.guard.iter().map(expr));
+                parts.push(expr(&arm.body));
+
+                children.push(Layout::new(SyntaxKind::MATCH_ARM, arm.span, parts));
+            }
+
+            children
+        }
+
+        ExprKind::WHILE { cond, body, .. } => vec![expr(cond), block(body)],
+        ExprKind::LOOP { body, .. } => vec![block(body)],
+        ExprKind::FOR {
+            pattern: pat,
+            iter,
+            body,
+            ..
+        } => vec![pattern(pat), expr(iter), block(body)],
+
+        ExprKind::BREAK { value, .. } | ExprKind::RETURN(value) => {
+            value.iter().map(|e| expr(e)).collect()
+        }
+
+        ExprKind::NEW { args, .. } => exprs(args),
+
+        ExprKind::STRUCT { fields, .. } => fields
+            .iter()
+            .map(|f| Layout::new(SyntaxKind::FIELD_INIT, f.span, vec![expr(&f.value)]))
+            .collect(),
+    };
+
+    Layout::new(SyntaxKind::EXPR, e.span, children)
+}
+
+fn pattern(p: &Pattern) -> Layout {
+    let children: Vec<Layout> = match &p.kind {
+        PatternKind::WILDCARD
+        | PatternKind::BINDING(_)
+        | PatternKind::LITERAL(_)
+        | PatternKind::RANGE { .. } => Vec::new(),
+
+        PatternKind::TUPLE(elems) | PatternKind::OR(elems) => elems.iter().map(pattern).collect(),
+
+        PatternKind::VARIANT { elems, .. } => elems.iter().flatten().map(pattern).collect(),
+
+        PatternKind::STRUCT { fields, .. } => fields
+            .iter()
+            .map(|f| Layout::new(SyntaxKind::FIELD_PATTERN, f.span, vec![pattern(&f.pattern)]))
+            .collect(),
+    };
+
+    Layout::new(SyntaxKind::PATTERN, p.span, children)
+}
+
+fn ty(t: &Type) -> Layout {
+    let types = |types: &[Type]| -> Vec<Layout> { types.iter().map(ty).collect() };
+
+    let children: Vec<Layout> = match &t.kind {
+        TypeKind::BOOL
+        | TypeKind::CHAR
+        | TypeKind::FLOAT
+        | TypeKind::INT
+        | TypeKind::STRING
+        | TypeKind::UINT => Vec::new(),
+
+        TypeKind::NAMED { args, .. } => types(args),
+        TypeKind::ARRAY(elem) | TypeKind::OPTIONAL(elem) => vec![ty(elem)],
+        TypeKind::FIXED_ARRAY { elem, len } => vec![ty(elem), expr(len)],
+        TypeKind::TUPLE(elems) => types(elems),
+
+        TypeKind::FUNC { params, ret } => {
+            let mut children: Vec<Layout> = types(params);
+            children.extend(ret.iter().map(|r| ty(r)));
+            children
+        }
+    };
+
+    Layout::new(SyntaxKind::TYPE, t.span, children)
+}
@@ -0,0 +1,152 @@
+use super::SyntaxKind;
+use crate::lexer::token::TokType;
+use std::fmt;
+use std::rc::Rc;
+
+/// An immutable node of the concrete syntax tree, which knows its kind, its children and the
+/// length of its text, but not its position in the file
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct GreenNode {
+    kind: SyntaxKind,
+    text_len: usize,
+    children: Vec<GreenElement>,
+}
+
+impl GreenNode {
+    pub fn new(kind: SyntaxKind, children: Vec<GreenElement>) -> Self {
+        let text_len: usize = children.iter().map(GreenElement::text_len).sum();
+
+        Self {
+            kind,
+            text_len,
+            children,
+        }
+    }
+
+    pub fn kind(&self) -> SyntaxKind {
+        self.kind
+    }
+
+    /// Returns the length of the node's text, in bytes
+    ///
+    pub fn text_len(&self) -> usize {
+        self.text_len
+    }
+
+    pub fn children(&self) -> &[GreenElement] {
+        &self.children
+    }
+
+    /// Returns a copy of the node with one of its children replaced. The other children are
+    /// shared with this node rather than copied
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - the position of the child among the node's children
+    /// * `child` - the new child
+    ///
+    #[must_use]
+    pub fn replace_child(&self, index: usize, child: GreenElement) -> GreenNode {
+        let mut children: Vec<GreenElement> = self.children.clone();
+        children[index] = child;
+
+        GreenNode::new(self.kind, children)
+    }
+}
+
+impl fmt::Display for GreenNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.children.iter().try_for_each(|c| write!(f, "{}", c))
+    }
+}
+
+/// An immutable token of the concrete syntax tree, along with its exact text
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct GreenToken {
+    kind: SyntaxKind,
+    tok_type: Option<TokType>,
+    text: String,
+}
+
+impl GreenToken {
+    /// Returns a token of the given kind
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - `TOKEN` for a token of the language, or a kind of trivia
+    /// * `tok_type` - the type of the token, unless it is trivia
+    /// * `text` - the token's text, exactly as in the source
+    ///
+    pub fn new(kind: SyntaxKind, tok_type: Option<TokType>, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            tok_type,
+            text: text.into(),
+        }
+    }
+
+    /// Returns a token of the language, written the way the lexer reads it (e.g. to insert
+    /// a new identifier)
+    ///
+    pub fn from_tok_type(tok_type: TokType) -> Self {
+        let text: String = tok_type.to_string();
+
+        Self::new(SyntaxKind::TOKEN, Some(tok_type), text)
+    }
+
+    pub fn kind(&self) -> SyntaxKind {
+        self.kind
+    }
+
+    pub fn tok_type(&self) -> Option<&TokType> {
+        self.tok_type.as_ref()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for GreenToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// A child of a green node
+///
+#[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum GreenElement {
+    NODE(Rc<GreenNode>),
+    TOKEN(Rc<GreenToken>),
+}
+
+impl GreenElement {
+    pub fn kind(&self) -> SyntaxKind {
+        match self {
+            GreenElement::NODE(node) => node.kind(),
+            GreenElement::TOKEN(token) => token.kind(),
+        }
+    }
+
+    /// Returns the length of the element's text, in bytes
+    ///
+    pub fn text_len(&self) -> usize {
+        match self {
+            GreenElement::NODE(node) => node.text_len(),
+            GreenElement::TOKEN(token) => token.text().len(),
+        }
+    }
+}
+
+impl fmt::Display for GreenElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GreenElement::NODE(node) => write!(f, "{}", node),
+            GreenElement::TOKEN(token) => write!(f, "{}", token),
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use super::{GreenElement, GreenNode, GreenToken, SyntaxKind};
+use crate::lexer::token::TokType;
+use std::fmt;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// A node of the concrete syntax tree, positioned in its file
+///
+/// Syntax nodes are created on demand as the tree is walked, each holding its green node and
+/// its parent. Cloning one only bumps a reference count
+///
+#[derive(Clone)]
+pub struct SyntaxNode(Rc<NodeData>);
+
+struct NodeData {
+    green: Rc<GreenNode>,
+    parent: Option<SyntaxNode>,
+
+    /// the node's position among its parent's children
+    index: usize,
+
+    /// the byte offset of the node's text in the file
+    offset: usize,
+}
+
+impl SyntaxNode {
+    /// Returns the root of a tree
+    ///
+    pub fn new_root(green: Rc<GreenNode>) -> Self {
+        Self(Rc::new(NodeData {
+            green,
+            parent: None,
+            index: 0,
+            offset: 0,
+        }))
+    }
+
+    pub fn kind(&self) -> SyntaxKind {
+        self.0.green.kind()
+    }
+
+    pub fn green(&self) -> &Rc<GreenNode> {
+        &self.0.green
+    }
+
+    pub fn parent(&self) -> Option<&SyntaxNode> {
+        self.0.parent.as_ref()
+    }
+
+    /// Returns the byte range of the node's text in the file
+    ///
+    pub fn text_range(&self) -> Range<usize> {
+        self.0.offset..self.0.offset + self.0.green.text_len()
+    }
+
+    /// Returns the node's child nodes and tokens, trivia included
+    ///
+    pub fn children_with_tokens(&self) -> impl Iterator<Item = SyntaxElement> + '_ {
+        let mut offset: usize = self.0.offset;
+
+        self.0
+            .green
+            .children()
+            .iter()
+            .enumerate()
+            .map(move |(index, child)| {
+                let child_offset: usize = offset;
+                offset += child.text_len();
+
+                match child {
+                    GreenElement::NODE(green) => {
+                        SyntaxElement::NODE(SyntaxNode(Rc::new(NodeData {
+                            green: Rc::clone(green),
+                            parent: Some(self.clone()),
+                            index,
+                            offset: child_offset,
+                        })))
+                    }
+
+                    GreenElement::TOKEN(green) => SyntaxElement::TOKEN(SyntaxToken {
+                        green: Rc::clone(green),
+                        parent: self.clone(),
+                        index,
+                        offset: child_offset,
+                    }),
+                }
+            })
+    }
+
+    /// Returns the node's child nodes
+    ///
+    pub fn children(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.children_with_tokens().filter_map(|c| match c {
+            SyntaxElement::NODE(node) => Some(node),
+            SyntaxElement::TOKEN(_) => None,
+        })
+    }
+
+    /// Returns the node's child tokens, excluding trivia
+    ///
+    pub fn tokens(&self) -> impl Iterator<Item = SyntaxToken> + '_ {
+        self.children_with_tokens().filter_map(|c| match c {
+            SyntaxElement::TOKEN(token) if !token.kind().is_trivia() => Some(token),
+            _ => None,
+        })
+    }
+
+    /// Returns the node and all of the nodes below it, in the order they appear in the file
+    ///
+    pub fn descendants(&self) -> Vec<SyntaxNode> {
+        let mut nodes: Vec<SyntaxNode> = vec![self.clone()];
+
+        for child in self.children() {
+            nodes.extend(child.descendants());
+        }
+
+        nodes
+    }
+
+    /// Returns the first child token of the given type
+    ///
+    pub fn find_token(&self, f: impl Fn(&TokType) -> bool) -> Option<SyntaxToken> {
+        self.tokens().find(|t| t.tok_type().is_some_and(&f))
+    }
+
+    /// Returns the root of a copy of the tree in which this node is replaced. Only the
+    /// ancestors of the node are copied, and everything else is shared with this tree
+    ///
+    /// # Arguments
+    ///
+    /// * `replacement` - the node to put in this node's place
+    ///
+    #[must_use]
+    pub fn replace_with(&self, replacement: GreenNode) -> SyntaxNode {
+        let mut green: GreenNode = replacement;
+        let mut node: &SyntaxNode = self;
+
+        while let Some(parent) = node.parent() {
+            let child = GreenElement::NODE(Rc::new(green));
+            green = parent.green().replace_child(node.0.index, child);
+            node = parent;
+        }
+
+        SyntaxNode::new_root(Rc::new(green))
+    }
+}
+
+impl fmt::Display for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.green)
+    }
+}
+
+impl fmt::Debug for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}@{:?}", self.kind(), self.text_range())
+    }
+}
+
+/// A token of the concrete syntax tree, positioned in its file
+///
+#[derive(Clone)]
+pub struct SyntaxToken {
+    green: Rc<GreenToken>,
+    parent: SyntaxNode,
+    index: usize,
+    offset: usize,
+}
+
+impl SyntaxToken {
+    pub fn kind(&self) -> SyntaxKind {
+        self.green.kind()
+    }
+
+    pub fn tok_type(&self) -> Option<&TokType> {
+        self.green.tok_type()
+    }
+
+    pub fn text(&self) -> &str {
+        self.green.text()
+    }
+
+    pub fn green(&self) -> &Rc<GreenToken> {
+        &self.green
+    }
+
+    pub fn parent(&self) -> &SyntaxNode {
+        &self.parent
+    }
+
+    /// Returns the byte range of the token's text in the file
+    ///
+    pub fn text_range(&self) -> Range<usize> {
+        self.offset..self.offset + self.green.text().len()
+    }
+
+    /// Returns the root of a copy of the tree in which this token is replaced (see
+    /// `SyntaxNode::replace_with`)
+    ///
+    /// # Arguments
+    ///
+    /// * `replacement` - the token to put in this token's place
+    ///
+    #[must_use]
+    pub fn replace_with(&self, replacement: GreenToken) -> SyntaxNode {
+        let child = GreenElement::TOKEN(Rc::new(replacement));
+        let parent: GreenNode = self.parent.green().replace_child(self.index, child);
+
+        self.parent.replace_with(parent)
+    }
+}
+
+impl fmt::Display for SyntaxToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.green)
+    }
+}
+
+impl fmt::Debug for SyntaxToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}@{:?} {:?}",
+            self.kind(),
+            self.text_range(),
+            self.text()
+        )
+    }
+}
+
+/// A child of a syntax node
+///
+#[derive(Debug, Clone)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum SyntaxElement {
+    NODE(SyntaxNode),
+    TOKEN(SyntaxToken),
+}
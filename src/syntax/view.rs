@@ -0,0 +1,212 @@
+//! Typed views of syntax nodes
+//!
+//! Each view wraps a `SyntaxNode` of one kind, naming its parts (e.g. the name and body of a
+//! function). Views hold no data of their own, so edits go through the node they wrap
+
+use super::{SyntaxKind, SyntaxNode, SyntaxToken};
+use crate::lexer::token::TokType;
+
+/// A syntax node known to be of a particular kind
+///
+pub trait AstView: Sized {
+    /// Returns a view of the node, if it is of the view's kind
+    ///
+    fn cast(node: SyntaxNode) -> Option<Self>;
+
+    /// Returns the node behind the view
+    ///
+    fn syntax(&self) -> &SyntaxNode;
+}
+
+macro_rules! views {
+    ($($(#[$doc:meta])* $name:ident => $kind:ident,)*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone)]
+            pub struct $name(SyntaxNode);
+
+            impl AstView for $name {
+                fn cast(node: SyntaxNode) -> Option<Self> {
+                    (node.kind() == SyntaxKind::$kind).then_some(Self(node))
+                }
+
+                fn syntax(&self) -> &SyntaxNode {
+                    &self.0
+                }
+            }
+        )*
+    };
+}
+
+views! {
+    /// The root of a file
+    ///
+    SourceFile => MODULE,
+
+    /// A function or method
+    ///
+    FuncDecl => FUNC_DECL,
+
+    /// A `let`, `var` or `const` declaration
+    ///
+    VarDecl => VAR_DECL,
+
+    /// A struct declaration
+    ///
+    StructDecl => STRUCT_DECL,
+
+    /// A class declaration
+    ///
+    ClassDecl => CLASS_DECL,
+
+    /// A field of a struct, enum variant or class
+    ///
+    Field => FIELD,
+
+    /// A function parameter
+    ///
+    Param => PARAM,
+
+    /// A list of statements between curly braces
+    ///
+    Block => BLOCK,
+
+    Expr => EXPR,
+    Pattern => PATTERN,
+    TypeRef => TYPE,
+}
+
+/// Returns the first child of a node that fits a view
+///
+fn child<T: AstView>(node: &SyntaxNode) -> Option<T> {
+    node.children().find_map(T::cast)
+}
+
+/// Returns the first identifier directly within a node, which for a declaration is its name
+///
+fn name(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.find_token(TokType::is_iden)
+}
+
+impl SourceFile {
+    /// Returns the nodes of the file's items
+    ///
+    pub fn items(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.0.children()
+    }
+
+    pub fn funcs(&self) -> impl Iterator<Item = FuncDecl> + '_ {
+        self.0.children().filter_map(FuncDecl::cast)
+    }
+}
+
+impl FuncDecl {
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    pub fn params(&self) -> impl Iterator<Item = Param> + '_ {
+        self.0.children().filter_map(Param::cast)
+    }
+
+    /// Returns the return type, if one is declared
+    ///
+    pub fn ret(&self) -> Option<TypeRef> {
+        child(&self.0)
+    }
+
+    /// Returns the body, unless the function is `extern`, `abstract` or a required protocol
+    /// method
+    ///
+    pub fn body(&self) -> Option<Block> {
+        child(&self.0)
+    }
+}
+
+impl VarDecl {
+    pub fn pattern(&self) -> Option<Pattern> {
+        child(&self.0)
+    }
+
+    pub fn ty(&self) -> Option<TypeRef> {
+        child(&self.0)
+    }
+
+    pub fn value(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl StructDecl {
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = Field> + '_ {
+        self.0.children().filter_map(Field::cast)
+    }
+
+    pub fn methods(&self) -> impl Iterator<Item = FuncDecl> + '_ {
+        self.0.children().filter_map(FuncDecl::cast)
+    }
+}
+
+impl ClassDecl {
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    /// Returns the nodes of the class's fields, constructors, methods and type bindings
+    ///
+    pub fn members(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.0
+            .children()
+            .filter(|n| n.kind() != SyntaxKind::GENERIC_PARAM)
+    }
+}
+
+impl Field {
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    pub fn ty(&self) -> Option<TypeRef> {
+        child(&self.0)
+    }
+
+    /// Returns the initial value of a class field, if it has one
+    ///
+    pub fn value(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl Param {
+    pub fn pattern(&self) -> Option<Pattern> {
+        child(&self.0)
+    }
+
+    pub fn ty(&self) -> Option<TypeRef> {
+        child(&self.0)
+    }
+
+    /// Returns the default value, if there is one
+    ///
+    pub fn default(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl Block {
+    /// Returns the nodes of the block's statements, excluding its tail
+    ///
+    pub fn stmts(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.0.children().filter(|n| n.kind() != SyntaxKind::EXPR)
+    }
+
+    /// Returns the expression at the end of the block, which gives the block its value
+    ///
+    pub fn tail(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}